@@ -4,6 +4,14 @@ Alkahest Python bindings for ERC20, ERC721, ERC1155, and token bundle operations
 
 from .alkahest_py import (
     PyAlkahestClient as AlkahestClient,
+    PySigner as Signer,
+    PySyncClient as SyncClient,
+    PyTxOptions as TxOptions,
+    PyRetryConfig as RetryConfig,
+    PySimulationResult as SimulationResult,
+    PyTransactionReceipt as TransactionReceipt,
+    PyPendingTransaction as PendingTransaction,
+    PyUnsignedTransaction as UnsignedTransaction,
     EnvTestManager as EnvTestManager,
     PyMockERC20 as MockERC20,
     PyMockERC721 as MockERC721,
@@ -11,10 +19,12 @@ from .alkahest_py import (
     PyWalletProvider as WalletProvider,
     PyERC20EscrowObligationData as ERC20EscrowObligationData,
     PyERC20PaymentObligationData as ERC20PaymentObligationData,
+    PyPermitSignature as PermitSignature,
     PyERC721EscrowObligationData as ERC721EscrowObligationData,
     PyERC721PaymentObligationData as ERC721PaymentObligationData,
     PyERC1155EscrowObligationData as ERC1155EscrowObligationData,
     PyERC1155PaymentObligationData as ERC1155PaymentObligationData,
+    PyTokenBundlePaymentObligationData as TokenBundlePaymentObligationData,
     StringObligationClient,
     PyStringObligationData as StringObligationData,
     OracleClient,
@@ -29,6 +39,9 @@ from .alkahest_py import (
     PyTrustedOracleArbiterDemandData as TrustedOracleArbiterDemandData,
     PyEscrowParams as EscrowParams,
     PyEscrowArbitrationResult as EscrowArbitrationResult,
+    PyEscrowSubscriptionResult as EscrowSubscriptionResult,
+    PyVerdict as Verdict,
+    PyDecisionStream as DecisionStream,
     PyErc20Data as Erc20Data,
     # Address Configuration Classes
     PyErc20Addresses as Erc20Addresses,
@@ -47,10 +60,29 @@ from .alkahest_py import (
     PyRevocationRequestData as RevocationRequestData,
     PyRevoked as Revoked,
     PyTimestamped as Timestamped,
+    decode_revert,
+)
+from .exceptions import (
+    AlkahestError,
+    RpcError,
+    TransactionReverted,
+    TransactionCancelled,
+    InsufficientAllowance,
+    InvalidAddress,
+    DecodeError,
+    ExtensionNotAvailable,
 )
 
 __all__ = [
     "AlkahestClient",
+    "Signer",
+    "SyncClient",
+    "TxOptions",
+    "RetryConfig",
+    "SimulationResult",
+    "TransactionReceipt",
+    "PendingTransaction",
+    "UnsignedTransaction",
     "EnvTestManager", 
     "MockERC20",
     "MockERC721",
@@ -58,10 +90,12 @@ __all__ = [
     "WalletProvider",
     "ERC20EscrowObligationData",
     "ERC20PaymentObligationData",
+    "PermitSignature",
     "ERC721EscrowObligationData",
     "ERC721PaymentObligationData",
     "ERC1155EscrowObligationData",
     "ERC1155PaymentObligationData",
+    "TokenBundlePaymentObligationData",
     "StringObligationClient",
     "StringObligationData",
     "DecodedAttestation",
@@ -77,6 +111,9 @@ __all__ = [
     "TrustedOracleArbiterDemandData",
     "EscrowParams",
     "EscrowArbitrationResult",
+    "EscrowSubscriptionResult",
+    "Verdict",
+    "DecisionStream",
     "Erc20Data",
     # Address Configuration Classes
     "Erc20Addresses",
@@ -95,4 +132,14 @@ __all__ = [
     "RevocationRequestData",
     "Revoked",
     "Timestamped",
+    "decode_revert",
+    # Exceptions
+    "AlkahestError",
+    "RpcError",
+    "TransactionReverted",
+    "TransactionCancelled",
+    "InsufficientAllowance",
+    "InvalidAddress",
+    "DecodeError",
+    "ExtensionNotAvailable",
 ]
\ No newline at end of file
@@ -0,0 +1,57 @@
+"""
+Exceptions raised by alkahest-py.
+
+Every error derives from AlkahestError. Transaction failures carry the
+transaction hash, target contract and raw revert data where they're known.
+"""
+
+
+class AlkahestError(Exception):
+    """Base class for all alkahest-py errors."""
+
+    tx_hash = None
+    contract_address = None
+    revert_data = None
+
+
+class RpcError(AlkahestError):
+    """The RPC endpoint failed, timed out or rejected the request."""
+
+
+class TransactionReverted(AlkahestError):
+    """A transaction reverted on chain or during gas estimation.
+
+    When the revert data matches a known Alkahest, EAS or token error, its name
+    is in ``error_name`` (e.g. ``"InvalidFulfillment"``, or ``"Error"`` for a
+    ``require`` message) and its arguments in ``error_args``, a tuple in
+    declaration order.
+    """
+
+    error_name = None
+    error_args = None
+
+
+class TransactionCancelled(AlkahestError):
+    """A pending transaction was replaced by its cancellation, whose hash is in ``cancelled_by``."""
+
+    cancelled_by = None
+
+
+class InsufficientAllowance(TransactionReverted):
+    """A token transfer reverted because the spender wasn't approved for enough."""
+
+
+class InvalidAddress(AlkahestError, ValueError):
+    """A string couldn't be parsed as an address. The input is kept in ``value``."""
+
+    value = None
+
+
+class DecodeError(AlkahestError, ValueError):
+    """ABI data, or a value like a UID or transaction hash, couldn't be decoded into the expected type."""
+
+
+class ExtensionNotAvailable(AlkahestError, AttributeError):
+    """The client wasn't built with the requested extension, named in ``extension``."""
+
+    extension = None
@@ -0,0 +1,84 @@
+import pytest
+from alkahest_py import (
+    ArbitrateOptions,
+    AttestationFilter,
+    ERC20PaymentObligationData,
+    EnvTestManager,
+    FulfillmentParams,
+    MockERC20,
+    StringObligationData,
+)
+
+
+@pytest.mark.asyncio
+async def test_arbitrate_erc20_payments():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+
+    price = {"address": env.mock_addresses.erc20_a, "value": 60}
+    await env.alice_client.erc20.approve(price, "payment")
+    payment = await env.alice_client.erc20.pay_with_erc20(price, env.bob)
+    payment_uid = payment["log"]["uid"]
+
+    filter_obj = AttestationFilter(
+        attester=env.addresses.erc20_addresses.payment_obligation,
+        uid=payment_uid,
+        from_block=0,
+    )
+    # The class itself selects how fulfillments are decoded
+    params = FulfillmentParams(ERC20PaymentObligationData, filter_obj)
+    assert params.obligation_abi is ERC20PaymentObligationData
+
+    seen = []
+
+    def decision_function(obligation):
+        seen.append(obligation)
+        return obligation.amount >= 50
+
+    result = await env.bob_client.oracle.arbitrate_past_sync(
+        params, decision_function, ArbitrateOptions()
+    )
+
+    assert len(seen) == 1
+    assert isinstance(seen[0], ERC20PaymentObligationData)
+    assert seen[0].token.lower() == env.mock_addresses.erc20_a.lower()
+    assert seen[0].payee.lower() == env.bob.lower()
+
+    assert result.total_count == 1 and result.successful_count == 1
+    decision = result.decisions[0]
+    assert decision.attestation.uid == payment_uid
+    assert isinstance(decision.obligation_data, ERC20PaymentObligationData)
+    assert decision.obligation_data.amount == 60
+
+
+@pytest.mark.asyncio
+async def test_arbitrate_raw_bytes():
+    env = EnvTestManager()
+    fulfillment_uid = await env.bob_client.string_obligation.do_obligation(
+        "custom", None
+    )
+
+    filter_obj = AttestationFilter(
+        attester=env.addresses.string_obligation_addresses.obligation,
+        uid=fulfillment_uid,
+        from_block=0,
+    )
+
+    def decision_function(data):
+        assert isinstance(data, bytes)
+        return StringObligationData.decode(data).item == "custom"
+
+    result = await env.bob_client.oracle.arbitrate_past_sync(
+        FulfillmentParams(bytes, filter_obj), decision_function, None
+    )
+
+    assert result.successful_count == 1
+    assert result.decisions[0].obligation_data == StringObligationData(
+        "custom"
+    ).encode_self()
+
+
+def test_obligation_abi_must_be_supported():
+    with pytest.raises(TypeError):
+        FulfillmentParams(int, AttestationFilter())
@@ -97,7 +97,7 @@ async def test_arbitrate_past_sync():
     )
     
     # Verify collection receipt
-    assert not (not collection_receipt or not collection_receipt.startswith('0x')), "Invalid collection receipt: {collection_receipt}"
+    assert not (not collection_receipt or not collection_receipt.transaction_hash.startswith('0x')), "Invalid collection receipt: {collection_receipt}"
     
     # For compatibility with run_all_tests.py
 @pytest.mark.asyncio
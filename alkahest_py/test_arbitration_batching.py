@@ -0,0 +1,116 @@
+import asyncio
+import pytest
+from alkahest_py import (
+    ArbitrateOptions,
+    AttestationFilter,
+    EnvTestManager,
+    FulfillmentParams,
+    StringObligationData,
+    Verdict,
+)
+
+
+def string_fulfillments(env):
+    return FulfillmentParams(
+        StringObligationData,
+        AttestationFilter(
+            attester=env.addresses.string_obligation_addresses.obligation,
+            recipient=env.bob,
+            from_block=0,
+        ),
+    )
+
+
+async def make_obligations(env, *items):
+    for item in items:
+        await env.bob_client.string_obligation.do_obligation(item, None)
+
+
+@pytest.mark.asyncio
+async def test_batched_decision_function():
+    env = EnvTestManager()
+    await make_obligations(env, "good", "bad", "good", "bad", "good")
+    batches = []
+
+    def decision_function(obligations):
+        batches.append(list(obligations))
+        return [
+            Verdict(True) if obligation == "good" else False
+            for obligation in obligations
+        ]
+
+    result = await env.bob_client.oracle.arbitrate_past_sync(
+        string_fulfillments(env), decision_function, ArbitrateOptions(batch_size=2)
+    )
+
+    assert [len(batch) for batch in batches] == [2, 2, 1]
+    assert result.total_count == 5
+    assert result.successful_count == 3
+
+
+@pytest.mark.asyncio
+async def test_max_concurrency_limits_calls():
+    env = EnvTestManager()
+    await make_obligations(env, "a", "b", "c", "d")
+    in_flight = 0
+    most_in_flight = 0
+
+    async def decision_function(obligation):
+        nonlocal in_flight, most_in_flight
+        in_flight += 1
+        most_in_flight = max(most_in_flight, in_flight)
+        await asyncio.sleep(0.1)
+        in_flight -= 1
+        return True
+
+    result = await env.bob_client.oracle.arbitrate_past_sync(
+        string_fulfillments(env),
+        decision_function,
+        ArbitrateOptions(max_concurrency=2),
+    )
+
+    assert result.successful_count == 4
+    assert most_in_flight == 2
+
+
+@pytest.mark.asyncio
+async def test_pipelined_submissions():
+    env = EnvTestManager()
+    items = [f"item {i}" for i in range(6)]
+    await make_obligations(env, *items)
+
+    result = await env.bob_client.oracle.arbitrate_past_sync(
+        string_fulfillments(env),
+        lambda obligation: True,
+        ArbitrateOptions(pipeline_depth=3),
+    )
+
+    assert sorted(d.obligation_data for d in result.decisions) == items
+    assert len({d.transaction_hash for d in result.decisions}) == 6
+
+
+@pytest.mark.asyncio
+async def test_wrong_number_of_verdicts():
+    env = EnvTestManager()
+    await make_obligations(env, "a", "b")
+
+    result = await env.bob_client.oracle.arbitrate_past_sync(
+        string_fulfillments(env), lambda obligations: [True], ArbitrateOptions(batch_size=2)
+    )
+    assert result.decisions == []
+
+    with pytest.raises(ValueError, match="1 verdicts for 2 obligations"):
+        await env.bob_client.oracle.arbitrate_past_sync(
+            string_fulfillments(env),
+            lambda obligations: [True],
+            ArbitrateOptions(batch_size=2, on_error="abort"),
+        )
+
+
+def test_limits_must_be_positive():
+    with pytest.raises(ValueError):
+        ArbitrateOptions(batch_size=0)
+    with pytest.raises(ValueError):
+        ArbitrateOptions(pipeline_depth=0)
+    options = ArbitrateOptions()
+    assert options.max_concurrency is None and options.pipeline_depth == 1
@@ -0,0 +1,122 @@
+import json
+import pytest
+from alkahest_py import (
+    ArbitrateOptions,
+    AttestationFilter,
+    EnvTestManager,
+    FulfillmentParams,
+    StringObligationData,
+)
+
+
+def string_fulfillments(env):
+    return FulfillmentParams(
+        StringObligationData,
+        AttestationFilter(
+            attester=env.addresses.string_obligation_addresses.obligation,
+            recipient=env.bob,
+            from_block=0,
+        ),
+    )
+
+
+@pytest.mark.asyncio
+async def test_arbitrate_past_resumes(tmp_path):
+    env = EnvTestManager()
+    oracle = env.bob_client.oracle
+    options = ArbitrateOptions(checkpoint=str(tmp_path / "checkpoint.json"))
+    await env.bob_client.string_obligation.do_obligation("first", None)
+
+    result = await oracle.arbitrate_past_sync(
+        string_fulfillments(env), lambda _: True, options
+    )
+    assert [d.obligation_data for d in result.decisions] == ["first"]
+    saved = json.loads((tmp_path / "checkpoint.json").read_text())
+    [entry] = saved.values()
+    assert entry["last_block"] > 0 and entry["arbitrated"] == []
+
+    # Nothing new, so nothing is decided again
+    result = await oracle.arbitrate_past_sync(
+        string_fulfillments(env), lambda _: True, options
+    )
+    assert result.decisions == []
+
+    await env.bob_client.string_obligation.do_obligation("second", None)
+    result = await oracle.arbitrate_past_sync(
+        string_fulfillments(env), lambda _: True, options
+    )
+    assert [d.obligation_data for d in result.decisions] == ["second"]
+
+
+@pytest.mark.asyncio
+async def test_listener_catches_up_after_restart(tmp_path):
+    env = EnvTestManager()
+    oracle = env.bob_client.oracle
+    checkpoint = str(tmp_path / "checkpoint.json")
+    await oracle.arbitrate_past_sync(
+        string_fulfillments(env), lambda _: True, ArbitrateOptions(checkpoint=checkpoint)
+    )
+
+    # Made while the oracle was down
+    await env.bob_client.string_obligation.do_obligation("missed", None)
+
+    listener = await oracle.listen_and_arbitrate(
+        string_fulfillments(env),
+        lambda _: True,
+        None,
+        ArbitrateOptions(only_new=True, checkpoint=checkpoint),
+    )
+    assert [d.obligation_data for d in listener.initial_decisions] == ["missed"]
+    listener.stop()
+
+
+def test_rejects_other_files(tmp_path):
+    env = EnvTestManager()
+    path = tmp_path / "not-a-checkpoint.json"
+    path.write_text('{"hello": "world"}')
+
+    with pytest.raises(Exception, match="not an arbitration checkpoint"):
+        env.bob_client.sync.oracle.arbitrate_past_sync(
+            string_fulfillments(env),
+            lambda _: True,
+            ArbitrateOptions(checkpoint=str(path)),
+        )
+
+
+@pytest.mark.asyncio
+async def test_queries_share_a_file(tmp_path):
+    env = EnvTestManager()
+    oracle = env.bob_client.oracle
+    options = ArbitrateOptions(checkpoint=str(tmp_path / "checkpoint.json"))
+    await env.bob_client.string_obligation.do_obligation("shared", None)
+
+    others = FulfillmentParams(
+        StringObligationData,
+        AttestationFilter(
+            attester=env.addresses.string_obligation_addresses.obligation,
+            recipient=env.alice,
+            from_block=0,
+        ),
+    )
+    await oracle.arbitrate_past_sync(string_fulfillments(env), lambda _: True, options)
+    await oracle.arbitrate_past_sync(others, lambda _: True, options)
+
+    # The second query didn't overwrite the first one's progress
+    saved = json.loads((tmp_path / "checkpoint.json").read_text())
+    assert len(saved) == 2
+    result = await oracle.arbitrate_past_sync(string_fulfillments(env), lambda _: True, options)
+    assert result.decisions == []
+
+
+@pytest.mark.asyncio
+async def test_query_in_use_is_rejected(tmp_path):
+    env = EnvTestManager()
+    oracle = env.bob_client.oracle
+    options = ArbitrateOptions(only_new=True, checkpoint=str(tmp_path / "checkpoint.json"))
+    listener = await oracle.listen_and_arbitrate(
+        string_fulfillments(env), lambda _: True, None, options
+    )
+
+    with pytest.raises(Exception, match="already in use"):
+        await oracle.arbitrate_past_sync(string_fulfillments(env), lambda _: True, options)
+    listener.stop()
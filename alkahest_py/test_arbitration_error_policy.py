@@ -0,0 +1,106 @@
+import pytest
+from alkahest_py import (
+    ArbitrateOptions,
+    AttestationFilter,
+    EnvTestManager,
+    FulfillmentParams,
+    StringObligationData,
+)
+
+
+def string_fulfillments(env):
+    return FulfillmentParams(
+        StringObligationData,
+        AttestationFilter(
+            attester=env.addresses.string_obligation_addresses.obligation,
+            recipient=env.bob,
+            from_block=0,
+        ),
+    )
+
+
+async def make_obligations(env, *items):
+    for item in items:
+        await env.bob_client.string_obligation.do_obligation(item, None)
+
+
+@pytest.mark.asyncio
+async def test_skip_leaves_fulfillment_undecided():
+    env = EnvTestManager()
+    await make_obligations(env, "good", "broken")
+
+    def decision_function(obligation):
+        if obligation == "broken":
+            raise ValueError("can't decide")
+        return True
+
+    result = await env.bob_client.oracle.arbitrate_past_sync(
+        string_fulfillments(env), decision_function, ArbitrateOptions(on_error="skip")
+    )
+
+    assert [d.obligation_data for d in result.decisions] == ["good"]
+
+
+@pytest.mark.asyncio
+async def test_retry_calls_again():
+    env = EnvTestManager()
+    await make_obligations(env, "good")
+    calls = []
+
+    def decision_function(obligation):
+        calls.append(obligation)
+        if len(calls) < 3:
+            raise ConnectionError("flaky")
+        return True
+
+    result = await env.bob_client.oracle.arbitrate_past_sync(
+        string_fulfillments(env),
+        decision_function,
+        ArbitrateOptions(on_error="retry", max_retries=2),
+    )
+
+    assert len(calls) == 3
+    assert result.successful_count == 1
+
+
+@pytest.mark.asyncio
+async def test_abort_raises_the_exception():
+    env = EnvTestManager()
+    await make_obligations(env, "good")
+
+    def decision_function(obligation):
+        raise ValueError("can't decide")
+
+    with pytest.raises(ValueError, match="can't decide"):
+        await env.bob_client.oracle.arbitrate_past_sync(
+            string_fulfillments(env),
+            decision_function,
+            ArbitrateOptions(on_error="abort"),
+        )
+
+
+@pytest.mark.asyncio
+async def test_abort_ends_background_listener():
+    env = EnvTestManager()
+
+    def callback_function(decision):
+        raise ValueError("callback failed")
+
+    listener = await env.bob_client.oracle.listen_and_arbitrate(
+        string_fulfillments(env),
+        lambda _: True,
+        callback_function,
+        ArbitrateOptions(only_new=True, on_error="abort"),
+    )
+    await make_obligations(env, "good")
+
+    with pytest.raises(ValueError, match="callback failed"):
+        await listener.wait()
+    assert not listener.is_running
+    assert "callback failed" in listener.last_error
+
+
+def test_unknown_policy():
+    with pytest.raises(ValueError):
+        ArbitrateOptions(on_error="ignore")
+    assert ArbitrateOptions().on_error == "skip"
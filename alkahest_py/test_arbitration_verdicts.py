@@ -0,0 +1,72 @@
+import pytest
+from alkahest_py import (
+    AttestationFilter,
+    EnvTestManager,
+    FulfillmentParams,
+    StringObligationData,
+    Verdict,
+)
+
+
+@pytest.mark.asyncio
+async def test_verdict_reason_and_payload():
+    env = EnvTestManager()
+    fulfillment_uid = await env.bob_client.string_obligation.do_obligation(
+        "too short", None
+    )
+    params = FulfillmentParams(
+        StringObligationData,
+        AttestationFilter(
+            attester=env.addresses.string_obligation_addresses.obligation,
+            uid=fulfillment_uid,
+            from_block=0,
+        ),
+    )
+
+    def decision_function(obligation):
+        return Verdict(False, "needs at least 20 characters", b"\x09")
+
+    result = await env.bob_client.oracle.arbitrate_past_sync(
+        params, decision_function, None
+    )
+
+    decision = result.decisions[0]
+    assert decision.decision is False
+    assert decision.reason == "needs at least 20 characters"
+    assert decision.payload == b"\x09"
+
+    # The seller can read it back from the chain
+    verdict = await env.alice_client.oracle.get_verdict(decision.transaction_hash)
+    assert verdict.decision is False
+    assert verdict.reason == "needs at least 20 characters"
+    assert verdict.payload == b"\x09"
+
+
+@pytest.mark.asyncio
+async def test_plain_bool_has_no_reason():
+    env = EnvTestManager()
+    fulfillment_uid = await env.bob_client.string_obligation.do_obligation(
+        "good", None
+    )
+    params = FulfillmentParams(
+        StringObligationData,
+        AttestationFilter(uid=fulfillment_uid, from_block=0),
+    )
+
+    result = await env.bob_client.oracle.arbitrate_past_sync(
+        params, lambda obligation: True, None
+    )
+
+    decision = result.decisions[0]
+    assert decision.reason is None and decision.payload == b""
+    verdict = await env.bob_client.oracle.get_verdict(decision.transaction_hash)
+    assert verdict.decision is True and verdict.reason is None
+
+
+@pytest.mark.asyncio
+async def test_get_verdict_of_other_transaction():
+    env = EnvTestManager()
+    transaction_hash = await env.alice_client.erc20.approve(
+        {"address": env.mock_addresses.erc20_a, "value": 1}, "payment"
+    )
+    assert await env.bob_client.oracle.get_verdict(transaction_hash) is None
@@ -0,0 +1,92 @@
+import asyncio
+import pytest
+from alkahest_py import (
+    ArbitrateOptions,
+    AttestationFilter,
+    EnvTestManager,
+    FulfillmentParams,
+    StringObligationData,
+)
+
+
+def string_fulfillments(env):
+    return FulfillmentParams(
+        StringObligationData,
+        AttestationFilter(
+            attester=env.addresses.string_obligation_addresses.obligation,
+            recipient=env.bob,
+            from_block=0,
+        ),
+    )
+
+
+@pytest.mark.asyncio
+async def test_async_decision_function():
+    env = EnvTestManager()
+    for item in ["good", "bad", "good"]:
+        await env.bob_client.string_obligation.do_obligation(item, None)
+
+    in_flight = 0
+    most_in_flight = 0
+
+    async def decision_function(obligation):
+        nonlocal in_flight, most_in_flight
+        in_flight += 1
+        most_in_flight = max(most_in_flight, in_flight)
+        await asyncio.sleep(0.2)
+        in_flight -= 1
+        return obligation == "good"
+
+    result = await env.bob_client.oracle.arbitrate_past_sync(
+        string_fulfillments(env), decision_function, ArbitrateOptions()
+    )
+
+    assert result.total_count == 3
+    assert result.successful_count == 2
+    # Fulfillments found together are decided together
+    assert most_in_flight == 3
+
+
+def test_async_decision_function_under_sync():
+    env = EnvTestManager()
+    env.bob_client.sync.string_obligation.do_obligation("good", None)
+
+    async def decision_function(obligation):
+        await asyncio.sleep(0)
+        return obligation == "good"
+
+    result = env.bob_client.sync.oracle.arbitrate_past_sync(
+        string_fulfillments(env), decision_function, None
+    )
+
+    assert result.successful_count == 1
+
+
+@pytest.mark.asyncio
+async def test_async_callback():
+    env = EnvTestManager()
+    options = ArbitrateOptions(only_new=True)
+    callback_calls = []
+
+    async def decision_function(obligation):
+        return obligation == "good"
+
+    async def callback_function(decision_info):
+        await asyncio.sleep(0)
+        callback_calls.append(decision_info)
+
+    listener = asyncio.create_task(
+        env.bob_client.oracle.listen_and_arbitrate_no_spawn(
+            string_fulfillments(env),
+            decision_function,
+            callback_function,
+            options,
+            5,
+        )
+    )
+    await asyncio.sleep(1)
+    await env.bob_client.string_obligation.do_obligation("good", None)
+
+    decisions = await listener
+    assert [d.obligation_data for d in decisions] == ["good"]
+    assert [(d.decision, d.obligation_data) for d in callback_calls] == [(True, "good")]
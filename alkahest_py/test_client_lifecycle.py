@@ -0,0 +1,162 @@
+import os
+import socket
+import threading
+import time
+from urllib.parse import urlparse
+
+import pytest
+from alkahest_py import (
+    AlkahestClient,
+    AlkahestError,
+    ArbitrateOptions,
+    AttestationFilter,
+    EnvTestManager,
+    FulfillmentParams,
+    StringObligationData,
+)
+
+PRIVATE_KEY = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"
+
+
+def thread_count():
+    with open("/proc/self/status") as status:
+        for line in status:
+            if line.startswith("Threads:"):
+                return int(line.split()[1])
+
+
+class ConnectionCountingProxy:
+    """Forwards TCP connections to the node and counts how many are open."""
+
+    def __init__(self, url):
+        target = urlparse(url)
+        self.target = (target.hostname, target.port)
+        self.server = socket.create_server(("127.0.0.1", 0))
+        self.url = f"{target.scheme}://127.0.0.1:{self.server.getsockname()[1]}"
+        self.open_connections = 0
+        self.lock = threading.Lock()
+        threading.Thread(target=self._accept, daemon=True).start()
+
+    def _accept(self):
+        while True:
+            try:
+                downstream, _ = self.server.accept()
+            except OSError:
+                return
+            upstream = socket.create_connection(self.target)
+            with self.lock:
+                self.open_connections += 1
+            threading.Thread(target=self._pipe, args=(downstream, upstream), daemon=True).start()
+            threading.Thread(target=self._pipe, args=(upstream, downstream, False), daemon=True).start()
+
+    def _pipe(self, source, sink, counts=True):
+        try:
+            while data := source.recv(65536):
+                sink.sendall(data)
+        except OSError:
+            pass
+        for sock in (source, sink):
+            try:
+                sock.shutdown(socket.SHUT_RDWR)
+            except OSError:
+                pass
+        if counts:
+            with self.lock:
+                self.open_connections -= 1
+
+    def wait_for(self, predicate, timeout=5):
+        deadline = time.monotonic() + timeout
+        while not predicate(self.open_connections):
+            if time.monotonic() > deadline:
+                return False
+            time.sleep(0.05)
+        return True
+
+    def close(self):
+        self.server.close()
+
+
+@pytest.mark.asyncio
+async def test_close_stops_client_and_its_extensions():
+    env = EnvTestManager()
+    client = AlkahestClient(PRIVATE_KEY, env.rpc_url, env.addresses)
+    string_client = client.string_obligation
+
+    assert not client.is_closed
+    client.close()
+    assert client.is_closed
+    client.close()
+
+    with pytest.raises(AlkahestError, match="closed"):
+        _ = client.erc20
+    with pytest.raises(AlkahestError, match="closed"):
+        await string_client.do_obligation("after close", None)
+    assert client.list_extensions() == []
+
+
+@pytest.mark.asyncio
+async def test_close_drops_the_connection():
+    env = EnvTestManager()
+    proxy = ConnectionCountingProxy(env.rpc_url)
+    try:
+        client = AlkahestClient(PRIVATE_KEY, proxy.url, env.addresses)
+        # Extension clients taken before closing share the connection, so
+        # holding one mustn't keep it open
+        string_client = client.string_obligation
+        assert proxy.wait_for(lambda n: n > 0)
+
+        client.close()
+        assert proxy.wait_for(lambda n: n == 0)
+        with pytest.raises(AlkahestError, match="closed"):
+            await string_client.do_obligation("after close", None)
+    finally:
+        proxy.close()
+
+
+@pytest.mark.asyncio
+async def test_close_stops_listeners():
+    env = EnvTestManager()
+    client = AlkahestClient(PRIVATE_KEY, env.rpc_url, env.addresses)
+    params = FulfillmentParams(
+        StringObligationData,
+        AttestationFilter(
+            attester=env.addresses.string_obligation_addresses.obligation, from_block=0
+        ),
+    )
+    listener = await client.oracle.listen_and_arbitrate(
+        params, lambda _: True, None, ArbitrateOptions(only_new=True)
+    )
+    assert listener.is_running
+
+    client.close()
+    assert not listener.is_running
+    await listener.wait()
+
+
+def test_sync_context_manager_closes():
+    env = EnvTestManager()
+    with AlkahestClient(PRIVATE_KEY, env.rpc_url, env.addresses) as client:
+        assert client.has_extension("erc20")
+    assert client.is_closed
+
+
+@pytest.mark.asyncio
+async def test_async_context_manager_closes():
+    env = EnvTestManager()
+    async with AlkahestClient(PRIVATE_KEY, env.rpc_url, env.addresses) as client:
+        uid = await client.string_obligation.do_obligation("inside", None)
+        assert uid.startswith("0x")
+    assert client.is_closed
+
+
+@pytest.mark.skipif(not os.path.exists("/proc/self/status"), reason="needs procfs")
+def test_clients_share_one_runtime():
+    env = EnvTestManager()
+    AlkahestClient(PRIVATE_KEY, env.rpc_url, env.addresses).close()
+    before = thread_count()
+
+    for _ in range(5):
+        with AlkahestClient(PRIVATE_KEY, env.rpc_url, env.addresses) as client:
+            assert client.erc20 is not None
+
+    assert thread_count() == before
@@ -31,8 +31,8 @@ async def test_basic_encode_decode():
     repr_str = repr(obligation)
     assert "ERC1155PaymentObligationData" in repr_str, "Repr should contain class name"
     assert obligation.token in repr_str, "Repr should contain token address"
-    assert obligation.token_id in repr_str, "Repr should contain token ID"
-    assert obligation.amount in repr_str, "Repr should contain amount"
+    assert str(obligation.token_id) in repr_str, "Repr should contain token ID"
+    assert str(obligation.amount) in repr_str, "Repr should contain amount"
     
     print("✅ ERC1155 Payment Obligation encode/decode test passed!")
     print(f"Original: {obligation}")
@@ -30,4 +30,4 @@ async def test_basic_encode_decode():
     repr_str = repr(obligation)
     assert "ERC721PaymentObligationData" in repr_str, "Repr should contain class name"
     assert obligation.token in repr_str, "Repr should contain token address"
-    assert obligation.token_id in repr_str, "Repr should contain token ID"
+    assert str(obligation.token_id) in repr_str, "Repr should contain token ID"
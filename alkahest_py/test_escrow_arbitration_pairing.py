@@ -0,0 +1,138 @@
+import asyncio
+import time
+
+import pytest
+from alkahest_py import (
+    ArbitrateOptions,
+    AttestationFilter,
+    EnvTestManager,
+    EscrowParams,
+    FulfillmentParams,
+    MockERC20,
+    StringObligationData,
+    TrustedOracleArbiterDemandData,
+)
+
+
+async def make_escrows(env, *datas):
+    """Makes an escrow per demand data with Bob as the oracle, and Bob's
+    fulfillment of each. Returns the demand and the escrow and fulfillment uids."""
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100 * len(datas))
+    made = []
+    for data in datas:
+        demand = TrustedOracleArbiterDemandData(env.bob, data).encode_self()
+        arbiter = {
+            "arbiter": env.addresses.arbiters_addresses.trusted_oracle_arbiter,
+            "demand": demand,
+        }
+        escrow = await env.alice_client.erc20.permit_and_buy_with_erc20(
+            {"address": env.mock_addresses.erc20_a, "value": 100},
+            arbiter,
+            int(time.time()) + 3600,
+        )
+        escrow_uid = escrow["log"]["uid"]
+        fulfillment_uid = await env.bob_client.string_obligation.do_obligation(
+            "good", escrow_uid
+        )
+        made.append((demand, escrow_uid, fulfillment_uid))
+    return made
+
+
+def escrow_params(env, demand):
+    return EscrowParams(
+        demand,
+        AttestationFilter(
+            attester=env.addresses.erc20_addresses.escrow_obligation, from_block=0
+        ),
+    )
+
+
+def fulfillment_params(env):
+    return FulfillmentParams(
+        StringObligationData,
+        AttestationFilter(
+            attester=env.addresses.string_obligation_addresses.obligation,
+            recipient=env.bob,
+            from_block=0,
+        ),
+    )
+
+
+@pytest.mark.asyncio
+async def test_decisions_link_to_their_escrow():
+    env = EnvTestManager()
+    [(demand, escrow_uid, fulfillment_uid)] = await make_escrows(env, b"\x01\x02")
+
+    result = await env.bob_client.oracle.arbitrate_past_for_escrow_sync(
+        escrow_params(env, demand),
+        fulfillment_params(env),
+        lambda obligation, demand: True,
+        ArbitrateOptions(require_oracle=True),
+    )
+
+    [decision] = result.decisions
+    assert decision.attestation.uid == fulfillment_uid
+    assert decision.escrow_attestation.uid == escrow_uid
+    assert decision.demand_data.oracle.lower() == env.bob.lower()
+    assert decision.demand_data.data == b"\x01\x02"
+
+    assert [a.uid for a in result.escrow_attestations] == [escrow_uid]
+    [escrow_demand] = result.escrow_demands
+    assert escrow_demand.oracle.lower() == env.bob.lower()
+    assert escrow_demand.encode_self() == demand
+
+
+@pytest.mark.asyncio
+async def test_escrow_listener_carries_demands():
+    env = EnvTestManager()
+    [(demand, escrow_uid, _)] = await make_escrows(env, b"")
+
+    listener = await env.bob_client.oracle.listen_and_arbitrate_for_escrow(
+        escrow_params(env, demand),
+        fulfillment_params(env),
+        lambda obligation, demand: True,
+    )
+    listener.stop()
+
+    [decision] = listener.initial_decisions
+    assert decision.escrow_attestation.uid == escrow_uid
+    assert [a.uid for a in listener.escrow_attestations] == [escrow_uid]
+    assert [d.encode_self() for d in listener.escrow_demands] == [demand]
+
+
+@pytest.mark.asyncio
+async def test_escrow_listener_adds_new_escrows():
+    env = EnvTestManager()
+    [(demand, first_uid, _)] = await make_escrows(env, b"")
+
+    listener = await env.bob_client.oracle.listen_and_arbitrate_for_escrow(
+        escrow_params(env, demand),
+        fulfillment_params(env),
+        lambda obligation, demand: True,
+    )
+    try:
+        [(_, second_uid, _)] = await make_escrows(env, b"")
+        for _ in range(50):
+            if len(listener.escrow_attestations) == 2:
+                break
+            await asyncio.sleep(0.2)
+    finally:
+        listener.stop()
+
+    assert [a.uid for a in listener.escrow_attestations] == [first_uid, second_uid]
+    assert [d.encode_self() for d in listener.escrow_demands] == [demand, demand]
+    assert len(listener.initial_decisions) == 1
+
+
+@pytest.mark.asyncio
+async def test_non_escrow_decisions_have_no_escrow():
+    env = EnvTestManager()
+    await env.bob_client.string_obligation.do_obligation("good", None)
+
+    result = await env.bob_client.oracle.arbitrate_past_sync(
+        fulfillment_params(env), lambda obligation: True, None
+    )
+
+    [decision] = result.decisions
+    assert decision.escrow_attestation is None and decision.demand_data is None
@@ -0,0 +1,87 @@
+import pytest
+from alkahest_py import (
+    AlkahestClient,
+    AlkahestError,
+    DecodeError,
+    EnvTestManager,
+    ERC20PaymentObligationData,
+    ExtensionNotAvailable,
+    InvalidAddress,
+    MockERC20,
+    MockERC721,
+    TransactionReverted,
+)
+
+# Anvil dev account #1
+PRIVATE_KEY = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"
+
+
+@pytest.mark.asyncio
+async def test_missing_extension():
+    env = EnvTestManager()
+    client = AlkahestClient.with_no_extensions(PRIVATE_KEY, env.rpc_url)
+
+    with pytest.raises(ExtensionNotAvailable) as exc_info:
+        _ = client.erc20
+    assert exc_info.value.extension == "erc20"
+
+    # Still an AttributeError, so hasattr keeps working
+    assert not hasattr(client, "erc721")
+
+
+@pytest.mark.asyncio
+async def test_invalid_address():
+    env = EnvTestManager()
+    obligation = ERC20PaymentObligationData(token="0xnot-an-address", amount=1, payee=env.bob)
+
+    with pytest.raises(InvalidAddress) as exc_info:
+        obligation.encode_self()
+    assert exc_info.value.value == "0xnot-an-address"
+    assert isinstance(exc_info.value, ValueError)
+    assert isinstance(exc_info.value, AlkahestError)
+
+
+def test_decode_error():
+    with pytest.raises(DecodeError):
+        ERC20PaymentObligationData.decode(b"\x01\x02")
+
+
+@pytest.mark.asyncio
+async def test_revert_without_allowance():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+
+    escrow = env.addresses.erc20_addresses.escrow_obligation
+    with pytest.raises(TransactionReverted) as exc_info:
+        await env.alice_client.erc20.buy_with_erc20(
+            {"address": env.mock_addresses.erc20_a, "value": 100},
+            {"arbiter": env.addresses.erc20_addresses.payment_obligation, "demand": b""},
+            0,
+        )
+    assert exc_info.value.contract_address.lower() == escrow.lower()
+    assert isinstance(exc_info.value.revert_data, (bytes, type(None)))
+
+
+@pytest.mark.asyncio
+async def test_malformed_uid():
+    env = EnvTestManager()
+
+    with pytest.raises(DecodeError) as exc_info:
+        await env.bob_client.erc20.collect_escrow("0x1234", "not-a-uid")
+    assert isinstance(exc_info.value, ValueError)
+
+    with pytest.raises(DecodeError):
+        await env.bob_client.oracle.get_verdict("0xzz")
+
+
+def test_fixture_errors():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+
+    with pytest.raises(InvalidAddress):
+        mock_erc20.balance_of("0xnot-an-address")
+    # The token was never minted, so the call reverts
+    mock_erc721 = MockERC721(env.mock_addresses.erc721_a, env.god_wallet_provider)
+    with pytest.raises(AlkahestError):
+        mock_erc721.owner_of(2**200)
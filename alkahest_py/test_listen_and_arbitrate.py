@@ -0,0 +1,102 @@
+import asyncio
+import pytest
+from alkahest_py import (
+    ArbitrateOptions,
+    AttestationFilter,
+    EnvTestManager,
+    FulfillmentParams,
+    StringObligationData,
+)
+
+
+def string_fulfillments(env, recipient):
+    return FulfillmentParams(
+        StringObligationData,
+        AttestationFilter(
+            attester=env.addresses.string_obligation_addresses.obligation,
+            recipient=recipient,
+            from_block=0,
+        ),
+    )
+
+
+async def wait_for(condition, timeout=10):
+    for _ in range(int(timeout / 0.1)):
+        if condition():
+            return
+        await asyncio.sleep(0.1)
+    raise AssertionError("timed out")
+
+
+@pytest.mark.asyncio
+async def test_listener_runs_in_background():
+    env = EnvTestManager()
+    await env.bob_client.string_obligation.do_obligation("good", None)
+
+    callback_calls = []
+    listener = await env.bob_client.oracle.listen_and_arbitrate(
+        string_fulfillments(env, env.bob),
+        lambda obligation: obligation == "good",
+        callback_calls.append,
+    )
+
+    assert listener.is_running
+    assert listener.subscription_id.startswith("0x")
+    assert [d.obligation_data for d in listener.initial_decisions] == ["good"]
+    assert listener.total_count == 1
+
+    await env.bob_client.string_obligation.do_obligation("bad", None)
+    await wait_for(lambda: listener.total_count == 2)
+
+    assert listener.successful_count == 1
+    assert listener.error_count == 0 and listener.last_error is None
+    assert [(d.decision, d.obligation_data) for d in callback_calls] == [(False, "bad")]
+
+    listener.stop()
+    assert not listener.is_running
+
+
+@pytest.mark.asyncio
+async def test_listeners_side_by_side():
+    env = EnvTestManager()
+    options = ArbitrateOptions(only_new=True)
+    oracle = env.bob_client.oracle
+
+    for_bob = await oracle.listen_and_arbitrate(
+        string_fulfillments(env, env.bob), lambda _: True, None, options
+    )
+    for_alice = await oracle.listen_and_arbitrate(
+        string_fulfillments(env, env.alice), lambda _: False, None, options
+    )
+    assert for_bob.subscription_id != for_alice.subscription_id
+
+    await env.bob_client.string_obligation.do_obligation("bob's", None)
+    await env.alice_client.string_obligation.do_obligation("alice's", None)
+    await wait_for(lambda: for_bob.total_count == 1 and for_alice.total_count == 1)
+    assert for_bob.successful_count == 1 and for_alice.successful_count == 0
+
+    await oracle.unsubscribe(for_bob.subscription_id)
+    assert not for_bob.is_running
+    assert for_alice.is_running
+    for_alice.stop()
+
+
+@pytest.mark.asyncio
+async def test_listener_records_callback_errors():
+    env = EnvTestManager()
+
+    def callback_function(decision):
+        raise ValueError("callback failed")
+
+    listener = await env.bob_client.oracle.listen_and_arbitrate(
+        string_fulfillments(env, env.bob),
+        lambda _: True,
+        callback_function,
+        ArbitrateOptions(only_new=True),
+    )
+    await env.bob_client.string_obligation.do_obligation("good", None)
+    await wait_for(lambda: listener.error_count == 1)
+
+    assert "callback failed" in listener.last_error
+    assert listener.is_running
+    listener.stop()
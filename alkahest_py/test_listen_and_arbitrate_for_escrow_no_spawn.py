@@ -156,7 +156,7 @@ async def test_listen_and_arbitrate_for_escrow_no_spawn():
             try:
                 collection_receipt = await env.bob_client.erc20.collect_escrow(escrow_uid, good_uid)
                 print(f"💰 Collection receipt: {collection_receipt}")
-                if collection_receipt and collection_receipt.startswith('0x'):
+                if collection_receipt and collection_receipt.status:
                     collection_success = True
                     print("✅ Payment collection successful!")
                 else:
@@ -144,7 +144,7 @@ async def test_listen_and_arbitrate_new_fulfillments_for_escrow_no_spawn():
                 )
                 print("collection_receipt:", collection_receipt)
                 
-                if collection_receipt and collection_receipt.startswith('0x'):
+                if collection_receipt and collection_receipt.status:
                     collection_success = True
                     print(f"🎉 Fulfillment thread: Payment collected successfully: {collection_receipt}")
             except Exception as e:
@@ -0,0 +1,59 @@
+import asyncio
+import time
+
+import pytest
+from alkahest_py import EnvTestManager, MockERC20, RpcError, TxOptions
+
+
+@pytest.mark.asyncio
+async def test_parallel_escrows_share_one_nonce_sequence():
+    env = EnvTestManager()
+    token = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    token.transfer(env.alice, 1000)
+    await env.alice_client.erc20.approve(
+        {"address": env.mock_addresses.erc20_a, "value": 1000}, "escrow"
+    )
+
+    item = {"arbiter": env.addresses.arbiters_addresses.trivial_arbiter, "demand": b""}
+    expiration = int(time.time()) + 3600
+    escrows = await asyncio.gather(
+        *[
+            env.alice_client.erc20.buy_with_erc20(
+                {"address": env.mock_addresses.erc20_a, "value": 100}, item, expiration
+            )
+            for _ in range(8)
+        ],
+        # Other extension clients draw from the same sequence
+        *[env.alice_client.string_obligation.do_obligation(f"item {i}", None) for i in range(4)],
+    )
+
+    assert len({escrow["log"]["uid"] for escrow in escrows[:8]}) == 8
+    assert token.balance_of(env.alice) == 200
+
+
+@pytest.mark.asyncio
+async def test_failed_send_gives_its_nonce_back():
+    env = EnvTestManager()
+    token = {"address": env.mock_addresses.erc20_a, "value": 100}
+
+    # A fee cap under the base fee never reaches the chain; if its nonce stayed
+    # reserved, the sends after it would wait behind a gap forever
+    results = await asyncio.gather(
+        env.alice_client.erc20.approve(
+            token, "escrow", tx_options=TxOptions(max_fee_per_gas=1, max_priority_fee_per_gas=1)
+        ),
+        *[env.alice_client.string_obligation.do_obligation(f"after {i}", None) for i in range(3)],
+        return_exceptions=True,
+    )
+    assert isinstance(results[0], RpcError)
+    assert all(uid.startswith("0x") for uid in results[1:])
+
+
+def test_reset_nonce_rereads_from_node():
+    env = EnvTestManager()
+    client = env.alice_client.sync
+    token = {"address": env.mock_addresses.erc20_a, "value": 100}
+    client.erc20.approve(token, "escrow")
+
+    env.alice_client.reset_nonce()
+    assert client.erc20.approve(token, "payment").status
@@ -0,0 +1,72 @@
+import json
+import time
+
+import pytest
+from alkahest_py import EnvTestManager, MockERC20, PermitSignature
+
+
+@pytest.mark.asyncio
+async def test_relayer_submits_signed_permit():
+    env = EnvTestManager()
+    token = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    token.transfer(env.alice, 100)
+
+    price = {"address": env.mock_addresses.erc20_a, "value": 100}
+    permit = await env.alice_client.erc20.sign_permit(price, "escrow")
+    assert isinstance(permit, PermitSignature)
+    assert permit.owner.lower() == env.alice.lower()
+    assert permit.spender.lower() == env.addresses.erc20_addresses.escrow_obligation.lower()
+
+    # The permit travels as JSON; Bob relays it and pays the gas
+    relayed = json.loads(json.dumps(permit.to_dict()))
+    item = {"arbiter": env.addresses.arbiters_addresses.trivial_arbiter, "demand": b""}
+    escrow = await env.bob_client.erc20.permit_and_buy_with_erc20(
+        price, item, int(time.time()) + 3600, None, relayed
+    )
+
+    assert escrow["log"]["recipient"].lower() == env.alice.lower()
+    assert token.balance_of(env.alice) == 0
+    assert token.balance_of(env.addresses.erc20_addresses.escrow_obligation) == 100
+
+
+@pytest.mark.asyncio
+async def test_presigned_permit_sent_by_owner():
+    env = EnvTestManager()
+    token = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    token.transfer(env.alice, 100)
+    price = {"address": env.mock_addresses.erc20_a, "value": 100}
+
+    online = await env.alice_client.erc20.sign_permit(price, "payment", deadline=2**40)
+    # Passing the nonce instead of reading it gives the same signature
+    pinned = await env.alice_client.erc20.sign_permit(price, "payment", deadline=2**40, nonce=0)
+    assert online.nonce == 0
+    assert pinned.signature == online.signature
+
+    payment = await env.alice_client.erc20.permit_and_pay_with_erc20(
+        price, env.bob, permit=pinned
+    )
+    assert payment["log"]["uid"].startswith("0x")
+    assert token.balance_of(env.bob) == 100
+
+
+@pytest.mark.asyncio
+async def test_mismatched_permit_is_rejected():
+    env = EnvTestManager()
+    price = {"address": env.mock_addresses.erc20_a, "value": 100}
+    permit = await env.alice_client.erc20.sign_permit(price, "payment")
+
+    with pytest.raises(Exception, match="permit is for"):
+        await env.alice_client.erc20.permit_and_pay_with_erc20(
+            {"address": env.mock_addresses.erc20_a, "value": 50}, env.bob, permit=permit
+        )
+
+    # Barter helpers can't be relayed
+    with pytest.raises(Exception, match="relayer"):
+        await env.bob_client.erc20.permit_and_buy_erc20_for_erc20(
+            {"address": env.mock_addresses.erc20_a, "value": 100},
+            {"address": env.mock_addresses.erc20_b, "value": 100},
+            0,
+            permit=await env.alice_client.erc20.sign_permit(
+                {"address": env.mock_addresses.erc20_a, "value": 100}, "escrow"
+            ),
+        )
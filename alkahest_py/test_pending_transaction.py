@@ -0,0 +1,80 @@
+import pytest
+from alkahest_py import (
+    EnvTestManager,
+    PendingTransaction,
+    TransactionCancelled,
+    TransactionReceipt,
+)
+
+
+@pytest.mark.asyncio
+async def test_no_wait_returns_handle_then_result():
+    env = EnvTestManager()
+    token = {"address": env.mock_addresses.erc20_a, "value": 100}
+
+    handle = await env.alice_client.no_wait.erc20.approve(token, "escrow")
+    assert isinstance(handle, PendingTransaction)
+    assert handle.transaction_hash.startswith("0x")
+
+    receipt = await handle.wait()
+    assert isinstance(receipt, TransactionReceipt)
+    assert receipt.transaction_hash == handle.transaction_hash
+    assert handle.is_mined
+
+    # Attestation methods give back their usual decoded result
+    handle = await env.alice_client.no_wait.string_obligation.do_obligation("later", None)
+    uid = await handle.wait()
+    assert uid.startswith("0x")
+
+
+@pytest.mark.asyncio
+async def test_wait_for_confirmations():
+    env = EnvTestManager()
+    handle = await env.alice_client.no_wait.erc20.approve(
+        {"address": env.mock_addresses.erc20_a, "value": 100}, "escrow"
+    )
+
+    with pytest.raises(TimeoutError):
+        await handle.wait(confirmations=3, timeout=1)
+
+    await env.god_wallet_provider.anvil_mine(2)
+    receipt = await handle.wait(confirmations=3, timeout=10)
+    assert receipt.status
+
+
+@pytest.mark.asyncio
+async def test_speed_up_and_cancel():
+    env = EnvTestManager()
+    token = {"address": env.mock_addresses.erc20_a, "value": 100}
+    await env.god_wallet_provider.anvil_set_auto_mine(False)
+    try:
+        handle = await env.alice_client.no_wait.erc20.approve(token, "escrow")
+        original = handle.transaction_hash
+        faster = await handle.speed_up(1.5)
+        assert handle.transaction_hashes == [original, faster]
+
+        await env.god_wallet_provider.anvil_mine(1)
+        receipt = await handle.wait(timeout=10)
+        assert receipt.transaction_hash == faster
+
+        handle = await env.alice_client.no_wait.erc20.approve(token, "payment")
+        cancelled_by = await handle.cancel()
+        await env.god_wallet_provider.anvil_mine(1)
+        with pytest.raises(TransactionCancelled) as raised:
+            await handle.wait(timeout=10)
+        assert raised.value.cancelled_by == cancelled_by
+    finally:
+        await env.god_wallet_provider.anvil_set_auto_mine(True)
+
+    with pytest.raises(ValueError):
+        await handle.speed_up(1.0)
+
+
+def test_pending_transaction_through_sync_facade():
+    env = EnvTestManager()
+    client = env.alice_client.sync
+
+    handle = client.no_wait.erc20.approve(
+        {"address": env.mock_addresses.erc20_a, "value": 100}, "escrow"
+    )
+    assert handle.wait(timeout=10).status
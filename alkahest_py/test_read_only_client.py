@@ -0,0 +1,42 @@
+import pytest
+from alkahest_py import AlkahestClient, EnvTestManager, StringObligationData
+
+
+@pytest.mark.asyncio
+async def test_read_only_client_reads_and_decodes():
+    env = EnvTestManager()
+
+    uid = await env.alice_client.string_obligation.do_obligation("read me", None)
+
+    client = AlkahestClient.read_only(env.rpc_url, env.addresses)
+    assert client.is_read_only
+    assert not env.alice_client.is_read_only
+
+    obligation = await client.string_obligation.get_obligation(uid)
+    assert obligation["data"].item == "read me"
+
+    encoded = StringObligationData.encode(StringObligationData("read me"))
+    assert StringObligationData.decode(encoded).item == "read me"
+
+    for extension in client.list_extensions():
+        assert client.has_extension(extension)
+
+
+@pytest.mark.asyncio
+async def test_read_only_client_refuses_to_send():
+    env = EnvTestManager()
+    client = AlkahestClient.read_only(env.rpc_url, env.addresses)
+
+    with pytest.raises(Exception, match="read-only"):
+        await client.erc20.approve(
+            {"address": env.mock_addresses.erc20_a, "value": 100},
+            "escrow",
+        )
+
+    with pytest.raises(Exception, match="read-only"):
+        await client.string_obligation.do_obligation("nope", None)
+
+    # Extensions added later stay read-only
+    erc721_client = await client.with_erc721()
+    with pytest.raises(Exception, match="read-only"):
+        await erc721_client.erc721.approve_all(env.mock_addresses.erc721_a, "escrow")
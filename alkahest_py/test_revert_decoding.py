@@ -0,0 +1,94 @@
+import time
+
+import pytest
+from alkahest_py import (
+    EnvTestManager,
+    InsufficientAllowance,
+    MockERC20,
+    TransactionReverted,
+    decode_revert,
+)
+from eth_abi import encode
+
+# Error(string) with message "escrow expired"
+ERROR_STRING = bytes.fromhex(
+    "08c379a0"
+    "0000000000000000000000000000000000000000000000000000000000000020"
+    "000000000000000000000000000000000000000000000000000000000000000e"
+    "657363726f772065787069726564000000000000000000000000000000000000"
+)
+
+
+def test_decode_error_string():
+    name, args = decode_revert(ERROR_STRING)
+    assert name == "Error"
+    assert args == ("escrow expired",)
+
+
+def test_decode_contract_error():
+    spender = "0x" + "11" * 20
+    data = bytes.fromhex("fb8f41b2") + encode(
+        ["address", "uint256", "uint256"], [spender, 5, 100]
+    )
+    name, args = decode_revert(data)
+    assert name == "ERC20InsufficientAllowance"
+    assert args[0].lower() == spender
+    assert args[1:] == (5, 100)
+
+
+def test_unknown_revert_data():
+    assert decode_revert(bytes.fromhex("deadbeef")) is None
+    assert decode_revert(b"") is None
+
+
+@pytest.mark.asyncio
+async def test_revert_exposes_error_name_and_args():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+    escrow_address = env.addresses.erc20_addresses.escrow_obligation
+
+    # No approval, so the escrow's transferFrom reverts
+    with pytest.raises(InsufficientAllowance) as exc_info:
+        await env.alice_client.erc20.buy_with_erc20(
+            {"address": env.mock_addresses.erc20_a, "value": 100},
+            {"arbiter": env.addresses.erc20_addresses.payment_obligation, "demand": b""},
+            0,
+        )
+
+    err = exc_info.value
+    assert err.error_name == "ERC20InsufficientAllowance"
+    assert err.error_args is not None
+    spender, allowance, needed = err.error_args
+    assert spender.lower() == escrow_address.lower()
+    assert (allowance, needed) == (0, 100)
+
+    decoded = decode_revert(err.revert_data)
+    assert decoded == (err.error_name, err.error_args)
+
+
+@pytest.mark.asyncio
+async def test_collect_without_decision_is_invalid_fulfillment():
+    env = EnvTestManager()
+    mock_erc20 = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    mock_erc20.transfer(env.alice, 100)
+
+    # Bob is the oracle but never arbitrates, so the fulfillment isn't accepted
+    arbiter = {
+        "arbiter": env.addresses.arbiters_addresses.trusted_oracle_arbiter,
+        "demand": encode(["(address,bytes)"], [(env.bob, b"")]),
+    }
+    escrow = await env.alice_client.erc20.permit_and_buy_with_erc20(
+        {"address": env.mock_addresses.erc20_a, "value": 100},
+        arbiter,
+        int(time.time()) + 3600,
+    )
+    escrow_uid = escrow["log"]["uid"]
+    fulfillment_uid = await env.bob_client.string_obligation.do_obligation("good", escrow_uid)
+
+    with pytest.raises(TransactionReverted) as exc_info:
+        await env.bob_client.erc20.collect_escrow(escrow_uid, fulfillment_uid)
+
+    err = exc_info.value
+    assert err.error_name == "InvalidFulfillment"
+    assert err.error_args == ()
@@ -0,0 +1,74 @@
+import pytest
+from alkahest_py import AlkahestClient, EnvTestManager, RetryConfig
+
+PRIVATE_KEY = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"
+# Nothing listens here, so every request to it fails at the transport level
+DEAD_ENDPOINT = "http://127.0.0.1:1"
+
+
+@pytest.mark.asyncio
+async def test_fails_over_to_a_healthy_endpoint():
+    env = EnvTestManager()
+    client = AlkahestClient(
+        PRIVATE_KEY,
+        [DEAD_ENDPOINT, env.rpc_url],
+        env.addresses,
+        retry=RetryConfig(max_retries=1, initial_backoff=0.01),
+    )
+
+    receipt = await client.erc20.approve(
+        {"address": env.mock_addresses.erc20_a, "value": 100}, "escrow"
+    )
+    assert receipt.status
+
+    # The dead endpoint is now skipped until its cooldown ends
+    receipt = await client.erc20.approve(
+        {"address": env.mock_addresses.erc20_a, "value": 200}, "payment"
+    )
+    assert receipt.status
+
+
+@pytest.mark.asyncio
+async def test_extension_reads_fail_over():
+    env = EnvTestManager()
+    client = AlkahestClient(
+        PRIVATE_KEY,
+        [DEAD_ENDPOINT, env.rpc_url],
+        env.addresses,
+        retry=RetryConfig(max_retries=1, initial_backoff=0.01),
+    )
+
+    # Reads made by the alkahest-rs clients go through the same endpoints,
+    # so the dead primary doesn't break them
+    uid = await client.string_obligation.do_obligation("failover", None)
+    obligation = await client.string_obligation.get_obligation(uid)
+    assert obligation["data"].item == "failover"
+
+    # So do extensions added after the client was built
+    bare = AlkahestClient.with_no_extensions(
+        PRIVATE_KEY,
+        [DEAD_ENDPOINT, env.rpc_url],
+        retry=RetryConfig(max_retries=1, initial_backoff=0.01),
+    )
+    string_client = await bare.with_string_obligation(env.addresses.string_obligation_addresses)
+    obligation = await string_client.string_obligation.get_obligation(uid)
+    assert obligation["data"].item == "failover"
+
+
+def test_retry_config_accepts_a_dict():
+    env = EnvTestManager()
+    client = AlkahestClient.read_only(
+        [env.rpc_url], env.addresses, retry={"max_retries": 5, "max_backoff": 2.0}
+    )
+    assert client.is_read_only
+
+    with pytest.raises(ValueError):
+        AlkahestClient.read_only(env.rpc_url, env.addresses, retry={"retries": 5})
+    with pytest.raises(ValueError):
+        AlkahestClient.read_only(env.rpc_url, env.addresses, retry=RetryConfig(initial_backoff=-1))
+
+
+def test_rpc_url_list_must_not_be_empty():
+    env = EnvTestManager()
+    with pytest.raises(ValueError):
+        AlkahestClient.read_only([], env.addresses)
@@ -0,0 +1,90 @@
+import pytest
+from alkahest_py import AlkahestClient, EnvTestManager, Signer
+
+# Anvil's default dev mnemonic; index 1 is the key used elsewhere in these tests
+MNEMONIC = "test test test test test test test test test test test junk"
+PRIVATE_KEY = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"
+ADDRESS = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+
+
+class DelegatingSigner:
+    """Minimal signing-protocol implementation, standing in for a KMS or hardware wallet."""
+
+    def __init__(self, signer):
+        self.inner = signer
+        self.address = signer.address
+        self.calls = 0
+
+    def sign_hash(self, digest):
+        self.calls += 1
+        return self.inner.sign_hash(digest)
+
+
+def test_mnemonic_derivation():
+    by_index = Signer.from_mnemonic(MNEMONIC, index=1)
+    by_path = Signer.from_mnemonic(MNEMONIC, derivation_path="m/44'/60'/0'/0/1")
+    from_key = Signer.from_private_key(PRIVATE_KEY)
+
+    assert by_index.address.lower() == ADDRESS.lower()
+    assert by_path.address == by_index.address == from_key.address
+    assert by_index.is_local
+
+
+def test_keystore_errors_are_value_errors(tmp_path):
+    with pytest.raises(ValueError):
+        Signer.from_keystore(str(tmp_path / "missing.json"), "password")
+
+
+def test_protocol_requires_address_and_sign_hash():
+    with pytest.raises(TypeError):
+        Signer.from_protocol(object())
+
+    external = Signer.from_protocol(DelegatingSigner(Signer.from_private_key(PRIVATE_KEY)))
+    assert not external.is_local
+    assert external.address == Signer.from_private_key(PRIVATE_KEY).address
+
+
+@pytest.mark.asyncio
+async def test_client_sends_with_mnemonic_signer():
+    env = EnvTestManager()
+
+    client = AlkahestClient(Signer.from_mnemonic(MNEMONIC, index=1), env.rpc_url)
+    receipt = await client.erc20.approve(
+        {"address": env.mock_addresses.erc20_a, "value": 100},
+        "escrow",
+    )
+    assert receipt.transaction_hash.startswith("0x")
+
+
+@pytest.mark.asyncio
+async def test_client_sends_with_protocol_signer():
+    env = EnvTestManager()
+
+    external = DelegatingSigner(Signer.from_private_key(PRIVATE_KEY))
+    client = AlkahestClient(external, env.rpc_url)
+    receipt = await client.erc20.approve(
+        {"address": env.mock_addresses.erc20_a, "value": 100},
+        "escrow",
+    )
+    assert receipt.transaction_hash.startswith("0x")
+    assert external.calls > 0
+
+    # Extensions added later sign with the same object rather than a new key
+    string_client = await AlkahestClient.with_no_extensions(external, env.rpc_url).with_string_obligation()
+    await string_client.string_obligation.do_obligation("hello", None)
+    assert external.calls > 1
+
+
+@pytest.mark.asyncio
+async def test_protocol_signer_for_wrong_address_is_rejected():
+    env = EnvTestManager()
+
+    impostor = DelegatingSigner(Signer.from_private_key(PRIVATE_KEY))
+    impostor.address = env.bob
+    client = AlkahestClient(impostor, env.rpc_url)
+
+    with pytest.raises(Exception):
+        await client.erc20.approve(
+            {"address": env.mock_addresses.erc20_a, "value": 100},
+            "escrow",
+        )
@@ -0,0 +1,82 @@
+import time
+
+import pytest
+from alkahest_py import EnvTestManager, MockERC20, SimulationResult
+
+
+@pytest.mark.asyncio
+async def test_simulated_escrow_predicts_attestation():
+    env = EnvTestManager()
+    alice = env.alice_client
+    token = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    token.transfer(env.alice, 100)
+    await alice.erc20.approve({"address": env.mock_addresses.erc20_a, "value": 100}, "escrow")
+    balance_before = token.balance_of(env.alice)
+
+    price = {"address": env.mock_addresses.erc20_a, "value": 100}
+    item = {"arbiter": env.addresses.erc20_addresses.payment_obligation, "demand": b""}
+    expiration = int(time.time()) + 3600
+
+    result = await alice.simulate.erc20.buy_with_erc20(price, item, expiration)
+    assert isinstance(result, SimulationResult)
+    assert result.success
+    assert result.transactions == 1
+    assert result.gas_estimate > 21_000
+    assert result.attested["attester"].lower() == env.addresses.erc20_addresses.escrow_obligation.lower()
+    assert result.uid.startswith("0x")
+
+    # Nothing was sent
+    assert token.balance_of(env.alice) == balance_before
+
+    sent = await alice.erc20.buy_with_erc20(price, item, expiration)
+    assert sent["log"]["attester"] == result.attested["attester"]
+
+
+@pytest.mark.asyncio
+async def test_simulated_revert_is_decoded_not_raised():
+    env = EnvTestManager()
+
+    result = await env.alice_client.simulate.erc20.collect_escrow("0x" + "00" * 32, "0x" + "00" * 32)
+    assert not result
+    assert result.gas_estimate is None
+    assert result.revert_reason
+    assert result.uid is None
+
+
+def test_simulate_through_sync_facade():
+    env = EnvTestManager()
+    client = env.alice_client
+
+    assert client.simulate.is_simulating
+    assert not client.is_simulating
+
+    result = client.sync.simulate.string_obligation.do_obligation("dry run", None)
+    assert result.success
+    assert result.uid.startswith("0x")
+
+
+@pytest.mark.asyncio
+async def test_simulated_relayed_permit_covers_both_transactions():
+    env = EnvTestManager()
+    token = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    token.transfer(env.alice, 100)
+    price = {"address": env.mock_addresses.erc20_a, "value": 100}
+    permit = await env.alice_client.erc20.sign_permit(price, "escrow")
+    item = {"arbiter": env.addresses.arbiters_addresses.trivial_arbiter, "demand": b""}
+
+    # The escrow only succeeds after the permit, so both have to be simulated
+    result = await env.bob_client.simulate.erc20.permit_and_buy_with_erc20(
+        price, item, int(time.time()) + 3600, permit=permit
+    )
+    assert isinstance(result, SimulationResult)
+    assert result.success
+    assert result.transactions == 2
+    assert result.attested["attester"].lower() == env.addresses.erc20_addresses.escrow_obligation.lower()
+    assert result.attested["recipient"].lower() == env.alice.lower()
+
+    # Nothing was sent, so the permit is still unused
+    assert token.balance_of(env.alice) == 100
+    escrow = await env.bob_client.erc20.permit_and_buy_with_erc20(
+        price, item, int(time.time()) + 3600, permit=permit
+    )
+    assert escrow["log"]["uid"].startswith("0x")
@@ -0,0 +1,97 @@
+import asyncio
+import pytest
+from alkahest_py import (
+    ArbitrateOptions,
+    AttestationFilter,
+    EnvTestManager,
+    FulfillmentParams,
+    StringObligationData,
+)
+
+
+def string_fulfillments(env):
+    return FulfillmentParams(
+        StringObligationData,
+        AttestationFilter(
+            attester=env.addresses.string_obligation_addresses.obligation,
+            recipient=env.bob,
+            from_block=0,
+        ),
+    )
+
+
+@pytest.mark.asyncio
+async def test_stream_past_then_new_decisions():
+    env = EnvTestManager()
+    await env.bob_client.string_obligation.do_obligation("first", None)
+
+    async def make_later():
+        await asyncio.sleep(1)
+        await env.bob_client.string_obligation.do_obligation("second", None)
+
+    later = asyncio.create_task(make_later())
+    seen = []
+    async for decision in env.bob_client.oracle.stream_decisions(
+        string_fulfillments(env), lambda obligation: obligation == "first"
+    ):
+        assert decision.transaction_hash.startswith("0x")
+        seen.append((decision.obligation_data, decision.decision))
+        if len(seen) == 2:
+            break
+    await later
+
+    assert seen == [("first", True), ("second", False)]
+
+
+@pytest.mark.asyncio
+async def test_slow_consumer_and_break():
+    env = EnvTestManager()
+    for item in ["a", "b", "c"]:
+        await env.bob_client.string_obligation.do_obligation(item, None)
+    calls = []
+
+    def decision_function(obligation):
+        calls.append(obligation)
+        return True
+
+    stream = env.bob_client.oracle.stream_decisions(
+        string_fulfillments(env), decision_function, None, 1
+    )
+    seen = []
+    async for decision in stream:
+        await asyncio.sleep(0.5)
+        seen.append(decision.obligation_data)
+        if len(seen) == 3:
+            break
+    await stream.aclose()
+
+    assert seen == ["a", "b", "c"]
+    # Nothing is arbitrated once the stream is closed
+    await env.bob_client.string_obligation.do_obligation("d", None)
+    await asyncio.sleep(2)
+    assert "d" not in calls
+
+
+@pytest.mark.asyncio
+async def test_stream_raises_abort():
+    env = EnvTestManager()
+    await env.bob_client.string_obligation.do_obligation("good", None)
+
+    def decision_function(obligation):
+        raise ValueError("can't decide")
+
+    with pytest.raises(ValueError, match="can't decide"):
+        async for _ in env.bob_client.oracle.stream_decisions(
+            string_fulfillments(env),
+            decision_function,
+            ArbitrateOptions(on_error="abort"),
+        ):
+            pass
+
+
+def test_buffer_must_be_positive():
+    env = EnvTestManager()
+    with pytest.raises(ValueError):
+        env.bob_client.oracle.stream_decisions(
+            string_fulfillments(env), lambda _: True, None, 0
+        )
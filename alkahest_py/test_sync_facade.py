@@ -0,0 +1,73 @@
+import asyncio
+from concurrent.futures import ThreadPoolExecutor
+
+import pytest
+from alkahest_py import AlkahestClient, EnvTestManager, ExtensionNotAvailable, SyncClient
+
+PRIVATE_KEY = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"
+
+
+def test_sync_methods_return_results():
+    env = EnvTestManager()
+    client = env.alice_client.sync
+
+    receipt = client.erc20.approve(
+        {"address": env.mock_addresses.erc20_a, "value": 100},
+        "escrow",
+    )
+    assert receipt.status
+
+    uid = client.string_obligation.do_obligation("blocking", None)
+    obligation = client.string_obligation.get_obligation(uid)
+    assert obligation["data"].item == "blocking"
+
+    # with_* returns the client itself rather than an awaitable, still blocking
+    extended = client.with_erc721()
+    assert isinstance(extended, SyncClient)
+    assert extended.has_extension("erc721")
+    receipt = extended.with_erc20().erc20.approve(
+        {"address": env.mock_addresses.erc20_a, "value": 50},
+        "payment",
+    )
+    assert receipt.status
+
+
+def test_sync_shares_the_async_client():
+    env = EnvTestManager()
+    uid = env.alice_client.sync.string_obligation.do_obligation("shared", None)
+
+    async def read_back():
+        return await env.alice_client.string_obligation.get_obligation(uid)
+
+    assert asyncio.run(read_back())["data"].item == "shared"
+
+
+def test_sync_works_inside_running_event_loop():
+    env = EnvTestManager()
+
+    async def main():
+        return env.alice_client.sync.string_obligation.do_obligation("in loop", None)
+
+    assert asyncio.run(main()).startswith("0x")
+
+
+def test_sync_calls_release_the_gil():
+    env = EnvTestManager()
+    client = env.alice_client.sync
+    uids = [client.string_obligation.do_obligation(f"item {i}", None) for i in range(4)]
+
+    with ThreadPoolExecutor(max_workers=4) as pool:
+        items = list(pool.map(lambda uid: client.string_obligation.get_obligation(uid), uids))
+
+    assert [o["data"].item for o in items] == [f"item {i}" for i in range(4)]
+
+
+def test_sync_keeps_errors():
+    env = EnvTestManager()
+
+    with pytest.raises(ValueError, match="Unknown extension"):
+        env.alice_client.sync.with_extensions(["nope"])
+
+    empty = AlkahestClient.with_no_extensions(PRIVATE_KEY, env.rpc_url)
+    with pytest.raises(ExtensionNotAvailable):
+        _ = empty.sync.erc20
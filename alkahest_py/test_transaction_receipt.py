@@ -0,0 +1,71 @@
+import time
+
+import pytest
+from alkahest_py import EnvTestManager, MockERC20, TransactionReceipt
+
+
+@pytest.mark.asyncio
+async def test_approve_returns_receipt():
+    env = EnvTestManager()
+    token = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    transfer = token.transfer(env.alice, 100)
+    assert isinstance(transfer, TransactionReceipt)
+    assert transfer.status
+
+    receipt = await env.alice_client.erc20.approve(
+        {"address": env.mock_addresses.erc20_a, "value": 100}, "escrow"
+    )
+    assert isinstance(receipt, TransactionReceipt)
+    assert receipt.status
+    assert receipt.transaction_hash.startswith("0x") and len(receipt.transaction_hash) == 66
+    assert str(receipt) == receipt.transaction_hash
+    assert receipt.block_number > 0
+    assert receipt.gas_used > 21_000
+    assert receipt.fee_paid == receipt.gas_used * receipt.effective_gas_price
+    assert receipt.from_address.lower() == env.alice.lower()
+    assert receipt.logs == []
+    assert receipt.attested is None
+
+
+@pytest.mark.asyncio
+async def test_collect_escrow_receipt_has_decoded_logs():
+    env = EnvTestManager()
+    token = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    token.transfer(env.alice, 100)
+
+    price = {"address": env.mock_addresses.erc20_a, "value": 100}
+    await env.alice_client.erc20.approve(price, "escrow")
+    escrow = await env.alice_client.erc20.buy_with_erc20(
+        price,
+        {"arbiter": env.addresses.arbiters_addresses.trivial_arbiter, "demand": b""},
+        int(time.time()) + 3600,
+    )
+    escrow_uid = escrow["log"]["uid"]
+    fulfillment = await env.bob_client.string_obligation.do_obligation("done", escrow_uid)
+
+    receipt = await env.bob_client.erc20.collect_escrow(escrow_uid, fulfillment)
+    claimed = receipt.escrow_claimed
+    assert claimed["payment"] == escrow_uid
+    assert claimed["fulfillment"] == fulfillment
+    assert claimed["fulfiller"].lower() == env.bob.lower()
+    assert "EscrowClaimed" in [log["event"] for log in receipt.logs]
+
+
+@pytest.mark.asyncio
+async def test_token_bundle_approve_returns_every_receipt():
+    env = EnvTestManager()
+    token = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    token.transfer(env.alice, 200)
+
+    bundle = {
+        "erc20s": [
+            {"address": env.mock_addresses.erc20_a, "value": 100},
+            {"address": env.mock_addresses.erc20_b, "value": 100},
+        ],
+        "erc721s": [],
+        "erc1155s": [],
+    }
+    receipts = await env.alice_client.token_bundle.approve(bundle, "escrow")
+    assert len(receipts) == 2
+    assert all(receipt.status for receipt in receipts)
+    assert receipts[0].transaction_hash != receipts[1].transaction_hash
@@ -0,0 +1,68 @@
+import pytest
+from alkahest_py import (
+    EnvTestManager,
+    RpcError,
+    TransactionReverted,
+    TxOptions,
+)
+
+
+@pytest.mark.asyncio
+async def test_tx_options_are_applied():
+    env = EnvTestManager()
+    token = {"address": env.mock_addresses.erc20_a, "value": 100}
+
+    options = TxOptions(
+        gas_limit=200_000,
+        max_fee_per_gas=50_000_000_000,
+        max_priority_fee_per_gas=1_000_000_000,
+    )
+    assert options.gas_limit == 200_000
+    receipt = await env.alice_client.erc20.approve(token, "escrow", tx_options=options)
+    assert receipt.gas_used <= 200_000
+
+    # Plain dicts work too
+    uid = await env.alice_client.string_obligation.do_obligation(
+        "with options", None, {"gas_limit": 300_000}
+    )
+    assert uid.startswith("0x")
+
+    # A gas limit too small for the call runs out of gas on chain
+    with pytest.raises(TransactionReverted):
+        await env.alice_client.erc20.approve(
+            token, "escrow", tx_options=TxOptions(gas_limit=30_000)
+        )
+
+    # A fee cap under the base fee is rejected by the node
+    with pytest.raises(RpcError):
+        await env.alice_client.erc20.approve(
+            token, "escrow", tx_options=TxOptions(max_fee_per_gas=1, max_priority_fee_per_gas=1)
+        )
+
+    # Nonce 0 has already been used by the transactions above
+    with pytest.raises(RpcError):
+        await env.alice_client.erc20.approve(token, "escrow", tx_options=TxOptions(nonce=0))
+
+
+@pytest.mark.asyncio
+async def test_value_is_attached():
+    env = EnvTestManager()
+
+    # approve isn't payable, so attaching value makes it revert
+    with pytest.raises(TransactionReverted):
+        await env.alice_client.erc20.approve(
+            {"address": env.mock_addresses.erc20_a, "value": 100},
+            "escrow",
+            tx_options=TxOptions(value=1),
+        )
+
+
+def test_unknown_tx_option_is_rejected():
+    env = EnvTestManager()
+
+    with pytest.raises(ValueError, match="Unknown transaction option"):
+        env.alice_client.sync.erc20.approve(
+            {"address": env.mock_addresses.erc20_a, "value": 100},
+            "escrow",
+            {"gas": 100_000},
+        )
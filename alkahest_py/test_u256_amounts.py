@@ -0,0 +1,75 @@
+import pytest
+from alkahest_py import (
+    EnvTestManager,
+    ERC20EscrowObligationData,
+    ERC20PaymentObligationData,
+    ERC721PaymentObligationData,
+    ERC1155EscrowObligationData,
+    Erc20Data,
+)
+
+MAX_UINT256 = 2**256 - 1
+
+
+def test_erc20_escrow_amount_above_u64():
+    env = EnvTestManager()
+
+    # 100 tokens of an 18-decimal ERC20 no longer fits in a u64
+    amount = 100 * 10**18
+    obligation = ERC20EscrowObligationData(
+        token=env.mock_addresses.erc20_a,
+        amount=amount,
+        arbiter=env.addresses.erc20_addresses.payment_obligation,
+        demand=[1, 2, 3],
+    )
+
+    decoded = ERC20EscrowObligationData.decode(ERC20EscrowObligationData.encode(obligation))
+    assert decoded.amount == amount, f"Amount mismatch: expected {amount}, got {decoded.amount}"
+
+
+def test_full_uint256_range_round_trips():
+    env = EnvTestManager()
+
+    payment = ERC20PaymentObligationData(
+        token=env.mock_addresses.erc20_a,
+        amount=MAX_UINT256,
+        payee=env.bob,
+    )
+    assert ERC20PaymentObligationData.decode(payment.encode_self()).amount == MAX_UINT256
+
+    nft = ERC721PaymentObligationData(
+        token=env.mock_addresses.erc721_a,
+        token_id=MAX_UINT256,
+        payee=env.bob,
+    )
+    assert ERC721PaymentObligationData.decode(nft.encode_self()).token_id == MAX_UINT256
+
+    multi = ERC1155EscrowObligationData(
+        token=env.mock_addresses.erc1155_a,
+        token_id=2**200,
+        amount=MAX_UINT256,
+        arbiter=env.addresses.erc1155_addresses.payment_obligation,
+        demand=[],
+    )
+    decoded = ERC1155EscrowObligationData.decode(multi.encode_self())
+    assert decoded.token_id == 2**200
+    assert decoded.amount == MAX_UINT256
+
+    assert Erc20Data(env.mock_addresses.erc20_a, MAX_UINT256).value == MAX_UINT256
+
+
+def test_out_of_range_values_are_rejected():
+    env = EnvTestManager()
+
+    with pytest.raises(ValueError):
+        ERC20PaymentObligationData(
+            token=env.mock_addresses.erc20_a,
+            amount=2**256,
+            payee=env.bob,
+        )
+
+    with pytest.raises(ValueError):
+        Erc20Data(env.mock_addresses.erc20_a, -1)
+
+    with pytest.raises(TypeError):
+        Erc20Data(env.mock_addresses.erc20_a, True)
@@ -0,0 +1,133 @@
+import json
+import time
+
+import pytest
+from alkahest_py import (
+    AlkahestClient,
+    AlkahestError,
+    EnvTestManager,
+    MockERC20,
+    Signer,
+    TransactionReceipt,
+    UnsignedTransaction,
+)
+
+# Anvil's dev account 1, standing in for a key held by an HSM
+PRIVATE_KEY = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"
+ADDRESS = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+
+
+@pytest.mark.asyncio
+async def test_build_sign_and_broadcast():
+    env = EnvTestManager()
+    client = AlkahestClient.read_only(env.rpc_url, env.addresses).unsigned(ADDRESS)
+
+    unsigned = await client.erc20.approve(
+        {"address": env.mock_addresses.erc20_a, "value": 100}, "escrow"
+    )
+    assert isinstance(unsigned, UnsignedTransaction)
+    assert unsigned.from_address.lower() == ADDRESS.lower()
+    assert unsigned.to.lower() == env.mock_addresses.erc20_a.lower()
+    assert unsigned.gas > 0
+    assert unsigned.nonce is not None
+    assert unsigned.data[:4].hex() == "095ea7b3"
+
+    tx = unsigned.to_dict()
+    assert tx["chainId"] == unsigned.chain_id
+    assert tx["maxFeePerGas"] == unsigned.max_fee_per_gas
+    assert json.loads(unsigned.to_json())["nonce"] == hex(unsigned.nonce)
+    assert unsigned.rlp()[0] == tx["type"]
+
+    raw = await unsigned.sign(Signer.from_private_key(PRIVATE_KEY))
+    receipt = await unsigned.broadcast_signed(raw)
+    assert isinstance(receipt, TransactionReceipt)
+    assert receipt.status
+    assert receipt.from_address.lower() == ADDRESS.lower()
+
+
+@pytest.mark.asyncio
+async def test_broadcast_returns_the_methods_result():
+    env = EnvTestManager()
+    client = AlkahestClient.read_only(env.rpc_url, env.addresses).unsigned(ADDRESS)
+    signer = Signer.from_private_key(PRIVATE_KEY)
+
+    unsigned = await client.string_obligation.do_obligation("signed elsewhere", None)
+    with pytest.raises(ValueError):
+        await unsigned.sign(Signer.from_private_key("0x" + "11" * 32))
+
+    # A transaction that isn't the one built is rejected before broadcasting
+    other = await client.string_obligation.do_obligation("something else", None)
+    with pytest.raises(ValueError):
+        await unsigned.broadcast_signed(await other.sign(signer))
+
+    uid = await unsigned.broadcast_signed(await unsigned.sign(signer))
+    assert uid.startswith("0x")
+
+
+@pytest.mark.asyncio
+async def test_failed_broadcast_can_be_retried():
+    env = EnvTestManager()
+    client = AlkahestClient.read_only(env.rpc_url, env.addresses).unsigned(ADDRESS)
+    unsigned = await client.string_obligation.do_obligation("retry me", None)
+    raw = await unsigned.sign(Signer.from_private_key(PRIVATE_KEY))
+
+    # Without funds for gas the node refuses the transaction
+    await env.god_wallet_provider.anvil_set_balance(ADDRESS, 0)
+    with pytest.raises(AlkahestError):
+        await unsigned.broadcast_signed(raw)
+
+    await env.god_wallet_provider.anvil_set_balance(ADDRESS, 10**22)
+    uid = await unsigned.broadcast_signed(raw)
+    assert uid.startswith("0x")
+
+    with pytest.raises(RuntimeError, match="already been broadcast"):
+        await unsigned.broadcast_signed(raw)
+
+
+@pytest.mark.asyncio
+async def test_multi_transaction_call_is_built_in_turn():
+    env = EnvTestManager()
+    token = MockERC20(env.mock_addresses.erc20_a, env.god_wallet_provider)
+    token.transfer(env.alice, 100)
+    signer = Signer.from_private_key(PRIVATE_KEY)
+
+    # Relaying someone else's permit submits it, then escrows on their behalf
+    price = {"address": env.mock_addresses.erc20_a, "value": 100}
+    permit = await env.alice_client.erc20.sign_permit(price, "escrow")
+    item = {"arbiter": env.addresses.arbiters_addresses.trivial_arbiter, "demand": b""}
+    client = AlkahestClient.read_only(env.rpc_url, env.addresses).unsigned(ADDRESS)
+
+    submit_permit = await client.erc20.permit_and_buy_with_erc20(
+        price, item, int(time.time()) + 3600, None, permit
+    )
+    assert submit_permit.to.lower() == env.mock_addresses.erc20_a.lower()
+
+    escrow = await submit_permit.broadcast_signed(await submit_permit.sign(signer))
+    assert isinstance(escrow, UnsignedTransaction)
+    assert escrow.to.lower() == env.addresses.erc20_addresses.escrow_obligation.lower()
+    assert escrow.nonce == submit_permit.nonce + 1
+
+    result = await escrow.broadcast_signed(await escrow.sign(signer))
+    assert result["log"]["recipient"].lower() == env.alice.lower()
+    assert token.balance_of(env.addresses.erc20_addresses.escrow_obligation) == 100
+
+
+@pytest.mark.asyncio
+async def test_client_broadcast_signed():
+    env = EnvTestManager()
+    client = AlkahestClient.read_only(env.rpc_url, env.addresses)
+    unsigned = await client.unsigned(ADDRESS).string_obligation.do_obligation(
+        "relayed", None
+    )
+    raw = await unsigned.sign(Signer.from_private_key(PRIVATE_KEY))
+
+    receipt = await client.broadcast_signed("0x" + raw.hex())
+    assert receipt.status
+    assert receipt.attested is not None
+
+
+def test_read_only_client_needs_from_address():
+    env = EnvTestManager()
+    client = AlkahestClient.read_only(env.rpc_url, env.addresses)
+    with pytest.raises(ValueError):
+        client.unsigned()
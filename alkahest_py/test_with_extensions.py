@@ -0,0 +1,59 @@
+import pytest
+from alkahest_py import AlkahestClient, EnvTestManager
+
+PRIVATE_KEY = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d"
+
+
+@pytest.mark.asyncio
+async def test_list_extensions_reports_wired_extensions():
+    env = EnvTestManager()
+
+    full = AlkahestClient(PRIVATE_KEY, env.rpc_url, env.addresses)
+    assert full.list_extensions() == [
+        "erc20",
+        "erc721",
+        "erc1155",
+        "token_bundle",
+        "attestation",
+        "string_obligation",
+        "oracle",
+    ]
+
+    bare = AlkahestClient.with_no_extensions(PRIVATE_KEY, env.rpc_url)
+    assert bare.list_extensions() == []
+    assert not bare.has_extension("erc20")
+
+    with_erc20 = await bare.with_erc20()
+    assert with_erc20.list_extensions() == ["erc20"]
+    assert with_erc20.has_extension("erc20")
+    assert not with_erc20.has_extension("erc721")
+
+
+@pytest.mark.asyncio
+async def test_with_extensions_adds_several_at_once():
+    env = EnvTestManager()
+    bare = AlkahestClient.with_no_extensions(PRIVATE_KEY, env.rpc_url)
+
+    client = await bare.with_extensions(["string_obligation", "erc20"], env.addresses)
+    assert client.list_extensions() == ["erc20", "string_obligation"]
+
+    # Both extensions send through the same signer and nonce stream
+    uid = await client.string_obligation.do_obligation("shared provider", None)
+    await client.erc20.approve(
+        {"address": env.mock_addresses.erc20_a, "value": 100},
+        "escrow",
+    )
+    obligation = await client.string_obligation.get_obligation(uid)
+    assert obligation["data"].item == "shared provider"
+
+    # The original client is left untouched
+    assert bare.list_extensions() == []
+
+
+@pytest.mark.asyncio
+async def test_with_extensions_rejects_unknown_names():
+    env = EnvTestManager()
+    bare = AlkahestClient.with_no_extensions(PRIVATE_KEY, env.rpc_url)
+
+    with pytest.raises(ValueError, match="Unknown extension"):
+        await bare.with_extensions(["erc20", "erc9999"])
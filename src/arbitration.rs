@@ -0,0 +1,1139 @@
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    fmt,
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use alkahest_rs::{
+    clients::{arbiters::TrustedOracleArbiter, oracle::AttestationFilter},
+    contracts::{
+        ERC1155PaymentObligation, ERC20PaymentObligation, ERC721PaymentObligation,
+        StringObligation, TokenBundlePaymentObligation,
+        IEAS::{self, Attested},
+    },
+};
+use alloy::{
+    consensus::Transaction,
+    primitives::{keccak256, Address, Bytes, TxHash, B256},
+    providers::Provider,
+    rpc::types::{Filter, FilterBlockOption, TransactionRequest, ValueOrArray},
+    sol_types::{SolCall, SolEvent, SolValue},
+};
+use futures::{
+    future::{BoxFuture, Shared},
+    stream, FutureExt, StreamExt, TryStreamExt,
+};
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    pybacked::PyBackedStr,
+    types::{PyAnyMethods, PyBytes, PyString, PyType},
+    Bound, FromPyObject, IntoPyObject, PyAny, PyErr, PyResult, Python,
+};
+use tokio::task::AbortHandle;
+
+use crate::{
+    checkpoint::Checkpoint,
+    clients::{
+        erc1155::PyERC1155PaymentObligationData,
+        erc20::PyERC20PaymentObligationData,
+        erc721::PyERC721PaymentObligationData,
+        oracle::{PyArbitrateOptions, PyVerdict},
+        string_obligation::PyStringObligationData,
+        token_bundle::PyTokenBundlePaymentObligationData,
+    },
+    error_handling::map_eyre_to_pyerr,
+    interfaces::{EscrowArbiter, ITrustedOracleArbiter},
+    runtime::runtime,
+    tx::TxSender,
+};
+
+// Obligation UIDs per eth_getLogs request when checking for past arbitrations
+const UIDS_PER_QUERY: usize = 100;
+// Decision function calls run, and attestations fetched, at once when
+// `max_concurrency` isn't set
+const DEFAULT_MAX_CONCURRENCY: usize = 16;
+
+// Selector and the two arguments of `arbitrate`, before a verdict's reason
+// and payload
+const ARBITRATE_CALLDATA_LEN: usize = 4 + 2 * 32;
+
+// Before retrying a failed decision function or callback, doubling each time
+const RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/// The obligation contract whose data a fulfillment carries, chosen through
+/// `FulfillmentParams.obligation_abi`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObligationKind {
+    String,
+    Erc20Payment,
+    Erc721Payment,
+    Erc1155Payment,
+    TokenBundlePayment,
+    // Left undecoded, for custom obligation contracts
+    Bytes,
+}
+
+const OBLIGATION_KINDS: [ObligationKind; 6] = [
+    ObligationKind::String,
+    ObligationKind::Erc20Payment,
+    ObligationKind::Erc721Payment,
+    ObligationKind::Erc1155Payment,
+    ObligationKind::TokenBundlePayment,
+    ObligationKind::Bytes,
+];
+
+impl ObligationKind {
+    /// Decodes an attestation's data, or None if it isn't this kind of
+    /// obligation.
+    pub fn decode(self, data: &[u8]) -> Option<Obligation> {
+        Some(match self {
+            Self::String => Obligation::String(
+                StringObligation::ObligationData::abi_decode(data)
+                    .ok()?
+                    .item,
+            ),
+            Self::Erc20Payment => Obligation::Erc20Payment(
+                ERC20PaymentObligation::ObligationData::abi_decode(data)
+                    .ok()?
+                    .into(),
+            ),
+            Self::Erc721Payment => Obligation::Erc721Payment(
+                ERC721PaymentObligation::ObligationData::abi_decode(data)
+                    .ok()?
+                    .into(),
+            ),
+            Self::Erc1155Payment => Obligation::Erc1155Payment(
+                ERC1155PaymentObligation::ObligationData::abi_decode(data)
+                    .ok()?
+                    .into(),
+            ),
+            Self::TokenBundlePayment => Obligation::TokenBundlePayment(
+                TokenBundlePaymentObligation::ObligationData::abi_decode(data)
+                    .ok()?
+                    .into(),
+            ),
+            Self::Bytes => Obligation::Bytes(data.to_vec()),
+        })
+    }
+
+    fn python_type(self, py: Python<'_>) -> Bound<'_, PyType> {
+        match self {
+            Self::String => py.get_type::<PyStringObligationData>(),
+            Self::Erc20Payment => py.get_type::<PyERC20PaymentObligationData>(),
+            Self::Erc721Payment => py.get_type::<PyERC721PaymentObligationData>(),
+            Self::Erc1155Payment => py.get_type::<PyERC1155PaymentObligationData>(),
+            Self::TokenBundlePayment => py.get_type::<PyTokenBundlePaymentObligationData>(),
+            Self::Bytes => py.get_type::<PyBytes>(),
+        }
+    }
+}
+
+// Accepts one of the obligation data classes or `bytes`, or an instance of one
+impl<'py> FromPyObject<'py> for ObligationKind {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        for kind in OBLIGATION_KINDS {
+            let ty = kind.python_type(ob.py());
+            if ob.is(&ty) || ob.is_instance(&ty)? {
+                return Ok(kind);
+            }
+        }
+        Err(PyTypeError::new_err(
+            "obligation_abi must be StringObligationData, ERC20PaymentObligationData, \
+             ERC721PaymentObligationData, ERC1155PaymentObligationData, \
+             TokenBundlePaymentObligationData or bytes, or an instance of one",
+        ))
+    }
+}
+
+impl<'py> IntoPyObject<'py> for ObligationKind {
+    type Target = PyType;
+    type Output = Bound<'py, PyType>;
+    type Error = Infallible;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        Ok(self.python_type(py))
+    }
+}
+
+/// A fulfillment's decoded obligation data, as handed to decision functions.
+/// String obligations are passed as their item, and raw obligations as bytes.
+#[derive(Clone, FromPyObject, IntoPyObject)]
+pub enum Obligation {
+    String(String),
+    Erc20Payment(PyERC20PaymentObligationData),
+    Erc721Payment(PyERC721PaymentObligationData),
+    Erc1155Payment(PyERC1155PaymentObligationData),
+    TokenBundlePayment(PyTokenBundlePaymentObligationData),
+    Bytes(Vec<u8>),
+}
+
+/// What to do when a decision function or callback raises: leave the
+/// fulfillment undecided or the callback uncalled, try again up to
+/// `max_retries` times and then skip, or stop arbitrating and raise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    Skip,
+    Retry,
+    Abort,
+}
+
+impl<'py> FromPyObject<'py> for ErrorPolicy {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<PyBackedStr>()?.as_ref() {
+            "skip" => Ok(Self::Skip),
+            "retry" => Ok(Self::Retry),
+            "abort" => Ok(Self::Abort),
+            _ => Err(PyValueError::new_err(
+                "on_error must be \"skip\", \"retry\" or \"abort\"",
+            )),
+        }
+    }
+}
+
+impl<'py> IntoPyObject<'py> for ErrorPolicy {
+    type Target = PyString;
+    type Output = Bound<'py, PyString>;
+    type Error = Infallible;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let name = match self {
+            Self::Skip => "skip",
+            Self::Retry => "retry",
+            Self::Abort => "abort",
+        };
+        Ok(PyString::new(py, name))
+    }
+}
+
+/// Which fulfillments to arbitrate: those matching `fulfillment` whose data
+/// decodes as `kind`, and with `escrow` set, only those whose escrow matches it.
+pub struct Query {
+    pub fulfillment: AttestationFilter,
+    pub kind: ObligationKind,
+    pub escrow: Option<AttestationFilter>,
+}
+
+/// An escrow that names `TrustedOracleArbiter` as its arbiter.
+#[derive(Clone)]
+pub struct Escrow {
+    pub attestation: IEAS::Attestation,
+    pub demand: TrustedOracleArbiter::DemandData,
+}
+
+/// A fulfillment waiting on the oracle's decision. `escrow` is the escrow it
+/// refers to, when it was looked up.
+#[derive(Clone)]
+pub struct Fulfillment {
+    pub attestation: IEAS::Attestation,
+    pub obligation: Obligation,
+    pub escrow: Option<Escrow>,
+}
+
+/// Returned by an `on_decision` callback to stop arbitrating whatever the
+/// error policy says, e.g. once nothing consumes a decision stream any more.
+#[derive(Debug, Clone, Copy)]
+pub struct Stopped;
+
+impl fmt::Display for Stopped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "arbitration was stopped")
+    }
+}
+
+impl std::error::Error for Stopped {}
+
+pub struct Decision {
+    pub fulfillment: Fulfillment,
+    pub verdict: PyVerdict,
+    pub transaction_hash: TxHash,
+}
+
+/// The error of a pass that failed after some of its decisions were
+/// submitted. Those are in the checkpoint already, and are kept here so they
+/// can still be reported.
+pub struct Interrupted {
+    pub decisions: Vec<Decision>,
+    pub error: eyre::Report,
+}
+
+impl Interrupted {
+    /// Splits `error` into the decisions it carries, if any, and the error
+    /// that interrupted them.
+    pub fn split(error: eyre::Report) -> (Vec<Decision>, eyre::Report) {
+        match error.downcast::<Self>() {
+            Ok(interrupted) => (interrupted.decisions, interrupted.error),
+            Err(error) => (Vec::new(), error),
+        }
+    }
+}
+
+impl fmt::Debug for Interrupted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interrupted")
+            .field("decisions", &self.decisions.len())
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl fmt::Display for Interrupted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (after {} decisions were submitted)",
+            self.error,
+            self.decisions.len()
+        )
+    }
+}
+
+impl std::error::Error for Interrupted {}
+
+/// Counters and the last error of a [`Listener`], shared with its task.
+#[derive(Default)]
+struct ListenerStatus {
+    total: AtomicUsize,
+    successful: AtomicUsize,
+    errors: AtomicUsize,
+    last_error: Mutex<Option<String>>,
+    // The error that ended the listener
+    failure: Mutex<Option<PyErr>>,
+    stopped: AtomicBool,
+    // Each escrow the decisions settled, in the order first seen
+    escrows: Mutex<Vec<Escrow>>,
+}
+
+impl ListenerStatus {
+    fn record(&self, decision: &Decision) {
+        self.total.fetch_add(1, Ordering::Relaxed);
+        if decision.verdict.decision {
+            self.successful.fetch_add(1, Ordering::Relaxed);
+        }
+        if let Some(escrow) = &decision.fulfillment.escrow {
+            let mut escrows = self.escrows.lock().unwrap();
+            let uid = escrow.attestation.uid;
+            if !escrows.iter().any(|seen| seen.attestation.uid == uid) {
+                escrows.push(escrow.clone());
+            }
+        }
+    }
+
+    fn fail(&self, error: &eyre::Report) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+        *self.last_error.lock().unwrap() = Some(format!("{error:#}"));
+    }
+
+    fn end(&self, error: eyre::Report) {
+        self.fail(&error);
+        *self.failure.lock().unwrap() = Some(map_eyre_to_pyerr(error));
+    }
+}
+
+/// The listeners spawned through a client, by subscription id, so they can
+/// be stopped through any of its clones and are stopped when it's closed.
+#[derive(Clone, Default)]
+pub struct Listeners(Arc<Mutex<HashMap<B256, Listener>>>);
+
+impl Listeners {
+    fn insert(&self, listener: Listener) {
+        let mut listeners = self.0.lock().unwrap();
+        listeners.retain(|_, listener| listener.is_running());
+        listeners.insert(listener.id, listener);
+    }
+
+    /// Stops the listener with subscription id `id`, returning whether there
+    /// was one.
+    pub fn stop(&self, id: B256) -> bool {
+        let listener = self.0.lock().unwrap().remove(&id);
+        listener.inspect(Listener::stop).is_some()
+    }
+
+    pub fn stop_all(&self) {
+        let listeners = std::mem::take(&mut *self.0.lock().unwrap());
+        listeners.values().for_each(Listener::stop);
+    }
+}
+
+/// A listener arbitrating in the background, see [`Arbitrator::spawn`].
+/// Counts include the decisions made before it was spawned.
+#[derive(Clone)]
+pub struct Listener {
+    pub id: B256,
+    task: AbortHandle,
+    finished: Shared<BoxFuture<'static, ()>>,
+    status: Arc<ListenerStatus>,
+}
+
+impl Listener {
+    pub fn stop(&self) {
+        self.status.stopped.store(true, Ordering::Relaxed);
+        self.task.abort();
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.status.stopped.load(Ordering::Relaxed) && !self.task.is_finished()
+    }
+
+    pub fn total_count(&self) -> usize {
+        self.status.total.load(Ordering::Relaxed)
+    }
+
+    pub fn successful_count(&self) -> usize {
+        self.status.successful.load(Ordering::Relaxed)
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.status.errors.load(Ordering::Relaxed)
+    }
+
+    /// The escrows the listener's decisions have settled so far.
+    pub fn escrows(&self) -> Vec<Escrow> {
+        self.status.escrows.lock().unwrap().clone()
+    }
+
+    pub fn last_error(&self) -> Option<String> {
+        self.status.last_error.lock().unwrap().clone()
+    }
+
+    /// Waits for the listener to end, failing with the error that ended it
+    /// if it didn't end by being stopped.
+    pub async fn wait(&self) -> PyResult<()> {
+        self.finished.clone().await;
+        match &*self.status.failure.lock().unwrap() {
+            Some(error) => Err(Python::with_gil(|py| error.clone_ref(py))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Finds fulfillments and submits the oracle's decisions on them to
+/// `TrustedOracleArbiter`, through the client's provider and [`TxSender`].
+/// New attestations are found by polling, so listening works over HTTP and
+/// fails over like every other request.
+///
+/// This replaces alkahest-rs's `OracleClient::arbitrate_past` and
+/// `listen_and_arbitrate` rather than wrapping them. Those decode fulfillments
+/// as one obligation type fixed at compile time, take a synchronous decision
+/// function and send `arbitrate` themselves, from the oracle client's signer,
+/// with their own nonces. They also listen through an `eth_subscribe`
+/// subscription. So they can't decode to the Python data class chosen at run
+/// time, await `async def` decision functions, append a verdict's reason to
+/// the calldata, share the client's nonce sequence and `tx_options`, record a
+/// checkpoint, or run over HTTP. The on-chain side is unchanged: the same
+/// `Attested` queries and the same `arbitrate` call.
+#[derive(Clone)]
+pub struct Arbitrator {
+    tx: TxSender,
+    eas: Address,
+    arbiter: Address,
+    // Where a spawned listener's skipped errors are recorded
+    status: Option<Arc<ListenerStatus>>,
+    checkpoint: Option<Checkpoint>,
+}
+
+impl Arbitrator {
+    pub fn new(tx: TxSender, eas: Address, arbiter: Address) -> Self {
+        Self {
+            tx,
+            eas,
+            arbiter,
+            status: None,
+            checkpoint: None,
+        }
+    }
+
+    /// Stops the listener this client spawned with subscription id `id`,
+    /// returning whether there was one.
+    pub fn stop_listener(&self, id: B256) -> bool {
+        self.tx.listeners().stop(id)
+    }
+
+    /// Arbitrates every fulfillment matching `query` up to the latest block.
+    pub async fn arbitrate_past<F>(
+        &self,
+        query: &Query,
+        decide: &impl Fn(&[Fulfillment]) -> F,
+        options: &PyArbitrateOptions,
+    ) -> eyre::Result<Vec<Decision>>
+    where
+        F: Future<Output = eyre::Result<Vec<PyVerdict>>>,
+    {
+        let this = self.resuming(query, options)?;
+        let head = this.tx.provider().get_block_number().await?;
+        this.arbitrate_up_to(query, decide, options, head).await
+    }
+
+    /// Arbitrates past fulfillments unless `only_new` is set, then new ones as
+    /// they're attested until `timeout`, passing each of those to
+    /// `on_decision`. Returns every decision made.
+    pub async fn listen<F, G>(
+        &self,
+        query: &Query,
+        decide: &impl Fn(&[Fulfillment]) -> F,
+        mut on_decision: impl FnMut(&Decision) -> G,
+        options: &PyArbitrateOptions,
+        timeout: Option<Duration>,
+    ) -> eyre::Result<Vec<Decision>>
+    where
+        F: Future<Output = eyre::Result<Vec<PyVerdict>>>,
+        G: Future<Output = eyre::Result<()>>,
+    {
+        let this = self.resuming(query, options)?;
+        let head = this.tx.provider().get_block_number().await?;
+        let mut decisions = Vec::new();
+        if !options.only_new || this.checkpointed_block().is_some() {
+            decisions = this.arbitrate_up_to(query, decide, options, head).await?;
+        }
+
+        let follow = this.follow(
+            query,
+            decide,
+            &mut on_decision,
+            |decision| decisions.push(decision),
+            options,
+            head + 1,
+        );
+        match timeout {
+            // Running out of time is how a listener with a timeout ends
+            Some(timeout) => {
+                if let Ok(result) = tokio::time::timeout(timeout, follow).await {
+                    match result? {}
+                }
+            }
+            None => match follow.await? {},
+        }
+        Ok(decisions)
+    }
+
+    /// Arbitrates past fulfillments unless `only_new` is set, then new ones as
+    /// they're attested, passing every decision to `on_decision`, until an
+    /// error ends it.
+    pub async fn stream<F, G>(
+        &self,
+        query: &Query,
+        decide: &impl Fn(&[Fulfillment]) -> F,
+        on_decision: impl FnMut(&Decision) -> G,
+        options: &PyArbitrateOptions,
+    ) -> eyre::Result<Infallible>
+    where
+        F: Future<Output = eyre::Result<Vec<PyVerdict>>>,
+        G: Future<Output = eyre::Result<()>>,
+    {
+        let this = self.resuming(query, options)?;
+        let next = match (this.checkpointed_block(), options.only_new) {
+            (Some(block), _) => block + 1,
+            (None, true) => this.tx.provider().get_block_number().await? + 1,
+            // The first poll covers every past block
+            (None, false) => 0,
+        };
+        this.follow(query, decide, on_decision, |_| {}, options, next)
+            .await
+    }
+
+    /// Like [`Self::listen`] without a timeout, but returns once past
+    /// fulfillments are arbitrated, leaving a [`Listener`] to arbitrate new
+    /// ones in the background until it's stopped, the client is closed or the
+    /// error policy aborts. Other errors don't end it: they're recorded on the
+    /// listener, and the blocks a failed poll covered are retried.
+    pub async fn spawn<D, F, C, G>(
+        &self,
+        query: Query,
+        decide: D,
+        mut on_decision: C,
+        options: PyArbitrateOptions,
+    ) -> eyre::Result<(Listener, Vec<Decision>)>
+    where
+        D: Fn(&[Fulfillment]) -> F + Send + Sync + 'static,
+        F: Future<Output = eyre::Result<Vec<PyVerdict>>> + Send,
+        C: FnMut(&Decision) -> G + Send + 'static,
+        G: Future<Output = eyre::Result<()>> + Send,
+    {
+        let this = self.resuming(&query, &options)?;
+        let head = this.tx.provider().get_block_number().await?;
+        let mut initial = Vec::new();
+        if !options.only_new || this.checkpointed_block().is_some() {
+            initial = this
+                .arbitrate_up_to(&query, &decide, &options, head)
+                .await?;
+        }
+
+        let status = Arc::new(ListenerStatus::default());
+        initial.iter().for_each(|decision| status.record(decision));
+        let recorder = status.clone();
+        let arbitrator = Self {
+            status: Some(status.clone()),
+            ..this
+        };
+        let task = runtime().spawn(async move {
+            let follow = arbitrator.follow(
+                &query,
+                &decide,
+                &mut on_decision,
+                |decision| recorder.record(&decision),
+                &options,
+                head + 1,
+            );
+            if let Err(error) = follow.await {
+                recorder.end(error);
+            }
+        });
+
+        let listener = Listener {
+            id: B256::random(),
+            task: task.abort_handle(),
+            finished: task.map(|_| ()).boxed().shared(),
+            status,
+        };
+        self.tx.listeners().insert(listener.clone());
+        Ok((listener, initial))
+    }
+
+    async fn arbitrate_up_to<F>(
+        &self,
+        query: &Query,
+        decide: &impl Fn(&[Fulfillment]) -> F,
+        options: &PyArbitrateOptions,
+        head: u64,
+    ) -> eyre::Result<Vec<Decision>>
+    where
+        F: Future<Output = eyre::Result<Vec<PyVerdict>>>,
+    {
+        let (mut from, to) = block_range(&query.fulfillment, head);
+        if let Some(block) = self.checkpointed_block() {
+            from = from.max(block + 1);
+        }
+        let fulfillments = self.fulfillments(query, options, from, to).await?;
+        let decisions = self.arbitrate(fulfillments, decide, options).await?;
+        self.processed(to)?;
+        Ok(decisions)
+    }
+
+    /// A copy that keeps `query`'s entry in the checkpoint `options` name, if
+    /// any.
+    fn resuming(&self, query: &Query, options: &PyArbitrateOptions) -> eyre::Result<Self> {
+        let checkpoint = options
+            .checkpoint
+            .as_ref()
+            .map(|path| Checkpoint::open(path, self.checkpoint_key(query)))
+            .transpose()?;
+        Ok(Self {
+            checkpoint,
+            ..self.clone()
+        })
+    }
+
+    /// Names `query`'s entry in a checkpoint file after the oracle, the arbiter
+    /// and everything that selects its fulfillments.
+    fn checkpoint_key(&self, query: &Query) -> String {
+        let oracle = self
+            .tx
+            .address()
+            .map(|address| address.to_string())
+            .unwrap_or_default();
+        let mut key = format!(
+            "{oracle}/{}/{}/{:?}/{}",
+            self.eas,
+            self.arbiter,
+            query.kind,
+            describe(&query.fulfillment)
+        );
+        if let Some(escrow) = &query.escrow {
+            key.push_str(&format!("/escrow:{}", describe(escrow)));
+        }
+        keccak256(key).to_string()
+    }
+
+    /// The block the checkpoint says everything up to was arbitrated.
+    fn checkpointed_block(&self) -> Option<u64> {
+        self.checkpoint.as_ref()?.last_block()
+    }
+
+    fn processed(&self, block: u64) -> eyre::Result<()> {
+        match &self.checkpoint {
+            Some(checkpoint) => checkpoint.processed(block),
+            None => Ok(()),
+        }
+    }
+
+    /// Arbitrates fulfillments as they're attested from block `next` on,
+    /// handing each decision to `on_decision` as the error policy allows, then
+    /// to `decided`. A failed poll ends a listener run in the foreground; a
+    /// spawned one records it and retries the blocks the poll covered, less
+    /// the fulfillments it already submitted decisions on.
+    async fn follow<F, G>(
+        &self,
+        query: &Query,
+        decide: &impl Fn(&[Fulfillment]) -> F,
+        mut on_decision: impl FnMut(&Decision) -> G,
+        mut decided: impl FnMut(Decision),
+        options: &PyArbitrateOptions,
+        mut next: u64,
+    ) -> eyre::Result<Infallible>
+    where
+        F: Future<Output = eyre::Result<Vec<PyVerdict>>>,
+        G: Future<Output = eyre::Result<()>>,
+    {
+        let provider = self.tx.provider();
+        // Fulfillments decided on in blocks a failed poll covered, so retrying
+        // those blocks doesn't arbitrate them again
+        let mut submitted = HashSet::new();
+        loop {
+            tokio::time::sleep(provider.client().poll_interval()).await;
+            // A listener ends with its client
+            self.tx.ensure_open()?;
+            let decisions = match self.poll(query, decide, options, next, &submitted).await {
+                Ok((polled_to, decisions)) => {
+                    next = polled_to + 1;
+                    submitted.clear();
+                    decisions
+                }
+                Err(error) => {
+                    // Decisions that landed before the poll failed are still
+                    // passed on
+                    let (decisions, error) = Interrupted::split(error);
+                    submitted.extend(
+                        decisions
+                            .iter()
+                            .map(|decision| decision.fulfillment.attestation.uid),
+                    );
+                    for decision in decisions {
+                        self.attempt(options, || on_decision(&decision)).await?;
+                        decided(decision);
+                    }
+                    match &self.status {
+                        Some(status) => {
+                            status.fail(&error);
+                            continue;
+                        }
+                        None => return Err(error),
+                    }
+                }
+            };
+            for decision in decisions {
+                self.attempt(options, || on_decision(&decision)).await?;
+                decided(decision);
+            }
+        }
+    }
+
+    /// Arbitrates the fulfillments attested from block `next` up to the latest
+    /// block, other than those in `submitted`, returning that block's number
+    /// with the decisions.
+    async fn poll<F>(
+        &self,
+        query: &Query,
+        decide: &impl Fn(&[Fulfillment]) -> F,
+        options: &PyArbitrateOptions,
+        next: u64,
+        submitted: &HashSet<B256>,
+    ) -> eyre::Result<(u64, Vec<Decision>)>
+    where
+        F: Future<Output = eyre::Result<Vec<PyVerdict>>>,
+    {
+        let head = self.tx.provider().get_block_number().await?;
+        if head < next {
+            return Ok((next - 1, Vec::new()));
+        }
+        let (from, to) = block_range(&query.fulfillment, head);
+        let from = from.max(next);
+        let mut decisions = Vec::new();
+        if from <= to {
+            let mut fulfillments = self.fulfillments(query, options, from, to).await?;
+            fulfillments.retain(|fulfillment| !submitted.contains(&fulfillment.attestation.uid));
+            decisions = self.arbitrate(fulfillments, decide, options).await?;
+        }
+        self.processed(head)?;
+        Ok((head, decisions))
+    }
+
+    /// Decides on the fulfillments in batches of `batch_size`, up to
+    /// `max_concurrency` batches at a time, then submits the decisions with up
+    /// to `pipeline_depth` transactions in flight, skipping the batches the
+    /// error policy gives up on.
+    async fn arbitrate<F>(
+        &self,
+        fulfillments: Vec<Fulfillment>,
+        decide: &impl Fn(&[Fulfillment]) -> F,
+        options: &PyArbitrateOptions,
+    ) -> eyre::Result<Vec<Decision>>
+    where
+        F: Future<Output = eyre::Result<Vec<PyVerdict>>>,
+    {
+        let fulfillments: Vec<_> = match &self.checkpoint {
+            Some(checkpoint) => fulfillments
+                .into_iter()
+                .filter(|fulfillment| !checkpoint.is_arbitrated(&fulfillment.attestation.uid))
+                .collect(),
+            None => fulfillments,
+        };
+        let batch_size = options.batch_size.unwrap_or(1).max(1);
+        let verdicts: Vec<_> = stream::iter(fulfillments.chunks(batch_size))
+            .map(|batch| self.attempt(options, || decide(batch)))
+            .buffered(
+                options
+                    .max_concurrency
+                    .unwrap_or(DEFAULT_MAX_CONCURRENCY)
+                    .max(1),
+            )
+            .try_collect()
+            .await?;
+        let decided = fulfillments
+            .chunks(batch_size)
+            .zip(verdicts)
+            .filter_map(|(batch, verdicts)| Some(batch.iter().cloned().zip(verdicts?)))
+            .flatten();
+
+        // Every submission is seen through, so the checkpoint has all that
+        // landed, and they're returned with the first error, if any
+        let submitted: Vec<_> = stream::iter(decided)
+            .map(|(fulfillment, verdict)| async move {
+                let uid = fulfillment.attestation.uid;
+                let transaction_hash = self.submit(uid, &verdict).await?;
+                if let Some(checkpoint) = &self.checkpoint {
+                    checkpoint.arbitrated(uid)?;
+                }
+                Ok::<_, eyre::Report>(Decision {
+                    fulfillment,
+                    verdict,
+                    transaction_hash,
+                })
+            })
+            .buffered(options.pipeline_depth.max(1))
+            .collect()
+            .await;
+        let mut decisions = Vec::with_capacity(submitted.len());
+        let mut failure = None;
+        for result in submitted {
+            match result {
+                Ok(decision) => decisions.push(decision),
+                Err(error) => {
+                    failure.get_or_insert(error);
+                }
+            }
+        }
+        match failure {
+            None => Ok(decisions),
+            Some(error) if decisions.is_empty() => Err(error),
+            Some(error) => Err(Interrupted { decisions, error }.into()),
+        }
+    }
+
+    /// Runs a decision function or callback, retrying it as the error policy
+    /// allows. An error the policy skips is recorded on the listener, if any,
+    /// and gives None.
+    async fn attempt<T, F>(
+        &self,
+        options: &PyArbitrateOptions,
+        mut call: impl FnMut() -> F,
+    ) -> eyre::Result<Option<T>>
+    where
+        F: Future<Output = eyre::Result<T>>,
+    {
+        let mut retries = 0;
+        loop {
+            let error = match call().await {
+                Ok(value) => return Ok(Some(value)),
+                Err(error) if error.is::<Stopped>() => return Err(error),
+                Err(error) => error,
+            };
+            match options.on_error {
+                ErrorPolicy::Abort => return Err(error),
+                ErrorPolicy::Retry if retries < options.max_retries => {
+                    tokio::time::sleep(RETRY_BACKOFF * 2u32.pow(retries.min(6))).await;
+                    retries += 1;
+                }
+                _ => {
+                    if let Some(status) = &self.status {
+                        status.fail(&error);
+                    }
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    /// Sends the decision, with the verdict's reason and payload, if any,
+    /// appended to the calldata after the arguments, which the arbiter ignores.
+    async fn submit(&self, obligation: B256, verdict: &PyVerdict) -> eyre::Result<TxHash> {
+        let mut input = ITrustedOracleArbiter::arbitrateCall {
+            obligation,
+            decision: verdict.decision,
+        }
+        .abi_encode();
+        if verdict.reason.is_some() || !verdict.payload.is_empty() {
+            let reason = verdict.reason.clone().unwrap_or_default();
+            let payload = Bytes::copy_from_slice(&verdict.payload);
+            input.extend((reason, payload).abi_encode_params());
+        }
+        let call = TransactionRequest::default()
+            .to(self.arbiter)
+            .input(Bytes::from(input).into());
+        Ok(self.tx.send(call, None).await?.transaction_hash)
+    }
+
+    /// The verdict an arbitration transaction sent, or None if it isn't one.
+    pub async fn verdict(&self, transaction_hash: TxHash) -> eyre::Result<Option<PyVerdict>> {
+        let provider = self.tx.provider();
+        let Some(transaction) = provider.get_transaction_by_hash(transaction_hash).await? else {
+            return Ok(None);
+        };
+        let input = transaction.input();
+        if transaction.to() != Some(self.arbiter)
+            || !input.starts_with(&ITrustedOracleArbiter::arbitrateCall::SELECTOR)
+        {
+            return Ok(None);
+        }
+        let call = ITrustedOracleArbiter::arbitrateCall::abi_decode(input)?;
+        let mut verdict = PyVerdict {
+            decision: call.decision,
+            ..Default::default()
+        };
+        let appended = &input[ARBITRATE_CALLDATA_LEN.min(input.len())..];
+        if !appended.is_empty() {
+            let (reason, payload) = <(String, Bytes)>::abi_decode_params(appended)?;
+            verdict.reason = Some(reason).filter(|reason| !reason.is_empty());
+            verdict.payload = payload.to_vec();
+        }
+        Ok(Some(verdict))
+    }
+
+    /// The fulfillments matching `query` attested in blocks `from..=to` that
+    /// `options` allow arbitrating.
+    pub async fn fulfillments(
+        &self,
+        query: &Query,
+        options: &PyArbitrateOptions,
+        from: u64,
+        to: u64,
+    ) -> eyre::Result<Vec<Fulfillment>> {
+        let oracle = self.tx.address()?;
+        let mut escrows = HashMap::new();
+        let mut fulfillments = Vec::new();
+        let attestations = self
+            .attestations(&query.fulfillment, from, to, options.max_concurrency)
+            .await?;
+        for attestation in attestations {
+            let Some(obligation) = query.kind.decode(&attestation.data) else {
+                continue;
+            };
+            let escrow = if query.escrow.is_some() || options.require_oracle {
+                let escrow = self
+                    .escrow(&mut escrows, attestation.refUID, query.escrow.as_ref())
+                    .await?;
+                if escrow.is_none() {
+                    continue;
+                }
+                escrow
+            } else {
+                None
+            };
+            if options.require_oracle
+                && escrow.as_ref().map(|escrow| escrow.demand.oracle) != Some(oracle)
+            {
+                continue;
+            }
+            fulfillments.push(Fulfillment {
+                attestation,
+                obligation,
+                escrow,
+            });
+        }
+
+        if options.skip_arbitrated {
+            let arbitrated = self
+                .logged(
+                    ITrustedOracleArbiter::ArbitrationMade::SIGNATURE_HASH,
+                    &fulfillments,
+                    oracle,
+                )
+                .await?;
+            fulfillments.retain(|f| !arbitrated.contains(&f.attestation.uid));
+        }
+        if options.require_request {
+            let requested = self
+                .logged(
+                    ITrustedOracleArbiter::ArbitrationRequested::SIGNATURE_HASH,
+                    &fulfillments,
+                    oracle,
+                )
+                .await?;
+            fulfillments.retain(|f| requested.contains(&f.attestation.uid));
+        }
+        Ok(fulfillments)
+    }
+
+    /// Attestations matching `filter` made in blocks `from..=to`, fetching up
+    /// to `max_concurrency` at a time.
+    async fn attestations(
+        &self,
+        filter: &AttestationFilter,
+        from: u64,
+        to: u64,
+        max_concurrency: Option<usize>,
+    ) -> eyre::Result<Vec<IEAS::Attestation>> {
+        let mut log_filter = Filter::new()
+            .address(self.eas)
+            .event_signature(Attested::SIGNATURE_HASH)
+            .from_block(from)
+            .to_block(to);
+        if let Some(recipient) = &filter.recipient {
+            log_filter = log_filter.topic1(address_topics(recipient));
+        }
+        if let Some(attester) = &filter.attester {
+            log_filter = log_filter.topic2(address_topics(attester));
+        }
+        if let Some(schema) = &filter.schema_uid {
+            log_filter = log_filter.topic3(schema.clone());
+        }
+
+        let mut uids = Vec::new();
+        for log in self.tx.provider().get_logs(&log_filter).await? {
+            let uid = log.log_decode::<Attested>()?.inner.data.uid;
+            if contains(&filter.uid, &uid) {
+                uids.push(uid);
+            }
+        }
+        let eas = IEAS::new(self.eas, self.tx.provider());
+        let attestations: Vec<_> = stream::iter(uids)
+            .map(|uid| {
+                let eas = &eas;
+                async move { eas.getAttestation(uid).call().await }
+            })
+            .buffered(max_concurrency.unwrap_or(DEFAULT_MAX_CONCURRENCY).max(1))
+            .try_collect()
+            .await?;
+        Ok(attestations
+            .into_iter()
+            .filter(|attestation| contains(&filter.ref_uid, &attestation.refUID))
+            .collect())
+    }
+
+    /// The escrow `uid` refers to, if it matches `filter` and leaves the
+    /// decision to `TrustedOracleArbiter`.
+    async fn escrow(
+        &self,
+        cache: &mut HashMap<B256, Option<Escrow>>,
+        uid: B256,
+        filter: Option<&AttestationFilter>,
+    ) -> eyre::Result<Option<Escrow>> {
+        if let Some(escrow) = cache.get(&uid) {
+            return Ok(escrow.clone());
+        }
+        let escrow = if uid.is_zero() {
+            None
+        } else {
+            let attestation = IEAS::new(self.eas, self.tx.provider())
+                .getAttestation(uid)
+                .call()
+                .await?;
+            EscrowArbiter::abi_decode(&attestation.data)
+                .ok()
+                .filter(|escrow| escrow.arbiter == self.arbiter)
+                .filter(|_| filter.is_none_or(|filter| matches(filter, &attestation)))
+                .and_then(|escrow| {
+                    TrustedOracleArbiter::DemandData::abi_decode(&escrow.demand).ok()
+                })
+                .map(|demand| Escrow {
+                    attestation,
+                    demand,
+                })
+        };
+        cache.insert(uid, escrow.clone());
+        Ok(escrow)
+    }
+
+    /// Which of the fulfillments `oracle` has an `event` logged for.
+    async fn logged(
+        &self,
+        event: B256,
+        fulfillments: &[Fulfillment],
+        oracle: Address,
+    ) -> eyre::Result<HashSet<B256>> {
+        let uids: Vec<B256> = fulfillments.iter().map(|f| f.attestation.uid).collect();
+        let mut logged = HashSet::new();
+        for chunk in uids.chunks(UIDS_PER_QUERY) {
+            let filter = Filter::new()
+                .address(self.arbiter)
+                .event_signature(event)
+                .topic1(chunk.to_vec())
+                .topic2(oracle.into_word())
+                .from_block(0);
+            for log in self.tx.provider().get_logs(&filter).await? {
+                if let Some(uid) = log.topics().get(1) {
+                    logged.insert(*uid);
+                }
+            }
+        }
+        Ok(logged)
+    }
+}
+
+/// The blocks `filter` covers, capped at `head`.
+fn block_range(filter: &AttestationFilter, head: u64) -> (u64, u64) {
+    match &filter.block_option {
+        Some(FilterBlockOption::Range {
+            from_block,
+            to_block,
+        }) => (
+            from_block.and_then(|block| block.as_number()).unwrap_or(0),
+            to_block
+                .and_then(|block| block.as_number())
+                .map_or(head, |to| to.min(head)),
+        ),
+        _ => (0, head),
+    }
+}
+
+fn address_topics(addresses: &ValueOrArray<Address>) -> Vec<B256> {
+    match addresses {
+        ValueOrArray::Value(address) => vec![address.into_word()],
+        ValueOrArray::Array(addresses) => addresses.iter().map(|a| a.into_word()).collect(),
+    }
+}
+
+fn contains<T: PartialEq>(allowed: &Option<ValueOrArray<T>>, value: &T) -> bool {
+    match allowed {
+        None => true,
+        Some(ValueOrArray::Value(allowed)) => allowed == value,
+        Some(ValueOrArray::Array(allowed)) => allowed.contains(value),
+    }
+}
+
+/// `filter` as text, for [`Arbitrator::checkpoint_key`].
+fn describe(filter: &AttestationFilter) -> String {
+    fn values<T: fmt::Display>(allowed: &Option<ValueOrArray<T>>) -> String {
+        match allowed {
+            None => "*".to_string(),
+            Some(ValueOrArray::Value(value)) => value.to_string(),
+            Some(ValueOrArray::Array(values)) => values
+                .iter()
+                .map(T::to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+        }
+    }
+    let (from, to) = block_range(filter, u64::MAX);
+    format!(
+        "{from}-{to}:{}:{}:{}:{}:{}",
+        values(&filter.attester),
+        values(&filter.recipient),
+        values(&filter.schema_uid),
+        values(&filter.uid),
+        values(&filter.ref_uid)
+    )
+}
+
+/// Whether an attestation fetched directly matches `filter`, block range aside.
+fn matches(filter: &AttestationFilter, attestation: &IEAS::Attestation) -> bool {
+    contains(&filter.attester, &attestation.attester)
+        && contains(&filter.recipient, &attestation.recipient)
+        && contains(&filter.schema_uid, &attestation.schema)
+        && contains(&filter.uid, &attestation.uid)
+        && contains(&filter.ref_uid, &attestation.refUID)
+}
@@ -0,0 +1,195 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fs,
+    path::{self, PathBuf},
+    sync::{Arc, LazyLock, Mutex, Weak},
+};
+
+use alloy::primitives::B256;
+use serde_json::{json, Map, Value};
+
+// Checkpoint files open in this process, so listeners sharing a file write
+// their entries through one copy of it instead of over each other's
+static FILES: LazyLock<Mutex<HashMap<PathBuf, Weak<File>>>> = LazyLock::new(Default::default);
+
+/// Where an oracle got to, kept in a JSON file so a restarted oracle picks up
+/// where it stopped.
+///
+/// The file has an entry per query, keyed by the oracle, the arbiter and the
+/// filters that select its fulfillments, so several queries can share one. An
+/// entry holds the last block whose fulfillments were all arbitrated, and the
+/// fulfillments arbitrated since, so a range that was cut short isn't decided
+/// twice when it's scanned again. Once a range is done its fulfillments are
+/// covered by the block number, so the set only ever holds one range's worth.
+/// The file is replaced atomically on every change.
+///
+/// Only one run of a query can use its entry at a time; opening it again
+/// while it's in use fails. Separate processes must use separate files.
+/// SQLite databases aren't supported.
+#[derive(Clone)]
+pub struct Checkpoint {
+    entry: Arc<Entry>,
+}
+
+#[derive(Default)]
+struct State {
+    last_block: Option<u64>,
+    arbitrated: BTreeSet<B256>,
+}
+
+struct File {
+    path: PathBuf,
+    contents: Mutex<Contents>,
+}
+
+struct Contents {
+    entries: BTreeMap<String, State>,
+    // Keys with an open Checkpoint
+    open: HashSet<String>,
+}
+
+// One query's entry, released for reopening when the last clone is dropped
+struct Entry {
+    file: Arc<File>,
+    key: String,
+}
+
+impl Drop for Entry {
+    fn drop(&mut self) {
+        self.file.contents.lock().unwrap().open.remove(&self.key);
+    }
+}
+
+impl Checkpoint {
+    /// Opens the entry `key` of the checkpoint at `path`, starting an empty
+    /// one if there's no file or no such entry yet.
+    pub fn open(path: impl Into<PathBuf>, key: String) -> eyre::Result<Self> {
+        let file = File::open(path.into())?;
+        let mut contents = file.contents.lock().unwrap();
+        if !contents.open.insert(key.clone()) {
+            eyre::bail!(
+                "{} is already in use for this query; give each running listener its own checkpoint",
+                file.path.display()
+            );
+        }
+        drop(contents);
+        Ok(Self {
+            entry: Arc::new(Entry { file, key }),
+        })
+    }
+
+    pub fn last_block(&self) -> Option<u64> {
+        self.with_state(|state| state.last_block)
+    }
+
+    pub fn is_arbitrated(&self, uid: &B256) -> bool {
+        self.with_state(|state| state.arbitrated.contains(uid))
+    }
+
+    /// Records a decision on the fulfillment `uid`.
+    pub fn arbitrated(&self, uid: B256) -> eyre::Result<()> {
+        let mut contents = self.entry.file.contents.lock().unwrap();
+        contents
+            .entries
+            .entry(self.entry.key.clone())
+            .or_default()
+            .arbitrated
+            .insert(uid);
+        self.entry.file.save(&contents)
+    }
+
+    /// Records that every fulfillment up to `block` has been arbitrated.
+    pub fn processed(&self, block: u64) -> eyre::Result<()> {
+        let mut contents = self.entry.file.contents.lock().unwrap();
+        let state = contents.entries.entry(self.entry.key.clone()).or_default();
+        if state.last_block >= Some(block) {
+            return Ok(());
+        }
+        state.last_block = Some(block);
+        state.arbitrated.clear();
+        self.entry.file.save(&contents)
+    }
+
+    fn with_state<T: Default>(&self, f: impl FnOnce(&State) -> T) -> T {
+        let contents = self.entry.file.contents.lock().unwrap();
+        contents
+            .entries
+            .get(&self.entry.key)
+            .map(f)
+            .unwrap_or_default()
+    }
+}
+
+impl File {
+    /// The file at `path`, as already open in this process or read from disk.
+    fn open(path: PathBuf) -> eyre::Result<Arc<Self>> {
+        let path = path::absolute(path)?;
+        let mut files = FILES.lock().unwrap();
+        files.retain(|_, file| file.strong_count() > 0);
+        if let Some(file) = files.get(&path).and_then(Weak::upgrade) {
+            return Ok(file);
+        }
+
+        let entries = match fs::read(&path) {
+            Ok(contents) => parse(&serde_json::from_slice(&contents)?).ok_or_else(|| {
+                eyre::eyre!("{} is not an arbitration checkpoint", path.display())
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let file = Arc::new(Self {
+            path: path.clone(),
+            contents: Mutex::new(Contents {
+                entries,
+                open: HashSet::new(),
+            }),
+        });
+        files.insert(path, Arc::downgrade(&file));
+        Ok(file)
+    }
+
+    fn save(&self, contents: &Contents) -> eyre::Result<()> {
+        let entries: Map<String, Value> = contents
+            .entries
+            .iter()
+            .map(|(key, state)| {
+                let entry = json!({
+                    "last_block": state.last_block,
+                    "arbitrated": state.arbitrated.iter().map(|uid| format!("{uid:?}")).collect::<Vec<_>>(),
+                });
+                (key.clone(), entry)
+            })
+            .collect();
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        fs::write(&temporary, serde_json::to_vec_pretty(&entries)?)?;
+        fs::rename(&temporary, &self.path)?;
+        Ok(())
+    }
+}
+
+fn parse(contents: &Value) -> Option<BTreeMap<String, State>> {
+    contents
+        .as_object()?
+        .iter()
+        .map(|(key, entry)| Some((key.clone(), parse_entry(entry)?)))
+        .collect()
+}
+
+fn parse_entry(entry: &Value) -> Option<State> {
+    let entry = entry.as_object()?;
+    let last_block = match entry.get("last_block")? {
+        Value::Null => None,
+        block => Some(block.as_u64()?),
+    };
+    let arbitrated = entry
+        .get("arbitrated")?
+        .as_array()?
+        .iter()
+        .map(|uid| uid.as_str()?.parse().ok())
+        .collect::<Option<_>>()?;
+    Some(State {
+        last_block,
+        arbitrated,
+    })
+}
@@ -1,183 +1,231 @@
-use alkahest_rs::clients::attestation;
-use alloy::primitives::{Address, FixedBytes};
+use alkahest_rs::{
+    clients::attestation,
+    contracts::{
+        AttestationBarterUtils, AttestationEscrowObligation, AttestationEscrowObligation2, IEAS,
+    },
+};
+use alloy::{
+    primitives::{Address, FixedBytes},
+    sol_types::SolValue,
+};
 use pyo3::{pyclass, pymethods, PyResult};
 
 use crate::{
-    error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr},
-    get_attested_event,
-    types::{ArbiterData, AttestationRequest, AttestedLog, LogWithHash},
+    error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr, parse_address},
+    interfaces::ISchemaRegistry,
+    receipt::PyTransactionReceipt,
+    tx::{TxOptions, TxSender},
+    types::{ArbiterData, AttestationRequest},
 };
 
 #[pyclass]
 #[derive(Clone)]
 pub struct AttestationClient {
     inner: attestation::AttestationClient,
+    tx: TxSender,
 }
 
 impl AttestationClient {
-    pub fn new(inner: attestation::AttestationClient) -> Self {
-        Self { inner }
+    pub fn new(inner: attestation::AttestationClient, tx: TxSender) -> Self {
+        Self { inner, tx }
     }
 }
 
 #[pymethods]
 impl AttestationClient {
+    #[pyo3(signature = (schema, resolver, revocable, tx_options=None))]
     pub fn register_schema<'py>(
         &self,
         py: pyo3::Python<'py>,
         schema: String,
         resolver: String,
         revocable: bool,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let tx = self.tx.clone();
+        let registry = self.inner.addresses.eas_schema_registry;
+        crate::runtime::future_into_py(py, async move {
             let schema: FixedBytes<32> = schema.parse().map_err(map_parse_to_pyerr)?;
-            let resolver: Address = resolver.parse().map_err(map_parse_to_pyerr)?;
-            let receipt = inner
-                .register_schema(schema.to_string(), resolver, revocable)
+            let resolver: Address = parse_address(&resolver)?;
+            let call = ISchemaRegistry::new(registry, tx.provider())
+                .register(schema.to_string(), resolver, revocable)
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (attestation, tx_options=None))]
     pub fn attest<'py>(
         &self,
         py: pyo3::Python<'py>,
         attestation: AttestationRequest,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .attest(attestation.try_into().map_err(map_eyre_to_pyerr)?)
+        let tx = self.tx.clone();
+        let eas = self.inner.addresses.eas;
+        crate::runtime::future_into_py(py, async move {
+            let attestation: IEAS::AttestationRequest =
+                attestation.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = IEAS::new(eas, tx.provider())
+                .attest(attestation)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, fulfillment, tx_options=None))]
     pub fn collect_escrow<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
         fulfillment: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .collect_escrow(
+        let tx = self.tx.clone();
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let call = AttestationEscrowObligation::new(escrow_obligation, tx.provider())
+                .collectEscrow(
                     buy_attestation.parse().map_err(map_parse_to_pyerr)?,
                     fulfillment.parse().map_err(map_parse_to_pyerr)?,
                 )
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (buy_attestation, fulfillment, tx_options=None))]
     pub fn collect_escrow_2<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
         fulfillment: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .collect_escrow_2(
+        let tx = self.tx.clone();
+        let escrow_obligation = self.inner.addresses.escrow_obligation_2;
+        crate::runtime::future_into_py(py, async move {
+            let call = AttestationEscrowObligation2::new(escrow_obligation, tx.provider())
+                .collectEscrow(
                     buy_attestation.parse().map_err(map_parse_to_pyerr)?,
                     fulfillment.parse().map_err(map_parse_to_pyerr)?,
                 )
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (attestation, demand, expiration, tx_options=None))]
     pub fn create_escrow<'py>(
         &self,
         py: pyo3::Python<'py>,
         attestation: AttestationRequest,
         demand: ArbiterData,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .create_escrow(
-                    attestation.try_into().map_err(map_eyre_to_pyerr)?,
-                    demand.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let attestation: IEAS::AttestationRequest =
+                attestation.try_into().map_err(map_eyre_to_pyerr)?;
+            let demand: alkahest_rs::types::ArbiterData =
+                demand.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = AttestationEscrowObligation::new(escrow_obligation, tx.provider())
+                .doObligation(
+                    AttestationEscrowObligation::ObligationData {
+                        attestation: convert_request(&attestation).map_err(map_eyre_to_pyerr)?,
+                        arbiter: demand.arbiter,
+                        demand: demand.demand,
+                    },
                     expiration,
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (attestation, demand, expiration, tx_options=None))]
     pub fn create_escrow_2<'py>(
         &self,
         py: pyo3::Python<'py>,
         attestation: String,
         demand: ArbiterData,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .create_escrow_2(
-                    attestation.parse().map_err(map_parse_to_pyerr)?,
-                    demand.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let escrow_obligation = self.inner.addresses.escrow_obligation_2;
+        crate::runtime::future_into_py(py, async move {
+            let demand: alkahest_rs::types::ArbiterData =
+                demand.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = AttestationEscrowObligation2::new(escrow_obligation, tx.provider())
+                .doObligation(
+                    AttestationEscrowObligation2::ObligationData {
+                        attestationUid: attestation.parse().map_err(map_parse_to_pyerr)?,
+                        arbiter: demand.arbiter,
+                        demand: demand.demand,
+                    },
                     expiration,
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (attestation, demand, expiration, tx_options=None))]
     pub fn attest_and_create_escrow<'py>(
         &self,
         py: pyo3::Python<'py>,
         attestation: AttestationRequest,
         demand: ArbiterData,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .attest_and_create_escrow(
-                    attestation.try_into().map_err(map_eyre_to_pyerr)?,
-                    demand.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let attestation: IEAS::AttestationRequest =
+                attestation.try_into().map_err(map_eyre_to_pyerr)?;
+            let demand: alkahest_rs::types::ArbiterData =
+                demand.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = AttestationBarterUtils::new(barter_utils, tx.provider())
+                .attestAndCreateEscrow(
+                    convert_request(&attestation).map_err(map_eyre_to_pyerr)?,
+                    demand.arbiter,
+                    demand.demand,
                     expiration,
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 }
+
+/// Each contract binding generates its own copy of the EAS request struct, so
+/// they're converted through their (identical) ABI encoding.
+fn convert_request<T: SolValue + From<<T::SolType as alloy::sol_types::SolType>::RustType>>(
+    request: &IEAS::AttestationRequest,
+) -> eyre::Result<T> {
+    Ok(T::abi_decode(&request.abi_encode())?)
+}
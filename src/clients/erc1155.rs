@@ -1,359 +1,401 @@
-use alkahest_rs::clients::erc1155;
+use alkahest_rs::{
+    clients::erc1155,
+    contracts::{ERC1155BarterCrossToken, ERC1155EscrowObligation, ERC1155PaymentObligation},
+};
 use alloy::primitives::Address;
 use pyo3::{pyclass, pymethods, PyResult};
 
 use crate::{
-    error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr},
-    get_attested_event,
-    types::{
-        ArbiterData, AttestedLog, Erc1155Data, Erc20Data, Erc721Data, LogWithHash, TokenBundleData,
-    },
+    error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr, parse_address},
+    interfaces::IERC1155,
+    receipt::PyTransactionReceipt,
+    tx::{TxOptions, TxSender},
+    types::{ArbiterData, Erc1155Data, Erc20Data, Erc721Data, PyU256, TokenBundleData},
 };
 
 #[pyclass]
 #[derive(Clone)]
 pub struct Erc1155Client {
     inner: erc1155::Erc1155Client,
+    tx: TxSender,
 }
 
 impl Erc1155Client {
-    pub fn new(inner: erc1155::Erc1155Client) -> Self {
-        Self { inner }
+    pub fn new(inner: erc1155::Erc1155Client, tx: TxSender) -> Self {
+        Self { inner, tx }
     }
 }
 
 #[pymethods]
 impl Erc1155Client {
+    #[pyo3(signature = (token_contract, purpose, tx_options=None))]
     pub fn approve_all<'py>(
         &self,
         py: pyo3::Python<'py>,
         token_contract: String,
         purpose: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let token_contract: Address = token_contract.parse().map_err(map_parse_to_pyerr)?;
-            let purpose = match purpose.as_str() {
-                "payment" => alkahest_rs::types::ApprovalPurpose::Payment,
-                "escrow" => alkahest_rs::types::ApprovalPurpose::Escrow,
-                _ => return Err(map_eyre_to_pyerr(eyre::eyre!("Invalid purpose"))),
-            };
-            let receipt = inner
-                .approve_all(token_contract, purpose)
+        let tx = self.tx.clone();
+        let operator = approval_spender(&self.inner.addresses, &purpose)?;
+        crate::runtime::future_into_py(py, async move {
+            let token_contract: Address = parse_address(&token_contract)?;
+            let call = IERC1155::new(token_contract, tx.provider())
+                .setApprovalForAll(operator, true)
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (token_contract, purpose, tx_options=None))]
     pub fn revoke_all<'py>(
         &self,
         py: pyo3::Python<'py>,
         token_contract: String,
         purpose: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let token_contract: Address = token_contract.parse().map_err(map_parse_to_pyerr)?;
-            let purpose = match purpose.as_str() {
-                "payment" => alkahest_rs::types::ApprovalPurpose::Payment,
-                "escrow" => alkahest_rs::types::ApprovalPurpose::Escrow,
-                _ => return Err(map_eyre_to_pyerr(eyre::eyre!("Invalid purpose"))),
-            };
-            let receipt = inner
-                .revoke_all(token_contract, purpose)
+        let tx = self.tx.clone();
+        let operator = approval_spender(&self.inner.addresses, &purpose)?;
+        crate::runtime::future_into_py(py, async move {
+            let token_contract: Address = parse_address(&token_contract)?;
+            let call = IERC1155::new(token_contract, tx.provider())
+                .setApprovalForAll(operator, false)
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (buy_attestation, fulfillment, tx_options=None))]
     pub fn collect_escrow<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
         fulfillment: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .collect_escrow(
+        let tx = self.tx.clone();
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC1155EscrowObligation::new(escrow_obligation, tx.provider())
+                .collectEscrow(
                     buy_attestation.parse().map_err(map_parse_to_pyerr)?,
                     fulfillment.parse().map_err(map_parse_to_pyerr)?,
                 )
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn reclaim_expired<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .reclaim_expired(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC1155EscrowObligation::new(escrow_obligation, tx.provider())
+                .reclaimExpired(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (price, item, expiration, tx_options=None))]
     pub fn buy_with_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: Erc1155Data,
         item: ArbiterData,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_with_erc1155(
-                    &price.try_into().map_err(map_eyre_to_pyerr)?,
-                    &item.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let price: alkahest_rs::types::Erc1155Data =
+                price.try_into().map_err(map_eyre_to_pyerr)?;
+            let item: alkahest_rs::types::ArbiterData =
+                item.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC1155EscrowObligation::new(escrow_obligation, tx.provider())
+                .doObligation(
+                    ERC1155EscrowObligation::ObligationData {
+                        token: price.address,
+                        tokenId: price.id,
+                        amount: price.value,
+                        arbiter: item.arbiter,
+                        demand: item.demand,
+                    },
                     expiration,
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (price, payee, tx_options=None))]
     pub fn pay_with_erc_1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: Erc1155Data,
         payee: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let payee: Address = payee.parse().map_err(map_parse_to_pyerr)?;
-            let receipt = inner
-                .pay_with_erc1155(&price.try_into().map_err(map_eyre_to_pyerr)?, payee)
+        let tx = self.tx.clone();
+        let payment_obligation = self.inner.addresses.payment_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let price: alkahest_rs::types::Erc1155Data =
+                price.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC1155PaymentObligation::new(payment_obligation, tx.provider())
+                .doObligation(ERC1155PaymentObligation::ObligationData {
+                    token: price.address,
+                    tokenId: price.id,
+                    amount: price.value,
+                    payee: parse_address(&payee)?,
+                })
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None))]
     pub fn buy_erc1155_for_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc1155Data,
         ask: Erc1155Data,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_erc1155_for_erc1155(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::Erc1155Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::Erc1155Data = ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC1155BarterCrossToken::new(barter_utils, tx.provider())
+                .buyErc1155ForErc1155(
+                    bid.address,
+                    bid.id,
+                    bid.value,
+                    ask.address,
+                    ask.id,
+                    ask.value,
                     expiration,
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn pay_erc1155_for_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .pay_erc1155_for_erc1155(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC1155BarterCrossToken::new(barter_utils, tx.provider())
+                .payErc1155ForErc1155(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None))]
     pub fn buy_erc20_with_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc1155Data,
         ask: Erc20Data,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_erc20_with_erc1155(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::Erc1155Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::Erc20Data = ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC1155BarterCrossToken::new(barter_utils, tx.provider())
+                .buyErc20WithErc1155(
+                    bid.address,
+                    bid.id,
+                    bid.value,
+                    ask.address,
+                    ask.value,
                     expiration,
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn pay_erc1155_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .pay_erc1155_for_erc20(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC1155BarterCrossToken::new(barter_utils, tx.provider())
+                .payErc1155ForErc20(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None))]
     pub fn buy_erc721_with_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc1155Data,
         ask: Erc721Data,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_erc721_with_erc1155(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::Erc1155Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::Erc721Data = ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC1155BarterCrossToken::new(barter_utils, tx.provider())
+                .buyErc721WithErc1155(
+                    bid.address,
+                    bid.id,
+                    bid.value,
+                    ask.address,
+                    ask.id,
                     expiration,
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn pay_erc1155_for_erc721<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .pay_erc1155_for_erc721(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC1155BarterCrossToken::new(barter_utils, tx.provider())
+                .payErc1155ForErc721(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None))]
     pub fn buy_bundle_with_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc1155Data,
         ask: TokenBundleData,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_bundle_with_erc1155(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::Erc1155Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::TokenBundleData =
+                ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC1155BarterCrossToken::new(barter_utils, tx.provider())
+                .buyBundleWithErc1155(
+                    bid.address,
+                    bid.id,
+                    bid.value,
+                    (ask, tx.address().map_err(map_eyre_to_pyerr)?).into(),
                     expiration,
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn pay_erc1155_for_bundle<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .pay_erc1155_for_bundle(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC1155BarterCrossToken::new(barter_utils, tx.provider())
+                .payErc1155ForBundle(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 }
 
+fn approval_spender(addresses: &erc1155::Erc1155Addresses, purpose: &str) -> PyResult<Address> {
+    match purpose {
+        "payment" => Ok(addresses.payment_obligation),
+        "escrow" => Ok(addresses.escrow_obligation),
+        _ => Err(map_eyre_to_pyerr(eyre::eyre!("Invalid purpose"))),
+    }
+}
+
 #[pyclass]
 #[derive(Clone)]
 pub struct PyERC1155EscrowObligationData {
     #[pyo3(get)]
     pub token: String,
     #[pyo3(get)]
-    pub token_id: String,
+    pub token_id: PyU256,
     #[pyo3(get)]
-    pub amount: String,
+    pub amount: PyU256,
     #[pyo3(get)]
     pub arbiter: String,
     #[pyo3(get)]
@@ -365,8 +407,8 @@ impl PyERC1155EscrowObligationData {
     #[new]
     pub fn new(
         token: String,
-        token_id: String,
-        amount: String,
+        token_id: PyU256,
+        amount: PyU256,
         arbiter: String,
         demand: Vec<u8>,
     ) -> Self {
@@ -381,7 +423,7 @@ impl PyERC1155EscrowObligationData {
 
     pub fn __repr__(&self) -> String {
         format!(
-            "PyERC1155EscrowObligationData(token='{}', token_id='{}', amount='{}', arbiter='{}', demand={:?})",
+            "PyERC1155EscrowObligationData(token='{}', token_id={}, amount={}, arbiter='{}', demand={:?})",
             self.token, self.token_id, self.amount, self.arbiter, self.demand
         )
     }
@@ -404,10 +446,10 @@ impl PyERC1155EscrowObligationData {
             sol_types::SolValue,
         };
 
-        let token: Address = obligation.token.parse().map_err(map_parse_to_pyerr)?;
-        let token_id: U256 = obligation.token_id.parse().map_err(map_parse_to_pyerr)?;
-        let amount: U256 = obligation.amount.parse().map_err(map_parse_to_pyerr)?;
-        let arbiter: Address = obligation.arbiter.parse().map_err(map_parse_to_pyerr)?;
+        let token: Address = parse_address(&obligation.token)?;
+        let token_id: U256 = obligation.token_id.into();
+        let amount: U256 = obligation.amount.into();
+        let arbiter: Address = parse_address(&obligation.arbiter)?;
         let demand = Bytes::from(obligation.demand.clone());
 
         let obligation_data = ERC1155EscrowObligation::ObligationData {
@@ -432,8 +474,8 @@ impl From<alkahest_rs::contracts::ERC1155EscrowObligation::ObligationData>
     fn from(data: alkahest_rs::contracts::ERC1155EscrowObligation::ObligationData) -> Self {
         Self {
             token: format!("{:?}", data.token),
-            token_id: data.tokenId.to_string(),
-            amount: data.amount.to_string(),
+            token_id: data.tokenId.into(),
+            amount: data.amount.into(),
             arbiter: format!("{:?}", data.arbiter),
             demand: data.demand.to_vec(),
         }
@@ -446,9 +488,9 @@ pub struct PyERC1155PaymentObligationData {
     #[pyo3(get)]
     pub token: String,
     #[pyo3(get)]
-    pub token_id: String,
+    pub token_id: PyU256,
     #[pyo3(get)]
-    pub amount: String,
+    pub amount: PyU256,
     #[pyo3(get)]
     pub payee: String,
 }
@@ -456,7 +498,7 @@ pub struct PyERC1155PaymentObligationData {
 #[pymethods]
 impl PyERC1155PaymentObligationData {
     #[new]
-    pub fn new(token: String, token_id: String, amount: String, payee: String) -> Self {
+    pub fn new(token: String, token_id: PyU256, amount: PyU256, payee: String) -> Self {
         Self {
             token,
             token_id,
@@ -467,7 +509,7 @@ impl PyERC1155PaymentObligationData {
 
     pub fn __repr__(&self) -> String {
         format!(
-            "PyERC1155PaymentObligationData(token='{}', token_id='{}', amount='{}', payee='{}')",
+            "PyERC1155PaymentObligationData(token='{}', token_id={}, amount={}, payee='{}')",
             self.token, self.token_id, self.amount, self.payee
         )
     }
@@ -490,10 +532,10 @@ impl PyERC1155PaymentObligationData {
             sol_types::SolValue,
         };
 
-        let token: Address = obligation.token.parse().map_err(map_parse_to_pyerr)?;
-        let token_id: U256 = obligation.token_id.parse().map_err(map_parse_to_pyerr)?;
-        let amount: U256 = obligation.amount.parse().map_err(map_parse_to_pyerr)?;
-        let payee: Address = obligation.payee.parse().map_err(map_parse_to_pyerr)?;
+        let token: Address = parse_address(&obligation.token)?;
+        let token_id: U256 = obligation.token_id.into();
+        let amount: U256 = obligation.amount.into();
+        let payee: Address = parse_address(&obligation.payee)?;
 
         let obligation_data = ERC1155PaymentObligation::ObligationData {
             token,
@@ -516,8 +558,8 @@ impl From<alkahest_rs::contracts::ERC1155PaymentObligation::ObligationData>
     fn from(data: alkahest_rs::contracts::ERC1155PaymentObligation::ObligationData) -> Self {
         Self {
             token: format!("{:?}", data.token),
-            token_id: data.tokenId.to_string(),
-            amount: data.amount.to_string(),
+            token_id: data.tokenId.into(),
+            amount: data.amount.into(),
             payee: format!("{:?}", data.payee),
         }
     }
@@ -1,617 +1,1117 @@
-use alkahest_rs::clients::erc20;
-use pyo3::{pyclass, pymethods, PyResult};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use alkahest_rs::{
+    clients::erc20,
+    contracts::{
+        ERC1155EscrowObligation, ERC20BarterCrossToken, ERC20EscrowObligation,
+        ERC20PaymentObligation, ERC721EscrowObligation, TokenBundleEscrowObligation, IEAS,
+    },
+};
+use alloy::{
+    primitives::{keccak256, Address, Bytes, FixedBytes, Signature, U256},
+    rpc::types::TransactionReceipt,
+    sol_types::SolValue,
+};
+use pyo3::{
+    pyclass, pymethods,
+    types::{PyAnyMethods, PyDictMethods},
+    FromPyObject, PyErr, PyResult,
+};
 
 use crate::{
-    error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr},
-    get_attested_event,
-    types::{
-        ArbiterData, AttestedLog, Erc1155Data, Erc20Data, Erc721Data, LogWithHash, TokenBundleData,
+    error_handling::{
+        map_eyre_to_pyerr, map_parse_to_pyerr, map_sol_decode_to_pyerr, parse_address,
     },
+    interfaces::{IERC20Permit, IERC20},
+    receipt::PyTransactionReceipt,
+    tx::{TxOptions, TxSender},
+    types::{ArbiterData, Erc1155Data, Erc20Data, Erc721Data, PyU256, TokenBundleData},
 };
 
 #[pyclass]
 #[derive(Clone)]
 pub struct Erc20Client {
     inner: erc20::Erc20Client,
+    tx: TxSender,
 }
 
 impl Erc20Client {
-    pub fn new(inner: erc20::Erc20Client) -> Self {
-        Self { inner }
+    pub fn new(inner: erc20::Erc20Client, tx: TxSender) -> Self {
+        Self { inner, tx }
     }
 }
 
 #[pymethods]
 impl Erc20Client {
+    #[pyo3(signature = (token, purpose, tx_options=None))]
     pub fn approve<'py>(
         &self,
         py: pyo3::Python<'py>,
         token: Erc20Data,
         purpose: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let purpose = match purpose.as_str() {
-                "payment" => alkahest_rs::types::ApprovalPurpose::Payment,
-                "escrow" => alkahest_rs::types::ApprovalPurpose::Escrow,
-                _ => return Err(pyo3::exceptions::PyValueError::new_err("Invalid purpose")),
-            };
-            let receipt = inner
-                .approve(&token.try_into().map_err(map_eyre_to_pyerr)?, purpose)
+        let tx = self.tx.clone();
+        let spender = approval_spender(&self.inner.addresses, &purpose)?;
+        crate::runtime::future_into_py(py, async move {
+            let token: alkahest_rs::types::Erc20Data =
+                token.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = IERC20::new(token.address, tx.provider())
+                .approve(spender, token.value)
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
 
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (token, purpose, tx_options=None))]
     pub fn approve_if_less<'py>(
         &self,
         py: pyo3::Python<'py>,
         token: Erc20Data,
         purpose: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let purpose = match purpose.as_str() {
-                "payment" => alkahest_rs::types::ApprovalPurpose::Payment,
-                "escrow" => alkahest_rs::types::ApprovalPurpose::Escrow,
-                _ => return Err(pyo3::exceptions::PyValueError::new_err("Invalid purpose")),
-            };
-            let receipt = inner
-                .approve_if_less(&token.try_into().map_err(map_eyre_to_pyerr)?, purpose)
+        let tx = self.tx.clone();
+        let spender = approval_spender(&self.inner.addresses, &purpose)?;
+        crate::runtime::future_into_py(py, async move {
+            let token: alkahest_rs::types::Erc20Data =
+                token.try_into().map_err(map_eyre_to_pyerr)?;
+            let erc20 = IERC20::new(token.address, tx.provider());
+            let allowance = erc20
+                .allowance(tx.address().map_err(map_eyre_to_pyerr)?, spender)
+                .call()
+                .await
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+            if allowance >= token.value {
+                return Ok(None);
+            }
+
+            let call = erc20
+                .approve(spender, token.value)
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
 
-            Ok(receipt.map(|x| x.transaction_hash.to_string()))
+            Ok(Some(PyTransactionReceipt::from(receipt)))
+        })
+    }
+
+    /// Signs an EIP-2612 permit for `token` without sending anything, letting
+    /// the escrow or payment obligation (by `purpose`) pull `token.value`. Pass
+    /// the result as `permit=` to a `permit_and_*` method, from this client or
+    /// from a relayer's. Only hand it to a relayer you trust with the amount:
+    /// it doesn't bind the escrow's terms or the payee. With `nonce` and
+    /// `domain_separator` given, no RPC calls are made.
+    #[pyo3(signature = (token, purpose, deadline=None, nonce=None, domain_separator=None))]
+    pub fn sign_permit<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        token: Erc20Data,
+        purpose: String,
+        deadline: Option<PyU256>,
+        nonce: Option<PyU256>,
+        domain_separator: Option<String>,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let tx = self.tx.clone();
+        let spender = approval_spender(&self.inner.addresses, &purpose)?;
+        let domain_separator: Option<FixedBytes<32>> = domain_separator
+            .map(|separator| separator.parse())
+            .transpose()
+            .map_err(map_parse_to_pyerr)?;
+        crate::runtime::future_into_py(py, async move {
+            let token: alkahest_rs::types::Erc20Data =
+                token.try_into().map_err(map_eyre_to_pyerr)?;
+            let deadline = match deadline {
+                Some(deadline) => deadline.into(),
+                None => permit_deadline().map_err(map_eyre_to_pyerr)?,
+            };
+            let permit = sign_permit(
+                &tx,
+                &token,
+                spender,
+                deadline,
+                nonce.map(Into::into),
+                domain_separator,
+            )
+            .await
+            .map_err(map_eyre_to_pyerr)?;
+            Ok(PyPermitSignature::from(permit))
+        })
+    }
+
+    /// Submits a permit signed by another account, e.g. ahead of a
+    /// `permit_and_*` call the owner will send.
+    #[pyo3(signature = (permit, tx_options=None))]
+    pub fn submit_permit<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        permit: Permit,
+        tx_options: Option<TxOptions>,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        let tx = self.tx.clone();
+        crate::runtime::future_into_py(py, async move {
+            let receipt = relay_permit(&tx, &permit, tx_options.as_ref())
+                .await
+                .map_err(map_eyre_to_pyerr)?;
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (buy_attestation, fulfillment, tx_options=None))]
     pub fn collect_escrow<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
         fulfillment: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .collect_escrow(
+        let tx = self.tx.clone();
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC20EscrowObligation::new(escrow_obligation, tx.provider())
+                .collectEscrow(
                     buy_attestation.parse().map_err(map_parse_to_pyerr)?,
                     fulfillment.parse().map_err(map_parse_to_pyerr)?,
                 )
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn reclaim_expired<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .reclaim_expired(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC20EscrowObligation::new(escrow_obligation, tx.provider())
+                .reclaimExpired(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (price, item, expiration, tx_options=None))]
     pub fn buy_with_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: Erc20Data,
         item: ArbiterData,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_with_erc20(
-                    &price.try_into().map_err(map_eyre_to_pyerr)?,
-                    &item.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let price: alkahest_rs::types::Erc20Data =
+                price.try_into().map_err(map_eyre_to_pyerr)?;
+            let item: alkahest_rs::types::ArbiterData =
+                item.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC20EscrowObligation::new(escrow_obligation, tx.provider())
+                .doObligation(
+                    ERC20EscrowObligation::ObligationData {
+                        token: price.address,
+                        amount: price.value,
+                        arbiter: item.arbiter,
+                        demand: item.demand,
+                    },
                     expiration,
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// Escrows `price` for `item` with an EIP-2612 permit instead of an
+    /// approval. A permit signed by another account is relayed: the permit is
+    /// submitted, then the escrow is made for its owner in a second
+    /// transaction. The permit only binds the token, spender, amount and
+    /// deadline, not the arbiter, demand or expiration, so a relayer is fully
+    /// trusted: once the permit is on chain, it can escrow the owner's tokens
+    /// with any terms, including an arbiter it controls, and collect them.
+    #[pyo3(signature = (price, item, expiration, tx_options=None, permit=None))]
     pub fn permit_and_buy_with_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: Erc20Data,
         item: ArbiterData,
         expiration: u64,
+        tx_options: Option<TxOptions>,
+        permit: Option<Permit>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
             let price: alkahest_rs::types::Erc20Data =
                 price.try_into().map_err(map_eyre_to_pyerr)?;
             let item: alkahest_rs::types::ArbiterData =
                 item.try_into().map_err(map_eyre_to_pyerr)?;
-
-            match inner
-                .permit_and_buy_with_erc20(&price, &item, expiration)
-                .await
-            {
-                Ok(receipt) => Ok(LogWithHash::<AttestedLog> {
-                    log: get_attested_event(receipt.clone())
-                        .map_err(map_eyre_to_pyerr)?
-                        .data
-                        .into(),
-                    transaction_hash: receipt.transaction_hash.to_string(),
-                }),
-                Err(e) => {
-                    Err(map_eyre_to_pyerr(e))
-                }
-            }
+            let permit = permit_for(&tx, permit, &price, escrow_obligation)
+                .await
+                .map_err(map_eyre_to_pyerr)?;
+            let log = if is_own_permit(&tx, &permit).map_err(map_eyre_to_pyerr)? {
+                let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                    .permitAndBuyWithErc20(
+                        price.address,
+                        price.value,
+                        item.arbiter,
+                        item.demand,
+                        expiration,
+                        permit.deadline,
+                        permit.v(),
+                        permit.r(),
+                        permit.s(),
+                    )
+                    .into_transaction_request();
+                tx.send_attested(call, tx_options.as_ref())
+                    .await
+                    .map_err(map_eyre_to_pyerr)?
+            } else {
+                // Relayed: the barter contract would take the sender as the
+                // owner, so the permit and the escrow are sent separately
+                relay_permit(&tx, &permit, tx_options.as_ref())
+                    .await
+                    .map_err(map_eyre_to_pyerr)?;
+                let call = ERC20EscrowObligation::new(escrow_obligation, tx.provider())
+                    .doObligationFor(
+                        ERC20EscrowObligation::ObligationData {
+                            token: price.address,
+                            amount: price.value,
+                            arbiter: item.arbiter,
+                            demand: item.demand,
+                        },
+                        expiration,
+                        permit.owner,
+                        permit.owner,
+                    )
+                    .into_transaction_request();
+                let options = tx_options.as_ref().map(|options| options.for_sequence(1));
+                tx.send_attested(call, options.as_ref())
+                    .await
+                    .map_err(map_eyre_to_pyerr)?
+            };
+            Ok(log)
         })
     }
 
+    #[pyo3(signature = (price, payee, tx_options=None))]
     pub fn pay_with_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: Erc20Data,
         payee: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .pay_with_erc20(
-                    &price.try_into().map_err(map_eyre_to_pyerr)?,
-                    payee.parse().map_err(map_parse_to_pyerr)?,
-                )
+        let tx = self.tx.clone();
+        let payment_obligation = self.inner.addresses.payment_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let price: alkahest_rs::types::Erc20Data =
+                price.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC20PaymentObligation::new(payment_obligation, tx.provider())
+                .doObligation(ERC20PaymentObligation::ObligationData {
+                    token: price.address,
+                    amount: price.value,
+                    payee: parse_address(&payee)?,
+                })
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    /// Pays `price` to `payee` with an EIP-2612 permit instead of an approval.
+    /// A permit signed by another account is relayed as in
+    /// `permit_and_buy_with_erc20`, and the relayer is just as trusted: the
+    /// permit doesn't bind the payee, so it can pay the owner's tokens to
+    /// anyone, itself included.
+    #[pyo3(signature = (price, payee, tx_options=None, permit=None))]
     pub fn permit_and_pay_with_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: Erc20Data,
         payee: String,
+        tx_options: Option<TxOptions>,
+        permit: Option<Permit>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_pay_with_erc20(
-                    &price.try_into().map_err(map_eyre_to_pyerr)?,
-                    payee.parse().map_err(map_parse_to_pyerr)?,
-                )
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        let payment_obligation = self.inner.addresses.payment_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let price: alkahest_rs::types::Erc20Data =
+                price.try_into().map_err(map_eyre_to_pyerr)?;
+            let payee: Address = parse_address(&payee)?;
+            let permit = permit_for(&tx, permit, &price, payment_obligation)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
+            let log = if is_own_permit(&tx, &permit).map_err(map_eyre_to_pyerr)? {
+                let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                    .permitAndPayWithErc20(
+                        price.address,
+                        price.value,
+                        payee,
+                        permit.deadline,
+                        permit.v(),
+                        permit.r(),
+                        permit.s(),
+                    )
+                    .into_transaction_request();
+                tx.send_attested(call, tx_options.as_ref())
+                    .await
                     .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            } else {
+                // Relayed, as in permit_and_buy_with_erc20
+                relay_permit(&tx, &permit, tx_options.as_ref())
+                    .await
+                    .map_err(map_eyre_to_pyerr)?;
+                let call = ERC20PaymentObligation::new(payment_obligation, tx.provider())
+                    .doObligationFor(
+                        ERC20PaymentObligation::ObligationData {
+                            token: price.address,
+                            amount: price.value,
+                            payee,
+                        },
+                        permit.owner,
+                        permit.owner,
+                    )
+                    .into_transaction_request();
+                let options = tx_options.as_ref().map(|options| options.for_sequence(1));
+                tx.send_attested(call, options.as_ref())
+                    .await
+                    .map_err(map_eyre_to_pyerr)?
+            };
+            Ok(log)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None))]
     pub fn buy_erc20_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc20Data,
         ask: Erc20Data,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_erc20_for_erc20(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
-                    expiration,
-                )
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::Erc20Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::Erc20Data = ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                .buyErc20ForErc20(bid.address, bid.value, ask.address, ask.value, expiration)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None, permit=None))]
     pub fn permit_and_buy_erc20_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc20Data,
         ask: Erc20Data,
         expiration: u64,
+        tx_options: Option<TxOptions>,
+        permit: Option<Permit>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_buy_erc20_for_erc20(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::Erc20Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::Erc20Data = ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let permit = permit_for(&tx, permit, &bid, escrow_obligation)
+                .await
+                .map_err(map_eyre_to_pyerr)?;
+            ensure_own_permit(&tx, &permit).map_err(map_eyre_to_pyerr)?;
+            let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                .permitAndBuyErc20ForErc20(
+                    bid.address,
+                    bid.value,
+                    ask.address,
+                    ask.value,
                     expiration,
+                    permit.deadline,
+                    permit.v(),
+                    permit.r(),
+                    permit.s(),
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn pay_erc20_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .pay_erc20_for_erc20(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                .payErc20ForErc20(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None, permit=None))]
     pub fn permit_and_pay_erc20_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
+        permit: Option<Permit>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_pay_erc20_for_erc20(
-                    buy_attestation.parse().map_err(map_parse_to_pyerr)?,
-                )
+        let tx = self.tx.clone();
+        let eas = self.inner.addresses.eas;
+        let barter_utils = self.inner.addresses.barter_utils;
+        let payment_obligation = self.inner.addresses.payment_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let buy_attestation: FixedBytes<32> =
+                buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+            let escrow = get_attestation(&tx, eas, buy_attestation)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            let escrow = ERC20EscrowObligation::ObligationData::abi_decode(&escrow.data)
+                .map_err(map_sol_decode_to_pyerr)?;
+            let price = erc20_demand(&escrow.demand).map_err(map_sol_decode_to_pyerr)?;
+            let permit = permit_for(&tx, permit, &price, payment_obligation)
+                .await
+                .map_err(map_eyre_to_pyerr)?;
+            ensure_own_permit(&tx, &permit).map_err(map_eyre_to_pyerr)?;
+            let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                .permitAndPayErc20ForErc20(
+                    buy_attestation,
+                    permit.deadline,
+                    permit.v(),
+                    permit.r(),
+                    permit.s(),
+                )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
+                .await
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None))]
     pub fn buy_erc721_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc20Data,
         ask: Erc721Data,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_erc721_for_erc20(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
-                    expiration,
-                )
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::Erc20Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::Erc721Data = ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                .buyErc721WithErc20(bid.address, bid.value, ask.address, ask.id, expiration)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None, permit=None))]
     pub fn permit_and_buy_erc721_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc20Data,
         ask: Erc721Data,
         expiration: u64,
+        tx_options: Option<TxOptions>,
+        permit: Option<Permit>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_buy_erc721_for_erc20(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::Erc20Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::Erc721Data = ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let permit = permit_for(&tx, permit, &bid, escrow_obligation)
+                .await
+                .map_err(map_eyre_to_pyerr)?;
+            ensure_own_permit(&tx, &permit).map_err(map_eyre_to_pyerr)?;
+            let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                .permitAndBuyErc721WithErc20(
+                    bid.address,
+                    bid.value,
+                    ask.address,
+                    ask.id,
                     expiration,
+                    permit.deadline,
+                    permit.v(),
+                    permit.r(),
+                    permit.s(),
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn pay_erc20_for_erc721<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .pay_erc20_for_erc721(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                .payErc20ForErc721(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None, permit=None))]
     pub fn permit_and_pay_erc20_for_erc721<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
+        permit: Option<Permit>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_pay_erc20_for_erc721(
-                    buy_attestation.parse().map_err(map_parse_to_pyerr)?,
-                )
+        let tx = self.tx.clone();
+        let eas = self.inner.addresses.eas;
+        let barter_utils = self.inner.addresses.barter_utils;
+        let payment_obligation = self.inner.addresses.payment_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let buy_attestation: FixedBytes<32> =
+                buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+            let escrow = get_attestation(&tx, eas, buy_attestation)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            let escrow = ERC721EscrowObligation::ObligationData::abi_decode(&escrow.data)
+                .map_err(map_sol_decode_to_pyerr)?;
+            let price = erc20_demand(&escrow.demand).map_err(map_sol_decode_to_pyerr)?;
+            let permit = permit_for(&tx, permit, &price, payment_obligation)
+                .await
+                .map_err(map_eyre_to_pyerr)?;
+            ensure_own_permit(&tx, &permit).map_err(map_eyre_to_pyerr)?;
+            let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                .permitAndPayErc20ForErc721(
+                    buy_attestation,
+                    permit.deadline,
+                    permit.v(),
+                    permit.r(),
+                    permit.s(),
+                )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
+                .await
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None))]
     pub fn buy_erc1155_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc20Data,
         ask: Erc1155Data,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_erc1155_for_erc20(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::Erc20Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::Erc1155Data = ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                .buyErc1155WithErc20(
+                    bid.address,
+                    bid.value,
+                    ask.address,
+                    ask.id,
+                    ask.value,
                     expiration,
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None, permit=None))]
     pub fn permit_and_buy_erc1155_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc20Data,
         ask: Erc1155Data,
         expiration: u64,
+        tx_options: Option<TxOptions>,
+        permit: Option<Permit>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_buy_erc1155_for_erc20(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::Erc20Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::Erc1155Data = ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let permit = permit_for(&tx, permit, &bid, escrow_obligation)
+                .await
+                .map_err(map_eyre_to_pyerr)?;
+            ensure_own_permit(&tx, &permit).map_err(map_eyre_to_pyerr)?;
+            let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                .permitAndBuyErc1155WithErc20(
+                    bid.address,
+                    bid.value,
+                    ask.address,
+                    ask.id,
+                    ask.value,
                     expiration,
+                    permit.deadline,
+                    permit.v(),
+                    permit.r(),
+                    permit.s(),
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn pay_erc20_for_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .pay_erc20_for_erc1155(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                .payErc20ForErc1155(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None, permit=None))]
     pub fn permit_and_pay_erc20_for_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
+        permit: Option<Permit>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_pay_erc20_for_erc1155(
-                    buy_attestation.parse().map_err(map_parse_to_pyerr)?,
-                )
+        let tx = self.tx.clone();
+        let eas = self.inner.addresses.eas;
+        let barter_utils = self.inner.addresses.barter_utils;
+        let payment_obligation = self.inner.addresses.payment_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let buy_attestation: FixedBytes<32> =
+                buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+            let escrow = get_attestation(&tx, eas, buy_attestation)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            let escrow = ERC1155EscrowObligation::ObligationData::abi_decode(&escrow.data)
+                .map_err(map_sol_decode_to_pyerr)?;
+            let price = erc20_demand(&escrow.demand).map_err(map_sol_decode_to_pyerr)?;
+            let permit = permit_for(&tx, permit, &price, payment_obligation)
+                .await
+                .map_err(map_eyre_to_pyerr)?;
+            ensure_own_permit(&tx, &permit).map_err(map_eyre_to_pyerr)?;
+            let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                .permitAndPayErc20ForErc1155(
+                    buy_attestation,
+                    permit.deadline,
+                    permit.v(),
+                    permit.r(),
+                    permit.s(),
+                )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
+                .await
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None))]
     pub fn buy_bundle_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc20Data,
         ask: TokenBundleData,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_bundle_for_erc20(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::Erc20Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::TokenBundleData =
+                ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                .buyBundleWithErc20(
+                    bid.address,
+                    bid.value,
+                    (ask, tx.address().map_err(map_eyre_to_pyerr)?).into(),
                     expiration,
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None, permit=None))]
     pub fn permit_and_buy_bundle_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc20Data,
         ask: TokenBundleData,
         expiration: u64,
+        tx_options: Option<TxOptions>,
+        permit: Option<Permit>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_buy_bundle_for_erc20(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::Erc20Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::TokenBundleData =
+                ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let permit = permit_for(&tx, permit, &bid, escrow_obligation)
+                .await
+                .map_err(map_eyre_to_pyerr)?;
+            ensure_own_permit(&tx, &permit).map_err(map_eyre_to_pyerr)?;
+            let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                .permitAndBuyBundleWithErc20(
+                    bid.address,
+                    bid.value,
+                    (ask, tx.address().map_err(map_eyre_to_pyerr)?).into(),
                     expiration,
+                    permit.deadline,
+                    permit.v(),
+                    permit.r(),
+                    permit.s(),
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn pay_erc20_for_bundle<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .pay_erc20_for_bundle(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                .payErc20ForBundle(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None, permit=None))]
     pub fn permit_and_pay_erc20_for_bundle<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
+        permit: Option<Permit>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .permit_and_pay_erc20_for_bundle(
-                    buy_attestation.parse().map_err(map_parse_to_pyerr)?,
-                )
+        let tx = self.tx.clone();
+        let eas = self.inner.addresses.eas;
+        let barter_utils = self.inner.addresses.barter_utils;
+        let payment_obligation = self.inner.addresses.payment_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let buy_attestation: FixedBytes<32> =
+                buy_attestation.parse().map_err(map_parse_to_pyerr)?;
+            let escrow = get_attestation(&tx, eas, buy_attestation)
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+            let escrow = TokenBundleEscrowObligation::ObligationData::abi_decode(&escrow.data)
+                .map_err(map_sol_decode_to_pyerr)?;
+            let price = erc20_demand(&escrow.demand).map_err(map_sol_decode_to_pyerr)?;
+            let permit = permit_for(&tx, permit, &price, payment_obligation)
+                .await
+                .map_err(map_eyre_to_pyerr)?;
+            ensure_own_permit(&tx, &permit).map_err(map_eyre_to_pyerr)?;
+            let call = ERC20BarterCrossToken::new(barter_utils, tx.provider())
+                .permitAndPayErc20ForBundle(
+                    buy_attestation,
+                    permit.deadline,
+                    permit.v(),
+                    permit.r(),
+                    permit.s(),
+                )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
+                .await
+                .map_err(map_eyre_to_pyerr)
         })
     }
 }
 
+fn approval_spender(addresses: &erc20::Erc20Addresses, purpose: &str) -> PyResult<Address> {
+    match purpose {
+        "payment" => Ok(addresses.payment_obligation),
+        "escrow" => Ok(addresses.escrow_obligation),
+        _ => Err(pyo3::exceptions::PyValueError::new_err("Invalid purpose")),
+    }
+}
+
+/// Permits are signed to stay valid for an hour.
+fn permit_deadline() -> eyre::Result<U256> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    Ok(U256::from(now + 3600))
+}
+
+/// An EIP-2612 permit letting `spender` pull `value` of `token` from `owner`.
+#[derive(Clone, Debug)]
+pub struct Permit {
+    pub token: Address,
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+    pub nonce: U256,
+    pub deadline: U256,
+    pub signature: Signature,
+}
+
+impl Permit {
+    fn v(&self) -> u8 {
+        27 + self.signature.v() as u8
+    }
+
+    fn r(&self) -> FixedBytes<32> {
+        self.signature.r().into()
+    }
+
+    fn s(&self) -> FixedBytes<32> {
+        self.signature.s().into()
+    }
+
+    /// Checks the permit covers what a `permit_and_*` call is about to pull.
+    fn check(&self, token: &alkahest_rs::types::Erc20Data, spender: Address) -> eyre::Result<()> {
+        if self.token != token.address || self.value != token.value {
+            eyre::bail!(
+                "permit is for {} of token {}, but the call uses {} of {}",
+                self.value,
+                self.token,
+                token.value,
+                token.address
+            );
+        }
+        if self.spender != spender {
+            eyre::bail!(
+                "permit is for spender {}, but the call needs {}",
+                self.spender,
+                spender
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Signs an EIP-2612 permit letting `spender` pull `token.value` from the signer.
+/// The token's permit nonce and domain separator are read from the chain unless
+/// given, so with both the signature is made fully offline.
+async fn sign_permit(
+    tx: &TxSender,
+    token: &alkahest_rs::types::Erc20Data,
+    spender: Address,
+    deadline: U256,
+    nonce: Option<U256>,
+    domain_separator: Option<FixedBytes<32>>,
+) -> eyre::Result<Permit> {
+    let permit_typehash = keccak256(
+        "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)",
+    );
+    let owner = tx.address()?;
+    let signer = tx.signer()?;
+    // An unsigned client builds for an account whose key it doesn't hold
+    if signer.address() != owner {
+        eyre::bail!(
+            "can't sign a permit for {}; sign it externally and pass it as permit=",
+            owner
+        );
+    }
+
+    let erc20 = IERC20Permit::new(token.address, tx.provider());
+    let nonce = match nonce {
+        Some(nonce) => nonce,
+        None => erc20.nonces(owner).call().await?,
+    };
+    let domain_separator = match domain_separator {
+        Some(domain_separator) => domain_separator,
+        None => erc20.DOMAIN_SEPARATOR().call().await?,
+    };
+
+    let struct_hash = keccak256(
+        (
+            permit_typehash,
+            owner,
+            spender,
+            token.value,
+            nonce,
+            deadline,
+        )
+            .abi_encode(),
+    );
+    let digest = keccak256(
+        [
+            &[0x19, 0x01][..],
+            domain_separator.as_slice(),
+            struct_hash.as_slice(),
+        ]
+        .concat(),
+    );
+
+    Ok(Permit {
+        token: token.address,
+        owner,
+        spender,
+        value: token.value,
+        nonce,
+        deadline,
+        signature: signer.sign_hash(&digest).await?,
+    })
+}
+
+/// The permit a `permit_and_*` call submits: the one passed in, once it's
+/// checked against the call, or a fresh one signed by the sender.
+async fn permit_for(
+    tx: &TxSender,
+    given: Option<Permit>,
+    token: &alkahest_rs::types::Erc20Data,
+    spender: Address,
+) -> eyre::Result<Permit> {
+    match given {
+        Some(permit) => {
+            permit.check(token, spender)?;
+            Ok(permit)
+        }
+        None => sign_permit(tx, token, spender, permit_deadline()?, None, None).await,
+    }
+}
+
+fn is_own_permit(tx: &TxSender, permit: &Permit) -> eyre::Result<bool> {
+    Ok(permit.owner == tx.address()?)
+}
+
+/// The barter contracts take the permit's owner to be the sender, so only
+/// `permit_and_buy_with_erc20` and `permit_and_pay_with_erc20` can be relayed.
+fn ensure_own_permit(tx: &TxSender, permit: &Permit) -> eyre::Result<()> {
+    if !is_own_permit(tx, permit)? {
+        eyre::bail!(
+            "permit is signed by {}; only permit_and_buy_with_erc20 and permit_and_pay_with_erc20 can be sent by a relayer",
+            permit.owner
+        );
+    }
+    Ok(())
+}
+
+/// Submits `permit` to its token from the sending account.
+async fn relay_permit(
+    tx: &TxSender,
+    permit: &Permit,
+    options: Option<&TxOptions>,
+) -> eyre::Result<TransactionReceipt> {
+    let call = IERC20Permit::new(permit.token, tx.provider())
+        .permit(
+            permit.owner,
+            permit.spender,
+            permit.value,
+            permit.deadline,
+            permit.v(),
+            permit.r(),
+            permit.s(),
+        )
+        .into_transaction_request();
+    tx.send(call, options).await
+}
+
+async fn get_attestation(
+    tx: &TxSender,
+    eas: Address,
+    uid: FixedBytes<32>,
+) -> eyre::Result<IEAS::Attestation> {
+    let attestation = IEAS::new(eas, tx.provider())
+        .getAttestation(uid)
+        .call()
+        .await?;
+    Ok(attestation)
+}
+
+/// The ERC20 payment an escrow asks for, read from its demand.
+fn erc20_demand(demand: &Bytes) -> Result<alkahest_rs::types::Erc20Data, alloy::sol_types::Error> {
+    let payment = ERC20PaymentObligation::ObligationData::abi_decode(demand)?;
+    Ok(alkahest_rs::types::Erc20Data {
+        address: payment.token,
+        value: payment.amount,
+    })
+}
+
 #[pyclass]
 #[derive(Clone)]
 pub struct PyERC20EscrowObligationData {
     #[pyo3(get)]
     pub token: String,
     #[pyo3(get)]
-    pub amount: u64,
+    pub amount: PyU256,
     #[pyo3(get)]
     pub arbiter: String,
     #[pyo3(get)]
@@ -621,7 +1121,7 @@ pub struct PyERC20EscrowObligationData {
 #[pymethods]
 impl PyERC20EscrowObligationData {
     #[new]
-    pub fn new(token: String, amount: u64, arbiter: String, demand: Vec<u8>) -> Self {
+    pub fn new(token: String, amount: PyU256, arbiter: String, demand: Vec<u8>) -> Self {
         Self {
             token,
             amount,
@@ -637,24 +1137,25 @@ impl PyERC20EscrowObligationData {
         )
     }
     #[staticmethod]
-    pub fn decode(obligation_data: Vec<u8>) -> eyre::Result<PyERC20EscrowObligationData> {
+    pub fn decode(obligation_data: Vec<u8>) -> PyResult<PyERC20EscrowObligationData> {
         use alloy::primitives::Bytes;
         let bytes = Bytes::from(obligation_data);
-        let decoded = alkahest_rs::clients::erc20::Erc20Client::decode_escrow_obligation(&bytes)?;
+        let decoded = alkahest_rs::clients::erc20::Erc20Client::decode_escrow_obligation(&bytes)
+            .map_err(map_eyre_to_pyerr)?;
         Ok(decoded.into())
     }
 
     #[staticmethod]
-    pub fn encode(obligation: &PyERC20EscrowObligationData) -> eyre::Result<Vec<u8>> {
+    pub fn encode(obligation: &PyERC20EscrowObligationData) -> PyResult<Vec<u8>> {
         use alkahest_rs::contracts::ERC20EscrowObligation;
         use alloy::{
             primitives::{Address, Bytes, U256},
             sol_types::SolValue,
         };
 
-        let token: Address = obligation.token.parse()?;
-        let amount: U256 = U256::from(obligation.amount);
-        let arbiter: Address = obligation.arbiter.parse()?;
+        let token: Address = parse_address(&obligation.token)?;
+        let amount: U256 = obligation.amount.into();
+        let arbiter: Address = parse_address(&obligation.arbiter)?;
         let demand = Bytes::from(obligation.demand.clone());
 
         let obligation_data = ERC20EscrowObligation::ObligationData {
@@ -667,7 +1168,7 @@ impl PyERC20EscrowObligationData {
         Ok(obligation_data.abi_encode())
     }
 
-    pub fn encode_self(&self) -> eyre::Result<Vec<u8>> {
+    pub fn encode_self(&self) -> PyResult<Vec<u8>> {
         PyERC20EscrowObligationData::encode(self)
     }
 }
@@ -678,7 +1179,7 @@ impl From<alkahest_rs::contracts::ERC20EscrowObligation::ObligationData>
     fn from(data: alkahest_rs::contracts::ERC20EscrowObligation::ObligationData) -> Self {
         Self {
             token: format!("{:?}", data.token),
-            amount: data.amount.try_into().unwrap_or(0), // Handle potential overflow
+            amount: data.amount.into(),
             arbiter: format!("{:?}", data.arbiter),
             demand: data.demand.to_vec(),
         }
@@ -691,7 +1192,7 @@ pub struct PyERC20PaymentObligationData {
     #[pyo3(get)]
     pub token: String,
     #[pyo3(get)]
-    pub amount: u64,
+    pub amount: PyU256,
     #[pyo3(get)]
     pub payee: String,
 }
@@ -699,7 +1200,7 @@ pub struct PyERC20PaymentObligationData {
 #[pymethods]
 impl PyERC20PaymentObligationData {
     #[new]
-    pub fn new(token: String, amount: u64, payee: String) -> Self {
+    pub fn new(token: String, amount: PyU256, payee: String) -> Self {
         Self {
             token,
             amount,
@@ -715,16 +1216,16 @@ impl PyERC20PaymentObligationData {
     }
 
     #[staticmethod]
-    pub fn encode(obligation: &PyERC20PaymentObligationData) -> eyre::Result<Vec<u8>> {
+    pub fn encode(obligation: &PyERC20PaymentObligationData) -> PyResult<Vec<u8>> {
         use alkahest_rs::contracts::ERC20PaymentObligation;
         use alloy::{
             primitives::{Address, U256},
             sol_types::SolValue,
         };
 
-        let token: Address = obligation.token.parse()?;
-        let amount: U256 = U256::from(obligation.amount);
-        let payee: Address = obligation.payee.parse().map_err(map_parse_to_pyerr)?;
+        let token: Address = parse_address(&obligation.token)?;
+        let amount: U256 = obligation.amount.into();
+        let payee: Address = parse_address(&obligation.payee)?;
 
         let obligation_data = ERC20PaymentObligation::ObligationData {
             token,
@@ -736,14 +1237,15 @@ impl PyERC20PaymentObligationData {
     }
 
     #[staticmethod]
-    pub fn decode(obligation_data: Vec<u8>) -> eyre::Result<PyERC20PaymentObligationData> {
+    pub fn decode(obligation_data: Vec<u8>) -> PyResult<PyERC20PaymentObligationData> {
         use alloy::primitives::Bytes;
         let bytes = Bytes::from(obligation_data);
-        let decoded = alkahest_rs::clients::erc20::Erc20Client::decode_payment_obligation(&bytes)?;
+        let decoded = alkahest_rs::clients::erc20::Erc20Client::decode_payment_obligation(&bytes)
+            .map_err(map_eyre_to_pyerr)?;
         Ok(decoded.into())
     }
 
-    pub fn encode_self(&self) -> eyre::Result<Vec<u8>> {
+    pub fn encode_self(&self) -> PyResult<Vec<u8>> {
         PyERC20PaymentObligationData::encode(self)
     }
 }
@@ -754,8 +1256,135 @@ impl From<alkahest_rs::contracts::ERC20PaymentObligation::ObligationData>
     fn from(data: alkahest_rs::contracts::ERC20PaymentObligation::ObligationData) -> Self {
         Self {
             token: format!("{:?}", data.token),
-            amount: data.amount.try_into().unwrap_or(0), // Handle potential overflow
+            amount: data.amount.into(),
             payee: format!("{:?}", data.payee),
         }
     }
 }
+
+/// A signed EIP-2612 permit, as returned by `Erc20Client.sign_permit`.
+/// `to_dict()` gives a JSON-friendly form; a dict with the same keys is
+/// accepted wherever a permit is.
+#[pyclass]
+#[derive(Clone)]
+pub struct PyPermitSignature {
+    #[pyo3(get)]
+    pub token: String,
+    #[pyo3(get)]
+    pub owner: String,
+    #[pyo3(get)]
+    pub spender: String,
+    #[pyo3(get)]
+    pub value: PyU256,
+    #[pyo3(get)]
+    pub nonce: PyU256,
+    #[pyo3(get)]
+    pub deadline: PyU256,
+    #[pyo3(get)]
+    pub signature: String,
+}
+
+#[pymethods]
+impl PyPermitSignature {
+    #[new]
+    pub fn new(
+        token: String,
+        owner: String,
+        spender: String,
+        value: PyU256,
+        nonce: PyU256,
+        deadline: PyU256,
+        signature: String,
+    ) -> Self {
+        Self {
+            token,
+            owner,
+            spender,
+            value,
+            nonce,
+            deadline,
+            signature,
+        }
+    }
+
+    pub fn to_dict<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::types::PyDict>> {
+        let dict = pyo3::types::PyDict::new(py);
+        dict.set_item("token", &self.token)?;
+        dict.set_item("owner", &self.owner)?;
+        dict.set_item("spender", &self.spender)?;
+        dict.set_item("value", self.value)?;
+        dict.set_item("nonce", self.nonce)?;
+        dict.set_item("deadline", self.deadline)?;
+        dict.set_item("signature", &self.signature)?;
+        Ok(dict)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PyPermitSignature(token='{}', owner='{}', spender='{}', value={}, deadline={})",
+            self.token, self.owner, self.spender, self.value, self.deadline
+        )
+    }
+}
+
+impl From<Permit> for PyPermitSignature {
+    fn from(permit: Permit) -> Self {
+        Self {
+            token: format!("{:?}", permit.token),
+            owner: format!("{:?}", permit.owner),
+            spender: format!("{:?}", permit.spender),
+            value: permit.value.into(),
+            nonce: permit.nonce.into(),
+            deadline: permit.deadline.into(),
+            signature: format!("0x{}", alloy::hex::encode(permit.signature.as_bytes())),
+        }
+    }
+}
+
+impl TryFrom<PyPermitSignature> for Permit {
+    type Error = PyErr;
+
+    fn try_from(value: PyPermitSignature) -> PyResult<Self> {
+        let signature = value
+            .signature
+            .parse::<Signature>()
+            .map_err(map_parse_to_pyerr)?;
+        Ok(Self {
+            token: parse_address(&value.token)?,
+            owner: parse_address(&value.owner)?,
+            spender: parse_address(&value.spender)?,
+            value: value.value.into(),
+            nonce: value.nonce.into(),
+            deadline: value.deadline.into(),
+            signature,
+        })
+    }
+}
+
+// Accepts a PermitSignature or its to_dict() form
+impl<'py> FromPyObject<'py> for Permit {
+    fn extract_bound(ob: &pyo3::Bound<'py, pyo3::PyAny>) -> PyResult<Self> {
+        if let Ok(permit) = ob.extract::<PyPermitSignature>() {
+            return permit.try_into();
+        }
+        let dict = ob.downcast::<pyo3::types::PyDict>()?;
+        let item = |key: &str| -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+            dict.get_item(key)?.ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err(format!("permit is missing {:?}", key))
+            })
+        };
+        PyPermitSignature {
+            token: item("token")?.extract()?,
+            owner: item("owner")?.extract()?,
+            spender: item("spender")?.extract()?,
+            value: item("value")?.extract()?,
+            nonce: item("nonce")?.extract()?,
+            deadline: item("deadline")?.extract()?,
+            signature: item("signature")?.extract()?,
+        }
+        .try_into()
+    }
+}
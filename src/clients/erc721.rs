@@ -1,383 +1,405 @@
-use alkahest_rs::clients::erc721;
+use alkahest_rs::{
+    clients::erc721,
+    contracts::{ERC721BarterCrossToken, ERC721EscrowObligation, ERC721PaymentObligation},
+};
 use alloy::primitives::Address;
 use pyo3::{pyclass, pymethods, PyResult};
 
 use crate::{
-    error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr},
-    get_attested_event,
-    types::{
-        ArbiterData, AttestedLog, Erc1155Data, Erc20Data, Erc721Data, LogWithHash, TokenBundleData,
-    },
+    error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr, parse_address},
+    interfaces::IERC721,
+    receipt::PyTransactionReceipt,
+    tx::{TxOptions, TxSender},
+    types::{ArbiterData, Erc1155Data, Erc20Data, Erc721Data, PyU256, TokenBundleData},
 };
 
 #[pyclass]
 #[derive(Clone)]
 pub struct Erc721Client {
     inner: erc721::Erc721Client,
+    tx: TxSender,
 }
 
 impl Erc721Client {
-    pub fn new(inner: erc721::Erc721Client) -> Self {
-        Self { inner }
+    pub fn new(inner: erc721::Erc721Client, tx: TxSender) -> Self {
+        Self { inner, tx }
     }
 }
 
 #[pymethods]
 impl Erc721Client {
+    #[pyo3(signature = (token, purpose, tx_options=None))]
     pub fn approve<'py>(
         &self,
         py: pyo3::Python<'py>,
         token: Erc721Data,
         purpose: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let purpose = match purpose.as_str() {
-                "payment" => alkahest_rs::types::ApprovalPurpose::Payment,
-                "escrow" => alkahest_rs::types::ApprovalPurpose::Escrow,
-                _ => return Err(map_eyre_to_pyerr(eyre::eyre!("Invalid purpose"))),
-            };
-            let receipt = inner
-                .approve(&token.try_into().map_err(map_eyre_to_pyerr)?, purpose)
+        let tx = self.tx.clone();
+        let spender = approval_spender(&self.inner.addresses, &purpose)?;
+        crate::runtime::future_into_py(py, async move {
+            let token: alkahest_rs::types::Erc721Data =
+                token.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = IERC721::new(token.address, tx.provider())
+                .approve(spender, token.id)
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (token_contract, purpose, tx_options=None))]
     pub fn approve_all<'py>(
         &self,
         py: pyo3::Python<'py>,
         token_contract: String,
         purpose: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let token_contract: Address = token_contract.parse().map_err(map_parse_to_pyerr)?;
-            let purpose = match purpose.as_str() {
-                "payment" => alkahest_rs::types::ApprovalPurpose::Payment,
-                "escrow" => alkahest_rs::types::ApprovalPurpose::Escrow,
-                _ => return Err(map_eyre_to_pyerr(eyre::eyre!("Invalid purpose"))),
-            };
-            let receipt = inner
-                .approve_all(token_contract, purpose)
+        let tx = self.tx.clone();
+        let operator = approval_spender(&self.inner.addresses, &purpose)?;
+        crate::runtime::future_into_py(py, async move {
+            let token_contract: Address = parse_address(&token_contract)?;
+            let call = IERC721::new(token_contract, tx.provider())
+                .setApprovalForAll(operator, true)
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (token_contract, purpose, tx_options=None))]
     pub fn revoke_all<'py>(
         &self,
         py: pyo3::Python<'py>,
         token_contract: String,
         purpose: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let token_contract: Address = token_contract.parse().map_err(map_parse_to_pyerr)?;
-            let purpose = match purpose.as_str() {
-                "payment" => alkahest_rs::types::ApprovalPurpose::Payment,
-                "escrow" => alkahest_rs::types::ApprovalPurpose::Escrow,
-                _ => return Err(map_eyre_to_pyerr(eyre::eyre!("Invalid purpose"))),
-            };
-            let receipt = inner
-                .revoke_all(token_contract, purpose)
+        let tx = self.tx.clone();
+        let operator = approval_spender(&self.inner.addresses, &purpose)?;
+        crate::runtime::future_into_py(py, async move {
+            let token_contract: Address = parse_address(&token_contract)?;
+            let call = IERC721::new(token_contract, tx.provider())
+                .setApprovalForAll(operator, false)
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (buy_attestation, fulfillment, tx_options=None))]
     pub fn collect_escrow<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
         fulfillment: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .collect_escrow(
+        let tx = self.tx.clone();
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC721EscrowObligation::new(escrow_obligation, tx.provider())
+                .collectEscrow(
                     buy_attestation.parse().map_err(map_parse_to_pyerr)?,
                     fulfillment.parse().map_err(map_parse_to_pyerr)?,
                 )
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn reclaim_expired<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .reclaim_expired(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC721EscrowObligation::new(escrow_obligation, tx.provider())
+                .reclaimExpired(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (price, item, expiration, tx_options=None))]
     pub fn buy_with_erc721<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: Erc721Data,
         item: ArbiterData,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_with_erc721(
-                    &price.try_into().map_err(map_eyre_to_pyerr)?,
-                    &item.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let price: alkahest_rs::types::Erc721Data =
+                price.try_into().map_err(map_eyre_to_pyerr)?;
+            let item: alkahest_rs::types::ArbiterData =
+                item.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC721EscrowObligation::new(escrow_obligation, tx.provider())
+                .doObligation(
+                    ERC721EscrowObligation::ObligationData {
+                        token: price.address,
+                        tokenId: price.id,
+                        arbiter: item.arbiter,
+                        demand: item.demand,
+                    },
                     expiration,
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (price, payee, tx_options=None))]
     pub fn pay_with_erc721<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: Erc721Data,
         payee: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .pay_with_erc721(
-                    &price.try_into().map_err(map_eyre_to_pyerr)?,
-                    payee.parse().map_err(map_parse_to_pyerr)?,
-                )
+        let tx = self.tx.clone();
+        let payment_obligation = self.inner.addresses.payment_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let price: alkahest_rs::types::Erc721Data =
+                price.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC721PaymentObligation::new(payment_obligation, tx.provider())
+                .doObligation(ERC721PaymentObligation::ObligationData {
+                    token: price.address,
+                    tokenId: price.id,
+                    payee: parse_address(&payee)?,
+                })
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None))]
     pub fn buy_erc_721_for_erc_721<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc721Data,
         ask: Erc721Data,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_erc721_for_erc721(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
-                    expiration,
-                )
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::Erc721Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::Erc721Data = ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC721BarterCrossToken::new(barter_utils, tx.provider())
+                .buyErc721ForErc721(bid.address, bid.id, ask.address, ask.id, expiration)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn pay_erc_721_for_erc_721<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .pay_erc721_for_erc721(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC721BarterCrossToken::new(barter_utils, tx.provider())
+                .payErc721ForErc721(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None))]
     pub fn buy_erc20_with_erc721<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc721Data,
         ask: Erc20Data,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_erc20_with_erc721(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
-                    expiration,
-                )
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::Erc721Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::Erc20Data = ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC721BarterCrossToken::new(barter_utils, tx.provider())
+                .buyErc20WithErc721(bid.address, bid.id, ask.address, ask.value, expiration)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn pay_erc721_for_erc20<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .pay_erc721_for_erc20(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC721BarterCrossToken::new(barter_utils, tx.provider())
+                .payErc721ForErc20(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None))]
     pub fn buy_erc1155_with_erc721<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc721Data,
         ask: Erc1155Data,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_erc1155_with_erc721(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::Erc721Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::Erc1155Data = ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC721BarterCrossToken::new(barter_utils, tx.provider())
+                .buyErc1155WithErc721(
+                    bid.address,
+                    bid.id,
+                    ask.address,
+                    ask.id,
+                    ask.value,
                     expiration,
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn pay_erc721_for_erc1155<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .pay_erc721_for_erc1155(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC721BarterCrossToken::new(barter_utils, tx.provider())
+                .payErc721ForErc1155(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None))]
     pub fn buy_bundle_with_erc721<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: Erc721Data,
         ask: TokenBundleData,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_bundle_with_erc721(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    ask.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::Erc721Data = bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::TokenBundleData =
+                ask.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = ERC721BarterCrossToken::new(barter_utils, tx.provider())
+                .buyBundleWithErc721(
+                    bid.address,
+                    bid.id,
+                    (ask, tx.address().map_err(map_eyre_to_pyerr)?).into(),
                     expiration,
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn pay_erc721_for_bundle<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .pay_erc721_for_bundle(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let call = ERC721BarterCrossToken::new(barter_utils, tx.provider())
+                .payErc721ForBundle(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 }
 
+fn approval_spender(addresses: &erc721::Erc721Addresses, purpose: &str) -> PyResult<Address> {
+    match purpose {
+        "payment" => Ok(addresses.payment_obligation),
+        "escrow" => Ok(addresses.escrow_obligation),
+        _ => Err(map_eyre_to_pyerr(eyre::eyre!("Invalid purpose"))),
+    }
+}
+
 #[pyclass]
 #[derive(Clone)]
 pub struct PyERC721EscrowObligationData {
     #[pyo3(get)]
     pub token: String,
     #[pyo3(get)]
-    pub token_id: String,
+    pub token_id: PyU256,
     #[pyo3(get)]
     pub arbiter: String,
     #[pyo3(get)]
@@ -387,7 +409,7 @@ pub struct PyERC721EscrowObligationData {
 #[pymethods]
 impl PyERC721EscrowObligationData {
     #[new]
-    pub fn new(token: String, token_id: String, arbiter: String, demand: Vec<u8>) -> Self {
+    pub fn new(token: String, token_id: PyU256, arbiter: String, demand: Vec<u8>) -> Self {
         Self {
             token,
             token_id,
@@ -398,7 +420,7 @@ impl PyERC721EscrowObligationData {
 
     pub fn __repr__(&self) -> String {
         format!(
-            "PyERC721EscrowObligationData(token='{}', token_id='{}', arbiter='{}', demand={:?})",
+            "PyERC721EscrowObligationData(token='{}', token_id={}, arbiter='{}', demand={:?})",
             self.token, self.token_id, self.arbiter, self.demand
         )
     }
@@ -420,9 +442,9 @@ impl PyERC721EscrowObligationData {
             sol_types::SolValue,
         };
 
-        let token: Address = obligation.token.parse().map_err(map_parse_to_pyerr)?;
-        let token_id: U256 = obligation.token_id.parse().map_err(map_parse_to_pyerr)?;
-        let arbiter: Address = obligation.arbiter.parse().map_err(map_parse_to_pyerr)?;
+        let token: Address = parse_address(&obligation.token)?;
+        let token_id: U256 = obligation.token_id.into();
+        let arbiter: Address = parse_address(&obligation.arbiter)?;
         let demand = Bytes::from(obligation.demand.clone());
 
         let obligation_data = ERC721EscrowObligation::ObligationData {
@@ -446,7 +468,7 @@ impl From<alkahest_rs::contracts::ERC721EscrowObligation::ObligationData>
     fn from(data: alkahest_rs::contracts::ERC721EscrowObligation::ObligationData) -> Self {
         Self {
             token: format!("{:?}", data.token),
-            token_id: data.tokenId.to_string(),
+            token_id: data.tokenId.into(),
             arbiter: format!("{:?}", data.arbiter),
             demand: data.demand.to_vec(),
         }
@@ -459,7 +481,7 @@ pub struct PyERC721PaymentObligationData {
     #[pyo3(get)]
     pub token: String,
     #[pyo3(get)]
-    pub token_id: String,
+    pub token_id: PyU256,
     #[pyo3(get)]
     pub payee: String,
 }
@@ -467,7 +489,7 @@ pub struct PyERC721PaymentObligationData {
 #[pymethods]
 impl PyERC721PaymentObligationData {
     #[new]
-    pub fn new(token: String, token_id: String, payee: String) -> Self {
+    pub fn new(token: String, token_id: PyU256, payee: String) -> Self {
         Self {
             token,
             token_id,
@@ -477,7 +499,7 @@ impl PyERC721PaymentObligationData {
 
     pub fn __repr__(&self) -> String {
         format!(
-            "PyERC721PaymentObligationData(token='{}', token_id='{}', payee='{}')",
+            "PyERC721PaymentObligationData(token='{}', token_id={}, payee='{}')",
             self.token, self.token_id, self.payee
         )
     }
@@ -499,9 +521,9 @@ impl PyERC721PaymentObligationData {
             sol_types::SolValue,
         };
 
-        let token: Address = obligation.token.parse().map_err(map_parse_to_pyerr)?;
-        let token_id: U256 = obligation.token_id.parse().map_err(map_parse_to_pyerr)?;
-        let payee: Address = obligation.payee.parse().map_err(map_parse_to_pyerr)?;
+        let token: Address = parse_address(&obligation.token)?;
+        let token_id: U256 = obligation.token_id.into();
+        let payee: Address = parse_address(&obligation.payee)?;
 
         let obligation_data = ERC721PaymentObligation::ObligationData {
             token,
@@ -523,7 +545,7 @@ impl From<alkahest_rs::contracts::ERC721PaymentObligation::ObligationData>
     fn from(data: alkahest_rs::contracts::ERC721PaymentObligation::ObligationData) -> Self {
         Self {
             token: format!("{:?}", data.token),
-            token_id: data.tokenId.to_string(),
+            token_id: data.tokenId.into(),
             payee: format!("{:?}", data.payee),
         }
     }
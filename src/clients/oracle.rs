@@ -1,15 +1,28 @@
-use std::marker::PhantomData;
+use std::{future::Future, path::PathBuf, sync::Arc};
 
-use alkahest_rs::{
-    clients::oracle::OracleClient as InnerOracleClient, contracts::StringObligation,
+use crate::runtime::{future_into_py, runtime, PyFunction};
+use alkahest_rs::clients::oracle::OracleClient as InnerOracleClient;
+use alloy::{
+    primitives::{FixedBytes, TxHash},
+    sol_types::SolValue,
 };
-use alloy::primitives::FixedBytes;
-use pyo3::{pyclass, pymethods, PyAny, PyObject, PyResult, Python};
-use pyo3_async_runtimes::tokio::future_into_py;
+use pyo3::{
+    exceptions::{PyStopAsyncIteration, PyValueError},
+    pyclass, pymethods,
+    types::PyAnyMethods,
+    IntoPyObject, PyAny, PyErr, PyObject, PyRef, PyResult, Python,
+};
+use tokio::{sync::mpsc, task::AbortHandle};
 
 use crate::{
-    clients::string_obligation::PyStringObligationData,
-    error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr, map_sol_decode_to_pyerr},
+    arbitration::{
+        Arbitrator, Decision, ErrorPolicy, Escrow, Fulfillment, Interrupted, Listener, Obligation,
+        ObligationKind, Query, Stopped,
+    },
+    error_handling::{
+        map_eyre_to_pyerr, map_parse_to_pyerr, map_sol_decode_to_pyerr, parse_address,
+    },
+    tx::TxSender,
 };
 use alkahest_rs::clients::arbiters::TrustedOracleArbiter;
 
@@ -17,14 +30,173 @@ use alkahest_rs::clients::arbiters::TrustedOracleArbiter;
 #[derive(Clone)]
 pub struct OracleClient {
     inner: InnerOracleClient,
+    arbitrator: Arbitrator,
 }
 
 impl OracleClient {
-    pub fn new(inner: InnerOracleClient) -> Self {
-        Self { inner }
+    pub fn new(inner: InnerOracleClient, tx: TxSender) -> Self {
+        let arbitrator = Arbitrator::new(
+            tx,
+            inner.addresses.eas,
+            inner.addresses.trusted_oracle_arbiter,
+        );
+        Self { inner, arbitrator }
+    }
+}
+
+/// Calls a decision function with a fulfillment's obligation, followed by its
+/// escrow's demand when arbitrating for escrows. With `batched` set, it's
+/// called once with the whole batch: a list of obligations, and a list of
+/// demands, and returns a list of answers. An answer that isn't a `Verdict`
+/// is read as the decision by its truthiness.
+fn run_decision_func(
+    decision_func: &PyFunction,
+    fulfillments: &[Fulfillment],
+    with_demand: bool,
+    batched: bool,
+) -> impl Future<Output = eyre::Result<Vec<PyVerdict>>> + Send + 'static {
+    let count = fulfillments.len();
+    let obligations: Vec<Obligation> = fulfillments
+        .iter()
+        .map(|fulfillment| fulfillment.obligation.clone())
+        .collect();
+    let demands: Vec<Option<PyTrustedOracleArbiterDemandData>> = fulfillments
+        .iter()
+        .map(|fulfillment| {
+            let escrow = fulfillment.escrow.as_ref().filter(|_| with_demand)?;
+            Some(escrow.demand.clone().into())
+        })
+        .collect();
+    let result = decision_func.call(move |py| {
+        if batched {
+            return match with_demand {
+                true => (obligations, demands).into_pyobject(py),
+                false => (obligations,).into_pyobject(py),
+            };
+        }
+        // Unbatched calls get a batch of one
+        let obligation = obligations.into_iter().next();
+        match demands.into_iter().next().flatten() {
+            Some(demand) => (obligation, demand).into_pyobject(py),
+            None => (obligation,).into_pyobject(py),
+        }
+    });
+    async move {
+        let result = result.await?;
+        let verdicts = Python::with_gil(|py| {
+            let answers = match batched {
+                true => result.bind(py).try_iter()?.collect::<PyResult<Vec<_>>>()?,
+                false => vec![result.into_bound(py)],
+            };
+            if answers.len() != count {
+                return Err(PyValueError::new_err(format!(
+                    "decision function returned {} verdicts for {} obligations",
+                    answers.len(),
+                    count
+                )));
+            }
+            answers
+                .iter()
+                .map(read_verdict)
+                .collect::<PyResult<Vec<_>>>()
+        })?;
+        Ok(verdicts)
+    }
+}
+
+fn read_verdict(answer: &pyo3::Bound<'_, PyAny>) -> PyResult<PyVerdict> {
+    match answer.extract::<PyVerdict>() {
+        Ok(verdict) => Ok(verdict),
+        Err(_) => answer.is_truthy().map(PyVerdict::from),
+    }
+}
+
+/// Hands a listener's callback the decision made on a new fulfillment.
+fn notify(
+    callback_func: Option<&PyFunction>,
+    decision: &Decision,
+) -> impl Future<Output = eyre::Result<()>> + Send + 'static {
+    let called = callback_func.map(|callback_func| {
+        let decision = PyDecision::from(decision);
+        callback_func.call(move |py| (decision,).into_pyobject(py))
+    });
+    async move {
+        if let Some(called) = called {
+            called.await?;
+        }
+        Ok(())
     }
 }
 
+/// Converts an arbitration error. When decisions were submitted before it,
+/// they're on the exception as `decisions`.
+fn map_arbitration_error(error: eyre::Report) -> PyErr {
+    let (decisions, error) = Interrupted::split(error);
+    let err = map_eyre_to_pyerr(error);
+    if !decisions.is_empty() {
+        Python::with_gil(|py| {
+            let decisions: Vec<PyDecision> = decisions.iter().map(PyDecision::from).collect();
+            let _ = err.value(py).setattr("decisions", decisions);
+        });
+    }
+    err
+}
+
+fn fulfillment_query(params: PyFulfillmentParams) -> PyResult<Query> {
+    Ok(Query {
+        fulfillment: params.filter.try_into().map_err(|e| {
+            map_eyre_to_pyerr(eyre::eyre!("Failed to convert fulfillment filter: {}", e))
+        })?,
+        kind: params.obligation_abi,
+        escrow: None,
+    })
+}
+
+fn escrow_query(escrow: PyEscrowParams, fulfillment: PyFulfillmentParams) -> PyResult<Query> {
+    // Only escrows arbitrated by TrustedOracleArbiter can be decided on
+    TrustedOracleArbiter::DemandData::abi_decode(&escrow.demand_abi)
+        .map_err(map_sol_decode_to_pyerr)?;
+    Ok(Query {
+        escrow: Some(escrow.filter.try_into().map_err(|e| {
+            map_eyre_to_pyerr(eyre::eyre!("Failed to convert escrow filter: {}", e))
+        })?),
+        ..fulfillment_query(fulfillment)?
+    })
+}
+
+/// The escrows the decisions settled, each once, as attestations alongside
+/// their decoded demands.
+fn escrows(
+    decisions: &[Decision],
+) -> (
+    Vec<PyOracleAttestation>,
+    Vec<PyTrustedOracleArbiterDemandData>,
+) {
+    let mut seen = std::collections::HashSet::new();
+    py_escrows(
+        decisions
+            .iter()
+            .filter_map(|decision| decision.fulfillment.escrow.as_ref())
+            .filter(|escrow| seen.insert(escrow.attestation.uid)),
+    )
+}
+
+fn py_escrows<'a>(
+    escrows: impl Iterator<Item = &'a Escrow>,
+) -> (
+    Vec<PyOracleAttestation>,
+    Vec<PyTrustedOracleArbiterDemandData>,
+) {
+    escrows
+        .map(|escrow| {
+            (
+                PyOracleAttestation::from(&escrow.attestation),
+                PyTrustedOracleArbiterDemandData::from(escrow.demand.clone()),
+            )
+        })
+        .unzip()
+}
+
 #[pymethods]
 impl OracleClient {
     pub fn unsubscribe<'py>(
@@ -33,8 +205,12 @@ impl OracleClient {
         local_id: String,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
         let inner = self.inner.clone();
+        let arbitrator = self.arbitrator.clone();
         future_into_py(py, async move {
             let local_id: FixedBytes<32> = local_id.parse().map_err(map_parse_to_pyerr)?;
+            if arbitrator.stop_listener(local_id) {
+                return Ok(());
+            }
             inner
                 .unsubscribe(local_id)
                 .await
@@ -51,6 +227,26 @@ impl OracleClient {
         format!("{:?}", self.inner.addresses.trusted_oracle_arbiter)
     }
 
+    /// The verdict an arbitration transaction sent, with its reason and
+    /// payload, or None if the transaction isn't an arbitration.
+    pub fn get_verdict<'py>(
+        &self,
+        py: Python<'py>,
+        transaction_hash: String,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let arbitrator = self.arbitrator.clone();
+        future_into_py(py, async move {
+            let transaction_hash: TxHash = transaction_hash.parse().map_err(map_parse_to_pyerr)?;
+            arbitrator
+                .verdict(transaction_hash)
+                .await
+                .map_err(map_eyre_to_pyerr)
+        })
+    }
+
+    /// Decides on every past fulfillment matching `fulfillment_params` and
+    /// submits the decisions. `decision_func` gets each obligation decoded as
+    /// `fulfillment_params.obligation_abi`.
     pub fn arbitrate_past_sync<'py>(
         &self,
         py: Python<'py>,
@@ -58,66 +254,23 @@ impl OracleClient {
         decision_func: PyObject,
         options: Option<PyArbitrateOptions>,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
-        let inner = self.inner.clone();
+        let arbitrator = self.arbitrator.clone();
+        let query = fulfillment_query(fulfillment_params)?;
+        let decision_func = PyFunction::new(py, decision_func);
+        let batched = options
+            .as_ref()
+            .is_some_and(|opts| opts.batch_size.is_some());
         future_into_py(py, async move {
             let opts = options.unwrap_or_default();
+            let decide =
+                |batch: &[Fulfillment]| run_decision_func(&decision_func, batch, false, batched);
 
-            let rust_filter = fulfillment_params
-                .filter
-                .try_into()
-                .map_err(|e| map_eyre_to_pyerr(eyre::eyre!("Failed to convert filter: {}", e)))?;
-
-            let fulfillment = alkahest_rs::clients::oracle::FulfillmentParams {
-                _obligation_data: PhantomData::<StringObligation::ObligationData>,
-                filter: rust_filter,
-            };
-
-            let arbitrate_options = alkahest_rs::clients::oracle::ArbitrateOptions {
-                require_oracle: opts.require_oracle,
-                skip_arbitrated: opts.skip_arbitrated,
-                require_request: opts.require_request,
-                only_new: opts.only_new,
-            };
-
-            let arbitrate_func =
-                |obligation_data: &StringObligation::ObligationData| -> Option<bool> {
-                    Python::with_gil(|py| {
-                        let py_obligation = pyo3::types::PyString::new(py, &obligation_data.item);
-
-                        decision_func
-                            .call1(py, (py_obligation,))
-                            .ok()
-                            .and_then(|result| {
-                                result
-                                    .extract::<bool>(py)
-                                    .or_else(|_| result.is_truthy(py))
-                                    .ok()
-                            })
-                    })
-                };
-
-            let decisions = inner
-                .arbitrate_past_sync(&fulfillment, &arbitrate_func, &arbitrate_options)
+            let decisions = arbitrator
+                .arbitrate_past(&query, &decide, &opts)
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-
-            let py_decisions: Vec<PyDecision> = decisions
-                .into_iter()
-                .map(|decision| {
-                    let attestation = PyOracleAttestation::from(&decision.attestation);
-                    PyDecision::__new__(
-                        attestation,
-                        decision.decision,
-                        format!(
-                            "0x{}",
-                            alloy::hex::encode(decision.receipt.transaction_hash.as_slice())
-                        ),
-                        Some(decision.obligation.item),
-                        None,
-                    )
-                })
-                .collect();
+                .map_err(map_arbitration_error)?;
 
+            let py_decisions: Vec<PyDecision> = decisions.iter().map(PyDecision::from).collect();
             let total_count = py_decisions.len();
             let successful_count = py_decisions.iter().filter(|d| d.decision).count();
 
@@ -129,6 +282,8 @@ impl OracleClient {
         })
     }
 
+    /// Like `arbitrate_past_sync`, for fulfillments of escrows matching
+    /// `escrow_params`. `decision_func` also gets the escrow's demand.
     pub fn arbitrate_past_for_escrow_sync<'py>(
         &self,
         py: Python<'py>,
@@ -137,110 +292,34 @@ impl OracleClient {
         decision_func: PyObject,
         options: Option<PyArbitrateOptions>,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
-        let inner = self.inner.clone();
-
+        let arbitrator = self.arbitrator.clone();
+        let query = escrow_query(escrow_params, fulfillment_params)?;
+        let decision_func = PyFunction::new(py, decision_func);
+        let batched = options
+            .as_ref()
+            .is_some_and(|opts| opts.batch_size.is_some());
         future_into_py(py, async move {
             let opts = options.unwrap_or_default();
+            let decide =
+                |batch: &[Fulfillment]| run_decision_func(&decision_func, batch, true, batched);
 
-            let escrow_filter = escrow_params.filter.try_into().map_err(|e| {
-                map_eyre_to_pyerr(eyre::eyre!("Failed to convert escrow filter: {}", e))
-            })?;
-
-            let rust_filter = fulfillment_params.filter.try_into().map_err(|e| {
-                map_eyre_to_pyerr(eyre::eyre!("Failed to convert fulfillment filter: {}", e))
-            })?;
-
-            use alkahest_rs::clients::arbiters::TrustedOracleArbiter;
-            use alloy::primitives::Bytes;
-            use alloy::sol_types::SolValue;
-
-            let demand_bytes = Bytes::from(escrow_params.demand_abi.clone());
-            let demand_abi = TrustedOracleArbiter::DemandData::abi_decode(&demand_bytes)
-                .map_err(map_sol_decode_to_pyerr)?;
-
-            let escrow = alkahest_rs::clients::oracle::EscrowParams {
-                filter: escrow_filter,
-                _demand_data: PhantomData::<TrustedOracleArbiter::DemandData>,
-            };
-
-            let fulfillment = alkahest_rs::clients::oracle::FulfillmentParams {
-                _obligation_data: PhantomData::<StringObligation::ObligationData>,
-                filter: rust_filter,
-            };
-
-            let arbitrate_options = alkahest_rs::clients::oracle::ArbitrateOptions {
-                require_oracle: opts.require_oracle,
-                skip_arbitrated: opts.skip_arbitrated,
-                require_request: opts.require_request,
-                only_new: opts.only_new,
-            };
-
-            let arbitrate_func = |obligation_data: &StringObligation::ObligationData,
-                                  demand_data: &TrustedOracleArbiter::DemandData|
-             -> Option<bool> {
-                Python::with_gil(|py| {
-                    let py_obligation = pyo3::types::PyString::new(py, &obligation_data.item);
-
-                    let demand_py = PyTrustedOracleArbiterDemandData::from(demand_data.clone());
-
-                    decision_func
-                        .call1(py, (py_obligation, demand_py))
-                        .ok()
-                        .and_then(|result| {
-                            result
-                                .extract::<bool>(py)
-                                .or_else(|_| result.is_truthy(py))
-                                .ok()
-                        })
-                })
-            };
-
-            let (decisions, escrow_result, _) = inner
-                .arbitrate_past_for_escrow_sync(
-                    &escrow,
-                    &fulfillment,
-                    arbitrate_func,
-                    &arbitrate_options,
-                )
+            let decisions = arbitrator
+                .arbitrate_past(&query, &decide, &opts)
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-
-            let py_decisions: Vec<PyDecision> = decisions
-                .into_iter()
-                .map(|decision| {
-                    let attestation = PyOracleAttestation::from(&decision.attestation);
-                    PyDecision::__new__(
-                        attestation,
-                        decision.decision,
-                        format!(
-                            "0x{}",
-                            alloy::hex::encode(decision.receipt.transaction_hash.as_slice())
-                        ),
-                        Some(decision.obligation.item),
-                        None,
-                    )
-                })
-                .collect();
-
-            let py_escrow_attestations: Vec<PyOracleAttestation> = escrow_result
-                .into_iter()
-                .map(|att| PyOracleAttestation::from(&att))
-                .collect();
-
-            let py_demands = vec![format!(
-                "oracle: 0x{:x}, data: {} bytes",
-                demand_abi.oracle,
-                demand_abi.data.len()
-            )];
+                .map_err(map_arbitration_error)?;
 
+            let (escrow_attestations, escrow_demands) = escrows(&decisions);
             Ok(PyEscrowArbitrationResult::__new__(
-                py_decisions,
-                py_escrow_attestations,
-                py_demands,
+                decisions.iter().map(PyDecision::from).collect(),
+                escrow_attestations,
+                escrow_demands,
             ))
         })
     }
 
+    /// Arbitrates past fulfillments unless `options.only_new` is set, then new
+    /// ones as they're attested, for `timeout_seconds` or until cancelled.
+    /// `callback_func` is told about each decision on a new fulfillment.
     pub fn listen_and_arbitrate_no_spawn<'py>(
         &self,
         py: Python<'py>,
@@ -250,98 +329,31 @@ impl OracleClient {
         options: Option<PyArbitrateOptions>,
         timeout_seconds: Option<f64>,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
-        let inner = self.inner.clone();
+        let arbitrator = self.arbitrator.clone();
+        let query = fulfillment_query(fulfillment_params)?;
+        let decision_func = PyFunction::new(py, decision_func);
+        let batched = options
+            .as_ref()
+            .is_some_and(|opts| opts.batch_size.is_some());
+        let callback_func = callback_func.map(|func| PyFunction::new(py, func));
         future_into_py(py, async move {
             let opts = options.unwrap_or_default();
-            let timeout = timeout_seconds.map(|secs| std::time::Duration::from_secs_f64(secs));
-
-            let rust_filter = fulfillment_params
-                .filter
-                .try_into()
-                .map_err(|e| map_eyre_to_pyerr(eyre::eyre!("Failed to convert filter: {}", e)))?;
+            let timeout = timeout_seconds.map(std::time::Duration::from_secs_f64);
+            let decide =
+                |batch: &[Fulfillment]| run_decision_func(&decision_func, batch, false, batched);
+            let callback = |decision: &Decision| notify(callback_func.as_ref(), decision);
 
-            let fulfillment = alkahest_rs::clients::oracle::FulfillmentParams {
-                _obligation_data: PhantomData::<StringObligation::ObligationData>,
-                filter: rust_filter,
-            };
-
-            let arbitrate_options = alkahest_rs::clients::oracle::ArbitrateOptions {
-                require_oracle: opts.require_oracle,
-                skip_arbitrated: opts.skip_arbitrated,
-                require_request: opts.require_request,
-                only_new: opts.only_new,
-            };
-
-            let arbitrate_func =
-                |obligation_data: &StringObligation::ObligationData| -> Option<bool> {
-                    Python::with_gil(|py| {
-                        let py_obligation = pyo3::types::PyString::new(py, &obligation_data.item);
-
-                        decision_func
-                            .call1(py, (py_obligation,))
-                            .ok()
-                            .and_then(|result| {
-                                result
-                                    .extract::<bool>(py)
-                                    .or_else(|_| result.is_truthy(py))
-                                    .ok()
-                            })
-                    })
-                };
-
-            let callback = |decision: &alkahest_rs::clients::oracle::Decision<
-                StringObligation::ObligationData,
-                (),
-            >| {
-                if let Some(ref py_callback) = callback_func {
-                    Python::with_gil(|py| {
-                        let decision_info = format!(
-                            "Decision: {} for obligation: '{}'",
-                            decision.decision, decision.obligation.item
-                        );
-
-                        if let Err(e) = py_callback.call1(py, (decision_info,)) {
-                            panic!("Python callback failed: {}", e);
-                        }
-                    });
-                }
-
-                Box::pin(async {})
-            };
-
-            let listen_result = inner
-                .listen_and_arbitrate_no_spawn(
-                    &fulfillment,
-                    &arbitrate_func,
-                    callback,
-                    &arbitrate_options,
-                    timeout,
-                )
+            let decisions = arbitrator
+                .listen(&query, &decide, callback, &opts, timeout)
                 .await
-                .map_err(map_eyre_to_pyerr)?;
+                .map_err(map_arbitration_error)?;
 
-            let py_decisions: Vec<PyDecision> = listen_result
-                .decisions
-                .into_iter()
-                .map(|decision| {
-                    let attestation = PyOracleAttestation::from(&decision.attestation);
-                    PyDecision::__new__(
-                        attestation,
-                        decision.decision,
-                        format!(
-                            "0x{}",
-                            alloy::hex::encode(decision.receipt.transaction_hash.as_slice())
-                        ),
-                        Some(decision.obligation.item),
-                        None,
-                    )
-                })
-                .collect();
-
-            Ok(py_decisions)
+            Ok(decisions.iter().map(PyDecision::from).collect::<Vec<_>>())
         })
     }
 
+    /// Like `listen_and_arbitrate_no_spawn`, for fulfillments of escrows
+    /// matching `escrow_params`.
     pub fn listen_and_arbitrate_for_escrow_no_spawn<'py>(
         &self,
         py: Python<'py>,
@@ -352,125 +364,156 @@ impl OracleClient {
         options: Option<PyArbitrateOptions>,
         timeout_secs: Option<u64>,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
-        let inner = self.inner.clone();
+        let arbitrator = self.arbitrator.clone();
+        let query = escrow_query(escrow_params, fulfillment_params)?;
+        let decision_func = PyFunction::new(py, decision_func);
+        let batched = options
+            .as_ref()
+            .is_some_and(|opts| opts.batch_size.is_some());
+        let callback_func = callback_func.map(|func| PyFunction::new(py, func));
         future_into_py(py, async move {
             let opts = options.unwrap_or_default();
             let timeout = timeout_secs.map(std::time::Duration::from_secs);
+            let decide =
+                |batch: &[Fulfillment]| run_decision_func(&decision_func, batch, true, batched);
+            let callback = |decision: &Decision| notify(callback_func.as_ref(), decision);
 
-            let escrow_filter = escrow_params.filter.try_into().map_err(|e| {
-                map_eyre_to_pyerr(eyre::eyre!("Failed to convert escrow filter: {}", e))
-            })?;
+            let decisions = arbitrator
+                .listen(&query, &decide, callback, &opts, timeout)
+                .await
+                .map_err(map_arbitration_error)?;
 
-            let rust_filter = fulfillment_params.filter.try_into().map_err(|e| {
-                map_eyre_to_pyerr(eyre::eyre!("Failed to convert fulfillment filter: {}", e))
-            })?;
+            let (escrow_attestations, escrow_demands) = escrows(&decisions);
+            Ok(PyEscrowArbitrationResult::__new__(
+                decisions.iter().map(PyDecision::from).collect(),
+                escrow_attestations,
+                escrow_demands,
+            ))
+        })
+    }
 
-            use alloy::primitives::Bytes;
-            use alloy::sol_types::SolValue;
+    /// Arbitrates past fulfillments unless `options.only_new` is set, then
+    /// returns a handle while new ones are arbitrated in the background.
+    /// `callback_func` is told about each decision on a new fulfillment.
+    #[pyo3(signature = (fulfillment_params, decision_func, callback_func=None, options=None))]
+    pub fn listen_and_arbitrate<'py>(
+        &self,
+        py: Python<'py>,
+        fulfillment_params: PyFulfillmentParams,
+        decision_func: PyObject,
+        callback_func: Option<PyObject>,
+        options: Option<PyArbitrateOptions>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let arbitrator = self.arbitrator.clone();
+        let query = fulfillment_query(fulfillment_params)?;
+        let decision_func = PyFunction::new(py, decision_func);
+        let batched = options
+            .as_ref()
+            .is_some_and(|opts| opts.batch_size.is_some());
+        let callback_func = callback_func.map(|func| PyFunction::new(py, func));
+        future_into_py(py, async move {
+            let decide = move |batch: &[Fulfillment]| {
+                run_decision_func(&decision_func, batch, false, batched)
+            };
+            let callback = move |decision: &Decision| notify(callback_func.as_ref(), decision);
 
-            let demand_bytes = Bytes::from(escrow_params.demand_abi.clone());
-            let _demand_abi = TrustedOracleArbiter::DemandData::abi_decode(&demand_bytes)
-                .map_err(map_sol_decode_to_pyerr)?;
+            let (listener, initial) = arbitrator
+                .spawn(query, decide, callback, options.unwrap_or_default())
+                .await
+                .map_err(map_arbitration_error)?;
 
-            let escrow = alkahest_rs::clients::oracle::EscrowParams {
-                filter: escrow_filter,
-                _demand_data: PhantomData::<TrustedOracleArbiter::DemandData>,
-            };
+            Ok(PySubscriptionResult::spawned(
+                listener,
+                initial.iter().map(PyDecision::from).collect(),
+            ))
+        })
+    }
 
-            let fulfillment = alkahest_rs::clients::oracle::FulfillmentParams {
-                _obligation_data: PhantomData::<StringObligation::ObligationData>,
-                filter: rust_filter,
+    /// Like `listen_and_arbitrate`, for fulfillments of escrows matching
+    /// `escrow_params`.
+    #[pyo3(signature = (escrow_params, fulfillment_params, decision_func, callback_func=None, options=None))]
+    pub fn listen_and_arbitrate_for_escrow<'py>(
+        &self,
+        py: Python<'py>,
+        escrow_params: PyEscrowParams,
+        fulfillment_params: PyFulfillmentParams,
+        decision_func: PyObject,
+        callback_func: Option<PyObject>,
+        options: Option<PyArbitrateOptions>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let arbitrator = self.arbitrator.clone();
+        let query = escrow_query(escrow_params, fulfillment_params)?;
+        let decision_func = PyFunction::new(py, decision_func);
+        let batched = options
+            .as_ref()
+            .is_some_and(|opts| opts.batch_size.is_some());
+        let callback_func = callback_func.map(|func| PyFunction::new(py, func));
+        future_into_py(py, async move {
+            let decide = move |batch: &[Fulfillment]| {
+                run_decision_func(&decision_func, batch, true, batched)
             };
+            let callback = move |decision: &Decision| notify(callback_func.as_ref(), decision);
 
-            let arbitrate_options = alkahest_rs::clients::oracle::ArbitrateOptions {
-                require_oracle: opts.require_oracle,
-                skip_arbitrated: opts.skip_arbitrated,
-                require_request: opts.require_request,
-                only_new: opts.only_new,
-            };
+            let (listener, initial) = arbitrator
+                .spawn(query, decide, callback, options.unwrap_or_default())
+                .await
+                .map_err(map_arbitration_error)?;
 
-            let arbitrate_func = |obligation_data: &StringObligation::ObligationData,
-                                  demand_data: &TrustedOracleArbiter::DemandData|
-             -> Option<bool> {
-                Python::with_gil(|py| {
-                    let py_obligation = pyo3::types::PyString::new(py, &obligation_data.item);
-
-                    let demand_py = PyTrustedOracleArbiterDemandData::from(demand_data.clone());
-
-                    decision_func
-                        .call1(py, (py_obligation, demand_py))
-                        .ok()
-                        .and_then(|result| {
-                            result
-                                .extract::<bool>(py)
-                                .or_else(|_| result.is_truthy(py))
-                                .ok()
-                        })
-                })
-            };
+            Ok(PyEscrowSubscriptionResult::spawned(
+                listener,
+                initial.iter().map(PyDecision::from).collect(),
+            ))
+        })
+    }
 
-            let callback = |decision_info: &alkahest_rs::clients::oracle::Decision<
-                StringObligation::ObligationData,
-                TrustedOracleArbiter::DemandData,
-            >| {
-                if let Some(ref py_callback) = callback_func {
-                    Python::with_gil(|py| {
-                        let decision_info_str = format!(
-                            "Decision: {} for obligation: '{}'",
-                            decision_info.decision, decision_info.obligation.item
-                        );
-
-                        if let Err(e) = py_callback.call1(py, (decision_info_str,)) {
-                            panic!("Python callback failed: {}", e);
-                        }
-                    });
+    /// Arbitrates past fulfillments unless `options.only_new` is set, then new
+    /// ones as they're attested, yielding each decision once its transaction
+    /// is mined: `async for decision in client.oracle.stream_decisions(...)`.
+    /// Arbitration pauses while `buffer` decisions wait to be consumed, and
+    /// stops once the stream is closed or dropped.
+    #[pyo3(signature = (fulfillment_params, decision_func, options=None, buffer=16))]
+    pub fn stream_decisions(
+        &self,
+        py: Python<'_>,
+        fulfillment_params: PyFulfillmentParams,
+        decision_func: PyObject,
+        options: Option<PyArbitrateOptions>,
+        buffer: usize,
+    ) -> PyResult<PyDecisionStream> {
+        if buffer == 0 {
+            return Err(PyValueError::new_err("buffer must be at least 1"));
+        }
+        let arbitrator = self.arbitrator.clone();
+        let query = fulfillment_query(fulfillment_params)?;
+        let decision_func = PyFunction::new(py, decision_func);
+        let batched = options
+            .as_ref()
+            .is_some_and(|opts| opts.batch_size.is_some());
+        let (sender, receiver) = mpsc::channel(buffer);
+        let task = runtime().spawn(async move {
+            let opts = options.unwrap_or_default();
+            let decide =
+                |batch: &[Fulfillment]| run_decision_func(&decision_func, batch, false, batched);
+            let yield_decision = |decision: &Decision| {
+                let sender = sender.clone();
+                let decision = PyDecision::from(decision);
+                async move {
+                    sender
+                        .send(Ok(decision))
+                        .await
+                        // Nothing consumes the stream any more
+                        .map_err(|_| eyre::Report::new(Stopped))
                 }
-
-                Box::pin(async {})
             };
 
-            let result = inner
-                .listen_and_arbitrate_for_escrow_no_spawn(
-                    &escrow,
-                    &fulfillment,
-                    &arbitrate_func,
-                    callback,
-                    &arbitrate_options,
-                    timeout,
-                )
-                .await
-                .map_err(map_eyre_to_pyerr)?;
-
-            let py_decisions: Vec<PyDecision> = result
-                .decisions
-                .into_iter()
-                .map(|decision| {
-                    let py_attestation = PyOracleAttestation::from(&decision.attestation);
-                    PyDecision::__new__(
-                        py_attestation,
-                        decision.decision,
-                        format!("{:?}", decision.receipt.transaction_hash),
-                        Some(decision.obligation.item.clone()),
-                        decision
-                            .demand
-                            .map(|d| PyTrustedOracleArbiterDemandData::from(d).oracle),
-                    )
-                })
-                .collect();
-
-            let py_escrow_attestations: Vec<PyOracleAttestation> = result
-                .escrow_attestations
-                .into_iter()
-                .map(|att| PyOracleAttestation::from(&att))
-                .collect();
-
-            let py_escrow_demands = vec![];
-
-            Ok(PyEscrowArbitrationResult::__new__(
-                py_decisions,
-                py_escrow_attestations,
-                py_escrow_demands,
-            ))
+            let Err(error) = arbitrator
+                .stream(&query, &decide, yield_decision, &opts)
+                .await;
+            let _ = sender.send(Err(map_eyre_to_pyerr(error))).await;
+        });
+        Ok(PyDecisionStream {
+            receiver: Arc::new(tokio::sync::Mutex::new(receiver)),
+            task: task.abort_handle(),
         })
     }
 }
@@ -511,8 +554,8 @@ impl TryFrom<PyOracleAddresses> for alkahest_rs::clients::oracle::OracleAddresse
 
     fn try_from(value: PyOracleAddresses) -> eyre::Result<Self> {
         Ok(Self {
-            eas: value.eas.parse()?,
-            trusted_oracle_arbiter: value.trusted_oracle_arbiter.parse()?,
+            eas: parse_address(&value.eas)?,
+            trusted_oracle_arbiter: parse_address(&value.trusted_oracle_arbiter)?,
         })
     }
 }
@@ -583,30 +626,75 @@ pub struct PyArbitrateOptions {
     pub require_request: bool,
     #[pyo3(get, set)]
     pub only_new: bool,
+    /// "skip", "retry" or "abort", for when a decision function or callback raises
+    #[pyo3(get, set)]
+    pub on_error: ErrorPolicy,
+    #[pyo3(get, set)]
+    pub max_retries: u32,
+    /// A JSON file recording how far arbitration got, to resume from. Queries
+    /// sharing one get an entry each; SQLite isn't supported
+    #[pyo3(get, set)]
+    pub checkpoint: Option<PathBuf>,
+    /// How many decision function calls may run at once, 16 if None
+    #[pyo3(get, set)]
+    pub max_concurrency: Option<usize>,
+    /// Pass the decision function lists of up to this many obligations
+    #[pyo3(get, set)]
+    pub batch_size: Option<usize>,
+    /// How many arbitration transactions may be in flight at once
+    #[pyo3(get, set)]
+    pub pipeline_depth: usize,
 }
 
 #[pymethods]
 impl PyArbitrateOptions {
     #[new]
-    #[pyo3(signature = (require_oracle=false, skip_arbitrated=false, require_request=false, only_new=false))]
+    #[pyo3(signature = (require_oracle=false, skip_arbitrated=false, require_request=false, only_new=false, on_error=ErrorPolicy::Skip, max_retries=3, checkpoint=None, max_concurrency=None, batch_size=None, pipeline_depth=1))]
+    #[allow(clippy::too_many_arguments)]
     pub fn __new__(
         require_oracle: bool,
         skip_arbitrated: bool,
         require_request: bool,
         only_new: bool,
-    ) -> Self {
-        Self {
+        on_error: ErrorPolicy,
+        max_retries: u32,
+        checkpoint: Option<PathBuf>,
+        max_concurrency: Option<usize>,
+        batch_size: Option<usize>,
+        pipeline_depth: usize,
+    ) -> PyResult<Self> {
+        if max_concurrency == Some(0) || batch_size == Some(0) || pipeline_depth == 0 {
+            return Err(PyValueError::new_err(
+                "max_concurrency, batch_size and pipeline_depth must be at least 1",
+            ));
+        }
+        Ok(Self {
             require_oracle,
             skip_arbitrated,
             require_request,
             only_new,
-        }
+            on_error,
+            max_retries,
+            checkpoint,
+            max_concurrency,
+            batch_size,
+            pipeline_depth,
+        })
     }
 
     pub fn __str__(&self) -> String {
         format!(
-            "PyArbitrateOptions(require_oracle={}, skip_arbitrated={}, require_request={}, only_new={})",
-            self.require_oracle, self.skip_arbitrated, self.require_request, self.only_new
+            "PyArbitrateOptions(require_oracle={}, skip_arbitrated={}, require_request={}, only_new={}, on_error={:?}, max_retries={}, checkpoint={:?}, max_concurrency={:?}, batch_size={:?}, pipeline_depth={})",
+            self.require_oracle,
+            self.skip_arbitrated,
+            self.require_request,
+            self.only_new,
+            self.on_error,
+            self.max_retries,
+            self.checkpoint,
+            self.max_concurrency,
+            self.batch_size,
+            self.pipeline_depth
         )
     }
 
@@ -622,6 +710,12 @@ impl Default for PyArbitrateOptions {
             skip_arbitrated: false,
             require_request: false,
             only_new: false,
+            on_error: ErrorPolicy::Skip,
+            max_retries: 3,
+            checkpoint: None,
+            max_concurrency: None,
+            batch_size: None,
+            pipeline_depth: 1,
         }
     }
 }
@@ -629,8 +723,9 @@ impl Default for PyArbitrateOptions {
 #[pyclass]
 #[derive(Clone)]
 pub struct PyFulfillmentParams {
+    /// The obligation data class fulfillments are decoded as, or `bytes`
     #[pyo3(get, set)]
-    pub obligation_abi: PyStringObligationData,
+    pub obligation_abi: ObligationKind,
     #[pyo3(get, set)]
     pub filter: PyAttestationFilter,
 }
@@ -638,7 +733,7 @@ pub struct PyFulfillmentParams {
 #[pymethods]
 impl PyFulfillmentParams {
     #[new]
-    pub fn __new__(obligation_abi: PyStringObligationData, filter: PyAttestationFilter) -> Self {
+    pub fn __new__(obligation_abi: ObligationKind, filter: PyAttestationFilter) -> Self {
         Self {
             obligation_abi,
             filter,
@@ -776,6 +871,56 @@ impl From<alkahest_rs::contracts::IEAS::Attestation> for PyOracleAttestation {
     }
 }
 
+/// What a decision function can return instead of a bool: the decision, why
+/// it was made and any data backing it. The reason and payload are sent with
+/// the arbitration transaction, after the arguments `TrustedOracleArbiter`
+/// reads, so buyers and sellers can read them back with `get_verdict`.
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct PyVerdict {
+    #[pyo3(get)]
+    pub decision: bool,
+    #[pyo3(get)]
+    pub reason: Option<String>,
+    #[pyo3(get)]
+    pub payload: Vec<u8>,
+}
+
+#[pymethods]
+impl PyVerdict {
+    #[new]
+    #[pyo3(signature = (decision, reason=None, payload=vec![]))]
+    pub fn __new__(decision: bool, reason: Option<String>, payload: Vec<u8>) -> Self {
+        Self {
+            decision,
+            reason,
+            payload,
+        }
+    }
+
+    pub fn __str__(&self) -> String {
+        format!(
+            "PyVerdict(decision={}, reason={:?}, payload={} bytes)",
+            self.decision,
+            self.reason,
+            self.payload.len()
+        )
+    }
+
+    pub fn __repr__(&self) -> String {
+        self.__str__()
+    }
+}
+
+impl From<bool> for PyVerdict {
+    fn from(decision: bool) -> Self {
+        Self {
+            decision,
+            ..Default::default()
+        }
+    }
+}
+
 #[pyclass]
 #[derive(Clone)]
 pub struct PyDecision {
@@ -786,21 +931,34 @@ pub struct PyDecision {
     #[pyo3(get)]
     pub transaction_hash: String,
     #[pyo3(get)]
-    pub obligation_data: Option<String>,
+    pub obligation_data: Option<Obligation>,
+    /// The demand of the escrow the fulfillment settled, for escrow arbitration
+    #[pyo3(get)]
+    pub demand_data: Option<PyTrustedOracleArbiterDemandData>,
+    /// The escrow the fulfillment settled, for escrow arbitration
+    #[pyo3(get)]
+    pub escrow_attestation: Option<PyOracleAttestation>,
+    /// Why the decision function decided as it did, if it said
     #[pyo3(get)]
-    pub demand_data: Option<String>,
+    pub reason: Option<String>,
+    #[pyo3(get)]
+    pub payload: Vec<u8>,
 }
 
 #[pymethods]
 impl PyDecision {
     #[new]
-    #[pyo3(signature = (attestation, decision, transaction_hash, obligation_data=None, demand_data=None))]
+    #[pyo3(signature = (attestation, decision, transaction_hash, obligation_data=None, demand_data=None, escrow_attestation=None, reason=None, payload=vec![]))]
+    #[allow(clippy::too_many_arguments)]
     pub fn __new__(
         attestation: PyOracleAttestation,
         decision: bool,
         transaction_hash: String,
-        obligation_data: Option<String>,
-        demand_data: Option<String>,
+        obligation_data: Option<Obligation>,
+        demand_data: Option<PyTrustedOracleArbiterDemandData>,
+        escrow_attestation: Option<PyOracleAttestation>,
+        reason: Option<String>,
+        payload: Vec<u8>,
     ) -> Self {
         Self {
             attestation,
@@ -808,6 +966,9 @@ impl PyDecision {
             transaction_hash,
             obligation_data,
             demand_data,
+            escrow_attestation,
+            reason,
+            payload,
         }
     }
 
@@ -823,6 +984,28 @@ impl PyDecision {
     }
 }
 
+impl From<&Decision> for PyDecision {
+    fn from(decision: &Decision) -> Self {
+        let fulfillment = &decision.fulfillment;
+        Self {
+            attestation: PyOracleAttestation::from(&fulfillment.attestation),
+            decision: decision.verdict.decision,
+            transaction_hash: decision.transaction_hash.to_string(),
+            obligation_data: Some(fulfillment.obligation.clone()),
+            demand_data: fulfillment
+                .escrow
+                .as_ref()
+                .map(|escrow| escrow.demand.clone().into()),
+            escrow_attestation: fulfillment
+                .escrow
+                .as_ref()
+                .map(|escrow| PyOracleAttestation::from(&escrow.attestation)),
+            reason: decision.verdict.reason.clone(),
+            payload: decision.verdict.payload.clone(),
+        }
+    }
+}
+
 // ===== RESULT TYPES =====
 
 #[pyclass]
@@ -865,6 +1048,45 @@ impl PyArbitrationResult {
     }
 }
 
+/// Decisions from `stream_decisions`, as an async iterator. Ends with the
+/// exception that stopped arbitrating, if any.
+#[pyclass]
+pub struct PyDecisionStream {
+    receiver: Arc<tokio::sync::Mutex<mpsc::Receiver<PyResult<PyDecision>>>>,
+    task: AbortHandle,
+}
+
+#[pymethods]
+impl PyDecisionStream {
+    pub fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let receiver = self.receiver.clone();
+        future_into_py(py, async move {
+            match receiver.lock().await.recv().await {
+                Some(decision) => decision,
+                None => Err(PyStopAsyncIteration::new_err(())),
+            }
+        })
+    }
+
+    /// Stops arbitrating. Decisions already made can still be consumed.
+    pub fn aclose<'py>(&self, py: Python<'py>) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        self.task.abort();
+        future_into_py(py, async move { Ok(()) })
+    }
+}
+
+impl Drop for PyDecisionStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// A listener running in the background, returned by `listen_and_arbitrate`.
+/// Its counts include the initial decisions.
 #[pyclass]
 #[derive(Clone)]
 pub struct PySubscriptionResult {
@@ -872,6 +1094,17 @@ pub struct PySubscriptionResult {
     pub subscription_id: String,
     #[pyo3(get)]
     pub initial_decisions: Vec<PyDecision>,
+    listener: Option<Listener>,
+}
+
+impl PySubscriptionResult {
+    pub fn spawned(listener: Listener, initial_decisions: Vec<PyDecision>) -> Self {
+        Self {
+            subscription_id: format!("{:?}", listener.id),
+            initial_decisions,
+            listener: Some(listener),
+        }
+    }
 }
 
 #[pymethods]
@@ -881,14 +1114,71 @@ impl PySubscriptionResult {
         Self {
             subscription_id,
             initial_decisions,
+            listener: None,
         }
     }
 
+    /// Stops the listener. Decisions already being submitted may still land.
+    pub fn stop(&self) {
+        if let Some(listener) = &self.listener {
+            listener.stop();
+        }
+    }
+
+    #[getter]
+    pub fn is_running(&self) -> bool {
+        self.listener.as_ref().is_some_and(Listener::is_running)
+    }
+
+    /// Waits for the listener to end, raising the exception that ended it
+    /// unless it was stopped.
+    pub fn wait<'py>(&self, py: Python<'py>) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let listener = self.listener.clone();
+        future_into_py(py, async move {
+            if let Some(listener) = listener {
+                listener.wait().await?;
+            }
+            Ok(())
+        })
+    }
+
+    #[getter]
+    pub fn total_count(&self) -> usize {
+        listener_count(
+            &self.listener,
+            &self.initial_decisions,
+            Listener::total_count,
+            |_| true,
+        )
+    }
+
+    #[getter]
+    pub fn successful_count(&self) -> usize {
+        listener_count(
+            &self.listener,
+            &self.initial_decisions,
+            Listener::successful_count,
+            |d| d.decision,
+        )
+    }
+
+    #[getter]
+    pub fn error_count(&self) -> usize {
+        self.listener.as_ref().map_or(0, Listener::error_count)
+    }
+
+    /// The last error the listener ran into, if any.
+    #[getter]
+    pub fn last_error(&self) -> Option<String> {
+        self.listener.as_ref().and_then(Listener::last_error)
+    }
+
     pub fn __str__(&self) -> String {
         format!(
-            "PySubscriptionResult(id={}, initial_decisions={})",
+            "PySubscriptionResult(id={}, initial_decisions={}, running={})",
             self.subscription_id,
-            self.initial_decisions.len()
+            self.initial_decisions.len(),
+            self.is_running()
         )
     }
 
@@ -897,13 +1187,27 @@ impl PySubscriptionResult {
     }
 }
 
+/// A listener's count, or for a result that isn't backed by one, the count
+/// of its initial decisions.
+fn listener_count(
+    listener: &Option<Listener>,
+    initial_decisions: &[PyDecision],
+    count: fn(&Listener) -> usize,
+    counted: fn(&PyDecision) -> bool,
+) -> usize {
+    match listener {
+        Some(listener) => count(listener),
+        None => initial_decisions.iter().filter(|d| counted(d)).count(),
+    }
+}
+
 #[pyclass]
 #[derive(Clone)]
 pub struct PyEscrowResult {
     #[pyo3(get)]
     pub escrow_attestations: Vec<PyOracleAttestation>,
     #[pyo3(get)]
-    pub escrow_demands: Vec<String>,
+    pub escrow_demands: Vec<PyTrustedOracleArbiterDemandData>,
 }
 
 #[pymethods]
@@ -911,7 +1215,7 @@ impl PyEscrowResult {
     #[new]
     pub fn __new__(
         escrow_attestations: Vec<PyOracleAttestation>,
-        escrow_demands: Vec<String>,
+        escrow_demands: Vec<PyTrustedOracleArbiterDemandData>,
     ) -> Self {
         Self {
             escrow_attestations,
@@ -937,10 +1241,12 @@ impl PyEscrowResult {
 pub struct PyEscrowArbitrationResult {
     #[pyo3(get)]
     pub decisions: Vec<PyDecision>,
+    /// The escrows the decisions settled, each once
     #[pyo3(get)]
     pub escrow_attestations: Vec<PyOracleAttestation>,
+    /// The demand of each escrow in `escrow_attestations`, in the same order
     #[pyo3(get)]
-    pub escrow_demands: Vec<String>, // JSON or hex representation
+    pub escrow_demands: Vec<PyTrustedOracleArbiterDemandData>,
 }
 
 #[pymethods]
@@ -949,7 +1255,7 @@ impl PyEscrowArbitrationResult {
     pub fn __new__(
         decisions: Vec<PyDecision>,
         escrow_attestations: Vec<PyOracleAttestation>,
-        escrow_demands: Vec<String>,
+        escrow_demands: Vec<PyTrustedOracleArbiterDemandData>,
     ) -> Self {
         Self {
             decisions,
@@ -972,17 +1278,37 @@ impl PyEscrowArbitrationResult {
     }
 }
 
+/// Like `PySubscriptionResult`, returned by
+/// `listen_and_arbitrate_for_escrow`. One listener follows both escrows and
+/// fulfillments, so both subscription ids are its id.
 #[pyclass]
 #[derive(Clone)]
 pub struct PyEscrowSubscriptionResult {
     #[pyo3(get)]
     pub initial_decisions: Vec<PyDecision>,
-    #[pyo3(get)]
-    pub escrow_attestations: Vec<PyOracleAttestation>,
+    // Only for a result that isn't backed by a listener; a listener's come
+    // from the listener, see the getters
+    escrow_attestations: Vec<PyOracleAttestation>,
+    escrow_demands: Vec<PyTrustedOracleArbiterDemandData>,
     #[pyo3(get)]
     pub escrow_subscription_id: String,
     #[pyo3(get)]
     pub fulfillment_subscription_id: String,
+    listener: Option<Listener>,
+}
+
+impl PyEscrowSubscriptionResult {
+    pub fn spawned(listener: Listener, initial_decisions: Vec<PyDecision>) -> Self {
+        let subscription_id = format!("{:?}", listener.id);
+        Self {
+            initial_decisions,
+            escrow_attestations: Vec::new(),
+            escrow_demands: Vec::new(),
+            escrow_subscription_id: subscription_id.clone(),
+            fulfillment_subscription_id: subscription_id,
+            listener: Some(listener),
+        }
+    }
 }
 
 #[pymethods]
@@ -991,24 +1317,107 @@ impl PyEscrowSubscriptionResult {
     pub fn __new__(
         initial_decisions: Vec<PyDecision>,
         escrow_attestations: Vec<PyOracleAttestation>,
+        escrow_demands: Vec<PyTrustedOracleArbiterDemandData>,
         escrow_subscription_id: String,
         fulfillment_subscription_id: String,
     ) -> Self {
         Self {
             initial_decisions,
             escrow_attestations,
+            escrow_demands,
             escrow_subscription_id,
             fulfillment_subscription_id,
+            listener: None,
+        }
+    }
+
+    #[getter]
+    pub fn subscription_id(&self) -> String {
+        self.fulfillment_subscription_id.clone()
+    }
+
+    /// Each escrow the listener's decisions have settled so far, including
+    /// the initial ones, kept up to date like the counts.
+    #[getter]
+    pub fn escrow_attestations(&self) -> Vec<PyOracleAttestation> {
+        match &self.listener {
+            Some(listener) => py_escrows(listener.escrows().iter()).0,
+            None => self.escrow_attestations.clone(),
+        }
+    }
+
+    /// The demand of each escrow in `escrow_attestations`, in the same order.
+    #[getter]
+    pub fn escrow_demands(&self) -> Vec<PyTrustedOracleArbiterDemandData> {
+        match &self.listener {
+            Some(listener) => py_escrows(listener.escrows().iter()).1,
+            None => self.escrow_demands.clone(),
+        }
+    }
+
+    /// Stops the listener. Decisions already being submitted may still land.
+    pub fn stop(&self) {
+        if let Some(listener) = &self.listener {
+            listener.stop();
         }
     }
 
+    #[getter]
+    pub fn is_running(&self) -> bool {
+        self.listener.as_ref().is_some_and(Listener::is_running)
+    }
+
+    /// Waits for the listener to end, raising the exception that ended it
+    /// unless it was stopped.
+    pub fn wait<'py>(&self, py: Python<'py>) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let listener = self.listener.clone();
+        future_into_py(py, async move {
+            if let Some(listener) = listener {
+                listener.wait().await?;
+            }
+            Ok(())
+        })
+    }
+
+    #[getter]
+    pub fn total_count(&self) -> usize {
+        listener_count(
+            &self.listener,
+            &self.initial_decisions,
+            Listener::total_count,
+            |_| true,
+        )
+    }
+
+    #[getter]
+    pub fn successful_count(&self) -> usize {
+        listener_count(
+            &self.listener,
+            &self.initial_decisions,
+            Listener::successful_count,
+            |d| d.decision,
+        )
+    }
+
+    #[getter]
+    pub fn error_count(&self) -> usize {
+        self.listener.as_ref().map_or(0, Listener::error_count)
+    }
+
+    /// The last error the listener ran into, if any.
+    #[getter]
+    pub fn last_error(&self) -> Option<String> {
+        self.listener.as_ref().and_then(Listener::last_error)
+    }
+
     pub fn __str__(&self) -> String {
         format!(
-            "PyEscrowSubscriptionResult(decisions={}, escrows={}, escrow_id={}, fulfillment_id={})",
+            "PyEscrowSubscriptionResult(decisions={}, escrows={}, escrow_id={}, fulfillment_id={}, running={})",
             self.initial_decisions.len(),
-            self.escrow_attestations.len(),
+            self.escrow_attestations().len(),
             self.escrow_subscription_id,
-            self.fulfillment_subscription_id
+            self.fulfillment_subscription_id,
+            self.is_running()
         )
     }
 
@@ -1025,7 +1434,7 @@ impl TryFrom<PyAttestationFilter> for alkahest_rs::clients::oracle::AttestationF
     fn try_from(py_filter: PyAttestationFilter) -> eyre::Result<Self> {
         use alloy::{
             eips::BlockNumberOrTag,
-            primitives::{Address, FixedBytes},
+            primitives::FixedBytes,
             rpc::types::{FilterBlockOption, ValueOrArray},
         };
 
@@ -1039,13 +1448,13 @@ impl TryFrom<PyAttestationFilter> for alkahest_rs::clients::oracle::AttestationF
         };
 
         let attester = if let Some(addr_str) = py_filter.attester {
-            Some(ValueOrArray::Value(addr_str.parse::<Address>()?))
+            Some(ValueOrArray::Value(parse_address(&addr_str)?))
         } else {
             None
         };
 
         let recipient = if let Some(addr_str) = py_filter.recipient {
-            Some(ValueOrArray::Value(addr_str.parse::<Address>()?))
+            Some(ValueOrArray::Value(parse_address(&addr_str)?))
         } else {
             None
         };
@@ -1121,22 +1530,23 @@ impl PyTrustedOracleArbiterDemandData {
     }
 
     #[staticmethod]
-    pub fn decode(demand_bytes: Vec<u8>) -> eyre::Result<PyTrustedOracleArbiterDemandData> {
+    pub fn decode(demand_bytes: Vec<u8>) -> PyResult<PyTrustedOracleArbiterDemandData> {
         use alkahest_rs::clients::arbiters::TrustedOracleArbiter;
         use alloy::primitives::Bytes;
         use alloy::sol_types::SolValue;
 
         let bytes = Bytes::from(demand_bytes);
-        let decoded = TrustedOracleArbiter::DemandData::abi_decode(&bytes)?;
+        let decoded = TrustedOracleArbiter::DemandData::abi_decode(&bytes)
+            .map_err(map_sol_decode_to_pyerr)?;
         Ok(decoded.into())
     }
 
     #[staticmethod]
-    pub fn encode(demand_data: &PyTrustedOracleArbiterDemandData) -> eyre::Result<Vec<u8>> {
+    pub fn encode(demand_data: &PyTrustedOracleArbiterDemandData) -> PyResult<Vec<u8>> {
         use alkahest_rs::clients::arbiters::{ArbitersClient, TrustedOracleArbiter};
         use alloy::primitives::{Address, Bytes};
 
-        let oracle: Address = demand_data.oracle.parse()?;
+        let oracle: Address = parse_address(&demand_data.oracle)?;
         let data = Bytes::from(demand_data.data.clone());
 
         let rust_demand_data = TrustedOracleArbiter::DemandData { oracle, data };
@@ -1144,7 +1554,7 @@ impl PyTrustedOracleArbiterDemandData {
         Ok(encoded.to_vec())
     }
 
-    pub fn encode_self(&self) -> eyre::Result<Vec<u8>> {
+    pub fn encode_self(&self) -> PyResult<Vec<u8>> {
         PyTrustedOracleArbiterDemandData::encode(self)
     }
 }
@@ -1168,7 +1578,7 @@ impl TryFrom<PyTrustedOracleArbiterDemandData>
     fn try_from(py_data: PyTrustedOracleArbiterDemandData) -> eyre::Result<Self> {
         use alloy::primitives::{Address, Bytes};
 
-        let oracle: Address = py_data.oracle.parse()?;
+        let oracle: Address = parse_address(&py_data.oracle)?;
         let data = Bytes::from(py_data.data);
 
         Ok(Self { oracle, data })
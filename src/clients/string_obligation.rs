@@ -1,4 +1,4 @@
-use alkahest_rs::clients::string_obligation;
+use alkahest_rs::{clients::string_obligation, contracts::StringObligation};
 use alloy::primitives::FixedBytes;
 use pyo3::prelude::PyAnyMethods;
 use pyo3::{pyclass, pymethods, types::PyAny, Bound, PyResult};
@@ -6,6 +6,7 @@ use pyo3::{pyclass, pymethods, types::PyAny, Bound, PyResult};
 use crate::{
     contract::PyDecodedAttestation,
     error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr, map_serde_to_pyerr},
+    tx::{TxOptions, TxSender},
 };
 
 // Helper function to convert Python object to JSON string
@@ -20,11 +21,12 @@ fn python_to_json_string(py_obj: &Bound<'_, PyAny>) -> eyre::Result<String> {
 #[derive(Clone)]
 pub struct StringObligationClient {
     inner: string_obligation::StringObligationClient,
+    tx: TxSender,
 }
 
 impl StringObligationClient {
-    pub fn new(inner: string_obligation::StringObligationClient) -> Self {
-        Self { inner }
+    pub fn new(inner: string_obligation::StringObligationClient, tx: TxSender) -> Self {
+        Self { inner, tx }
     }
 }
 
@@ -36,7 +38,7 @@ impl StringObligationClient {
         uid: String,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let inner: string_obligation::StringObligationClient = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        crate::runtime::future_into_py(py, async move {
             let uid: FixedBytes<32> = uid.parse().map_err(map_parse_to_pyerr)?;
             let obligation = inner.get_obligation(uid).await.map_err(map_eyre_to_pyerr)?;
             Ok(PyDecodedAttestation::<PyStringObligationData>::from(
@@ -45,67 +47,73 @@ impl StringObligationClient {
         })
     }
 
+    #[pyo3(signature = (item, ref_uid, tx_options=None))]
     pub fn do_obligation<'py>(
         &self,
         py: pyo3::Python<'py>,
         item: String,
         ref_uid: Option<String>,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let ref_uid = if let Some(ref_uid_str) = ref_uid {
+        let tx = self.tx.clone();
+        let obligation = self.inner.addresses.obligation;
+        crate::runtime::future_into_py(py, async move {
+            let ref_uid: Option<FixedBytes<32>> = if let Some(ref_uid_str) = ref_uid {
                 Some(ref_uid_str.parse().map_err(map_parse_to_pyerr)?)
             } else {
                 None
             };
 
-            let receipt = inner
-                .do_obligation(item, ref_uid)
+            let call = StringObligation::new(obligation, tx.provider())
+                .doObligation(
+                    StringObligation::ObligationData { item },
+                    ref_uid.unwrap_or_default(),
+                )
+                .into_transaction_request();
+            // The attestation UID rather than the transaction hash
+            let attested = tx
+                .send_attested(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-
-            // Extract the attestation UID from the receipt instead of returning transaction hash
-            use alkahest_rs::DefaultAlkahestClient;
-            let attested_event =
-                DefaultAlkahestClient::get_attested_event(receipt).map_err(map_eyre_to_pyerr)?;
-            Ok(format!(
-                "0x{}",
-                alloy::hex::encode(attested_event.uid.as_slice())
-            ))
+            Ok(attested.log.uid)
         })
     }
 
+    #[pyo3(signature = (json_data, ref_uid, tx_options=None))]
     pub fn do_obligation_json<'py>(
         &self,
         py: pyo3::Python<'py>,
         json_data: &Bound<'_, PyAny>,
         ref_uid: Option<String>,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
         let json_string = python_to_json_string(json_data).map_err(map_eyre_to_pyerr)?;
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let tx = self.tx.clone();
+        let obligation = self.inner.addresses.obligation;
+        crate::runtime::future_into_py(py, async move {
             let json_value: serde_json::Value =
                 serde_json::from_str(&json_string).map_err(map_serde_to_pyerr)?;
 
-            let ref_uid = if let Some(ref_uid_str) = ref_uid {
+            let ref_uid: Option<FixedBytes<32>> = if let Some(ref_uid_str) = ref_uid {
                 Some(ref_uid_str.parse().map_err(map_parse_to_pyerr)?)
             } else {
                 None
             };
 
-            let receipt = inner
-                .do_obligation_json(json_value, ref_uid)
+            let call = StringObligation::new(obligation, tx.provider())
+                .doObligation(
+                    StringObligation::ObligationData {
+                        item: serde_json::to_string(&json_value).map_err(map_serde_to_pyerr)?,
+                    },
+                    ref_uid.unwrap_or_default(),
+                )
+                .into_transaction_request();
+            // The attestation UID rather than the transaction hash
+            let attested = tx
+                .send_attested(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-
-            // Extract the attestation UID from the receipt instead of returning transaction hash
-            use alkahest_rs::DefaultAlkahestClient;
-            let attested_event =
-                DefaultAlkahestClient::get_attested_event(receipt).map_err(map_eyre_to_pyerr)?;
-            Ok(format!(
-                "0x{}",
-                alloy::hex::encode(attested_event.uid.as_slice())
-            ))
+            Ok(attested.log.uid)
         })
     }
 }
@@ -130,7 +138,6 @@ impl PyStringObligationData {
 
     #[staticmethod]
     pub fn encode(obligation: &PyStringObligationData) -> PyResult<Vec<u8>> {
-        use alkahest_rs::contracts::StringObligation;
         use alloy::sol_types::SolValue;
 
         let obligation_data = StringObligation::ObligationData {
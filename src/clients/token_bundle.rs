@@ -1,183 +1,392 @@
-use alkahest_rs::clients::token_bundle;
-use pyo3::{pyclass, pymethods, PyResult};
+use alkahest_rs::{
+    clients::token_bundle,
+    contracts::{
+        TokenBundleBarterUtils, TokenBundleEscrowObligation, TokenBundlePaymentObligation,
+    },
+};
+use alloy::primitives::{Address, Bytes};
+use pyo3::{pyclass, pymethods, IntoPyObjectExt, PyResult, Python};
 
 use crate::{
-    error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr},
-    get_attested_event,
-    types::{ArbiterData, AttestedLog, LogWithHash, TokenBundleData},
+    error_handling::{
+        map_eyre_to_pyerr, map_parse_to_pyerr, map_sol_decode_to_pyerr, parse_address,
+    },
+    interfaces::{IERC1155, IERC20, IERC721},
+    receipt::PyTransactionReceipt,
+    tx::{TxOptions, TxSender},
+    types::{ArbiterData, PyU256, TokenBundleData},
+    unsigned::build_sequence,
 };
 
 #[pyclass]
 #[derive(Clone)]
 pub struct TokenBundleClient {
     inner: token_bundle::TokenBundleClient,
+    tx: TxSender,
 }
 
 impl TokenBundleClient {
-    pub fn new(inner: token_bundle::TokenBundleClient) -> Self {
-        Self { inner }
+    pub fn new(inner: token_bundle::TokenBundleClient, tx: TxSender) -> Self {
+        Self { inner, tx }
     }
 }
 
 #[pymethods]
 impl TokenBundleClient {
+    #[pyo3(signature = (token, purpose, tx_options=None))]
     pub fn approve<'py>(
         &self,
         py: pyo3::Python<'py>,
         token: TokenBundleData,
         purpose: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let purpose = match purpose.as_str() {
-                "payment" => alkahest_rs::types::ApprovalPurpose::Payment,
-                "escrow" => alkahest_rs::types::ApprovalPurpose::Escrow,
-                _ => return Err(map_eyre_to_pyerr(eyre::eyre!("Invalid purpose"))),
-            };
-            let receipts = inner
-                .approve(&token.try_into().map_err(map_eyre_to_pyerr)?, purpose)
-                .await
-                .map_err(map_eyre_to_pyerr)?;
+        let tx = self.tx.clone();
+        let spender = match purpose.as_str() {
+            "payment" => self.inner.addresses.payment_obligation,
+            "escrow" => self.inner.addresses.escrow_obligation,
+            _ => return Err(map_eyre_to_pyerr(eyre::eyre!("Invalid purpose"))),
+        };
+        crate::runtime::future_into_py(py, async move {
+            let token: alkahest_rs::types::TokenBundleData =
+                token.try_into().map_err(map_eyre_to_pyerr)?;
 
-            // Return the transaction hash of the last receipt, or empty string if no receipts
-            match receipts.last() {
-                Some(receipt) => Ok(receipt.transaction_hash.to_string()),
-                None => Ok("".to_string()),
+            let mut calls = Vec::new();
+            for erc20 in &token.erc20s {
+                calls.push(
+                    IERC20::new(erc20.address, tx.provider())
+                        .approve(spender, erc20.value)
+                        .into_transaction_request(),
+                );
+            }
+            for erc721 in &token.erc721s {
+                calls.push(
+                    IERC721::new(erc721.address, tx.provider())
+                        .approve(spender, erc721.id)
+                        .into_transaction_request(),
+                );
+            }
+            // ERC1155 has no per-id approval, so each contract is approved once.
+            let mut erc1155_contracts: Vec<Address> =
+                token.erc1155s.iter().map(|x| x.address).collect();
+            erc1155_contracts.sort();
+            erc1155_contracts.dedup();
+            for erc1155 in erc1155_contracts {
+                calls.push(
+                    IERC1155::new(erc1155, tx.provider())
+                        .setApprovalForAll(spender, true)
+                        .into_transaction_request(),
+                );
             }
+
+            // Built all at once, since each approval is signed and broadcast
+            // on its own
+            if tx.is_unsigned() {
+                let built = build_sequence(&tx, calls, tx_options.as_ref())
+                    .await
+                    .map_err(map_eyre_to_pyerr)?;
+                return Python::with_gil(|py| built.into_py_any(py));
+            }
+
+            let mut receipts = Vec::with_capacity(calls.len());
+            for (index, call) in calls.into_iter().enumerate() {
+                let options = tx_options
+                    .as_ref()
+                    .map(|options| options.for_sequence(index as u64));
+                let receipt = tx
+                    .send(call, options.as_ref())
+                    .await
+                    .map_err(map_eyre_to_pyerr)?;
+                receipts.push(PyTransactionReceipt::from(receipt));
+            }
+
+            // One receipt per token approved, in bundle order
+            Python::with_gil(|py| receipts.into_py_any(py))
         })
     }
 
+    #[pyo3(signature = (buy_attestation, fulfillment, tx_options=None))]
     pub fn collect_escrow<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
         fulfillment: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .collect_escrow(
+        let tx = self.tx.clone();
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let call = TokenBundleEscrowObligation::new(escrow_obligation, tx.provider())
+                .collectEscrow(
                     buy_attestation.parse().map_err(map_parse_to_pyerr)?,
                     fulfillment.parse().map_err(map_parse_to_pyerr)?,
                 )
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn reclaim_expired<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .reclaim_expired(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let call = TokenBundleEscrowObligation::new(escrow_obligation, tx.provider())
+                .reclaimExpired(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            let receipt = tx
+                .send(call, tx_options.as_ref())
                 .await
                 .map_err(map_eyre_to_pyerr)?;
-            Ok(receipt.transaction_hash.to_string())
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
+    #[pyo3(signature = (price, item, expiration, tx_options=None))]
     pub fn buy_with_bundle<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: TokenBundleData,
         item: ArbiterData,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_with_bundle(
-                    &price.try_into().map_err(map_eyre_to_pyerr)?,
-                    &item.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let escrow_obligation = self.inner.addresses.escrow_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let price: alkahest_rs::types::TokenBundleData =
+                price.try_into().map_err(map_eyre_to_pyerr)?;
+            let item: alkahest_rs::types::ArbiterData =
+                item.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = TokenBundleEscrowObligation::new(escrow_obligation, tx.provider())
+                .doObligation(
+                    TokenBundleEscrowObligation::ObligationData {
+                        arbiter: item.arbiter,
+                        demand: item.demand,
+                        erc20Tokens: price.erc20s.iter().map(|x| x.address).collect(),
+                        erc20Amounts: price.erc20s.iter().map(|x| x.value).collect(),
+                        erc721Tokens: price.erc721s.iter().map(|x| x.address).collect(),
+                        erc721TokenIds: price.erc721s.iter().map(|x| x.id).collect(),
+                        erc1155Tokens: price.erc1155s.iter().map(|x| x.address).collect(),
+                        erc1155TokenIds: price.erc1155s.iter().map(|x| x.id).collect(),
+                        erc1155Amounts: price.erc1155s.iter().map(|x| x.value).collect(),
+                    },
                     expiration,
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (price, payee, tx_options=None))]
     pub fn pay_with_bundle<'py>(
         &self,
         py: pyo3::Python<'py>,
         price: TokenBundleData,
         payee: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .pay_with_bundle(
-                    &price.try_into().map_err(map_eyre_to_pyerr)?,
-                    payee.parse().map_err(map_parse_to_pyerr)?,
-                )
+        let tx = self.tx.clone();
+        let payment_obligation = self.inner.addresses.payment_obligation;
+        crate::runtime::future_into_py(py, async move {
+            let price: alkahest_rs::types::TokenBundleData =
+                price.try_into().map_err(map_eyre_to_pyerr)?;
+            let call = TokenBundlePaymentObligation::new(payment_obligation, tx.provider())
+                .doObligation(TokenBundlePaymentObligation::ObligationData {
+                    erc20Tokens: price.erc20s.iter().map(|x| x.address).collect(),
+                    erc20Amounts: price.erc20s.iter().map(|x| x.value).collect(),
+                    erc721Tokens: price.erc721s.iter().map(|x| x.address).collect(),
+                    erc721TokenIds: price.erc721s.iter().map(|x| x.id).collect(),
+                    erc1155Tokens: price.erc1155s.iter().map(|x| x.address).collect(),
+                    erc1155TokenIds: price.erc1155s.iter().map(|x| x.id).collect(),
+                    erc1155Amounts: price.erc1155s.iter().map(|x| x.value).collect(),
+                    payee: parse_address(&payee)?,
+                })
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (bid, ask, expiration, tx_options=None))]
     pub fn buy_bundle_for_bundle<'py>(
         &self,
         py: pyo3::Python<'py>,
         bid: TokenBundleData,
         ask: TokenBundleData,
         expiration: u64,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .buy_bundle_for_bundle(
-                    &bid.try_into().map_err(map_eyre_to_pyerr)?,
-                    &ask.try_into().map_err(map_eyre_to_pyerr)?,
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let bid: alkahest_rs::types::TokenBundleData =
+                bid.try_into().map_err(map_eyre_to_pyerr)?;
+            let ask: alkahest_rs::types::TokenBundleData =
+                ask.try_into().map_err(map_eyre_to_pyerr)?;
+            // The barter contract derives the escrow's arbiter and demand from `ask`.
+            let unset_arbiter = alkahest_rs::types::ArbiterData {
+                arbiter: Address::ZERO,
+                demand: Bytes::new(),
+            };
+            let call = TokenBundleBarterUtils::new(barter_utils, tx.provider())
+                .buyBundleForBundle(
+                    (bid, unset_arbiter).into(),
+                    (ask, tx.address().map_err(map_eyre_to_pyerr)?).into(),
                     expiration,
                 )
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 
+    #[pyo3(signature = (buy_attestation, tx_options=None))]
     pub fn pay_bundle_for_bundle<'py>(
         &self,
         py: pyo3::Python<'py>,
         buy_attestation: String,
+        tx_options: Option<TxOptions>,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
-        let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let receipt = inner
-                .pay_bundle_for_bundle(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+        let tx = self.tx.clone();
+        let barter_utils = self.inner.addresses.barter_utils;
+        crate::runtime::future_into_py(py, async move {
+            let call = TokenBundleBarterUtils::new(barter_utils, tx.provider())
+                .payBundleForBundle(buy_attestation.parse().map_err(map_parse_to_pyerr)?)
+                .into_transaction_request();
+            tx.send_attested(call, tx_options.as_ref())
                 .await
-                .map_err(map_eyre_to_pyerr)?;
-            Ok(LogWithHash::<AttestedLog> {
-                log: get_attested_event(receipt.clone())
-                    .map_err(map_eyre_to_pyerr)?
-                    .data
-                    .into(),
-                transaction_hash: receipt.transaction_hash.to_string(),
-            })
+                .map_err(map_eyre_to_pyerr)
         })
     }
 }
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyTokenBundlePaymentObligationData {
+    #[pyo3(get)]
+    pub erc20_tokens: Vec<String>,
+    #[pyo3(get)]
+    pub erc20_amounts: Vec<PyU256>,
+    #[pyo3(get)]
+    pub erc721_tokens: Vec<String>,
+    #[pyo3(get)]
+    pub erc721_token_ids: Vec<PyU256>,
+    #[pyo3(get)]
+    pub erc1155_tokens: Vec<String>,
+    #[pyo3(get)]
+    pub erc1155_token_ids: Vec<PyU256>,
+    #[pyo3(get)]
+    pub erc1155_amounts: Vec<PyU256>,
+    #[pyo3(get)]
+    pub payee: String,
+}
+
+#[pymethods]
+impl PyTokenBundlePaymentObligationData {
+    #[new]
+    #[pyo3(signature = (payee, erc20_tokens=vec![], erc20_amounts=vec![], erc721_tokens=vec![], erc721_token_ids=vec![], erc1155_tokens=vec![], erc1155_token_ids=vec![], erc1155_amounts=vec![]))]
+    pub fn new(
+        payee: String,
+        erc20_tokens: Vec<String>,
+        erc20_amounts: Vec<PyU256>,
+        erc721_tokens: Vec<String>,
+        erc721_token_ids: Vec<PyU256>,
+        erc1155_tokens: Vec<String>,
+        erc1155_token_ids: Vec<PyU256>,
+        erc1155_amounts: Vec<PyU256>,
+    ) -> Self {
+        Self {
+            erc20_tokens,
+            erc20_amounts,
+            erc721_tokens,
+            erc721_token_ids,
+            erc1155_tokens,
+            erc1155_token_ids,
+            erc1155_amounts,
+            payee,
+        }
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "PyTokenBundlePaymentObligationData(erc20s={}, erc721s={}, erc1155s={}, payee='{}')",
+            self.erc20_tokens.len(),
+            self.erc721_tokens.len(),
+            self.erc1155_tokens.len(),
+            self.payee
+        )
+    }
+
+    #[staticmethod]
+    pub fn decode(obligation_data: Vec<u8>) -> PyResult<PyTokenBundlePaymentObligationData> {
+        use alloy::sol_types::SolValue;
+
+        let decoded = TokenBundlePaymentObligation::ObligationData::abi_decode(&obligation_data)
+            .map_err(map_sol_decode_to_pyerr)?;
+        Ok(decoded.into())
+    }
+
+    #[staticmethod]
+    pub fn encode(obligation: &PyTokenBundlePaymentObligationData) -> PyResult<Vec<u8>> {
+        use alloy::sol_types::SolValue;
+
+        let addresses = |values: &[String]| {
+            values
+                .iter()
+                .map(|value| parse_address(value))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let amounts = |values: &[PyU256]| values.iter().map(|&value| value.into()).collect();
+
+        let obligation_data = TokenBundlePaymentObligation::ObligationData {
+            erc20Tokens: addresses(&obligation.erc20_tokens)?,
+            erc20Amounts: amounts(&obligation.erc20_amounts),
+            erc721Tokens: addresses(&obligation.erc721_tokens)?,
+            erc721TokenIds: amounts(&obligation.erc721_token_ids),
+            erc1155Tokens: addresses(&obligation.erc1155_tokens)?,
+            erc1155TokenIds: amounts(&obligation.erc1155_token_ids),
+            erc1155Amounts: amounts(&obligation.erc1155_amounts),
+            payee: parse_address(&obligation.payee)?,
+        };
+
+        Ok(obligation_data.abi_encode())
+    }
+
+    pub fn encode_self(&self) -> PyResult<Vec<u8>> {
+        PyTokenBundlePaymentObligationData::encode(self)
+    }
+}
+
+impl From<TokenBundlePaymentObligation::ObligationData> for PyTokenBundlePaymentObligationData {
+    fn from(data: TokenBundlePaymentObligation::ObligationData) -> Self {
+        let addresses =
+            |values: Vec<Address>| values.iter().map(|value| format!("{:?}", value)).collect();
+        let amounts =
+            |values: Vec<alloy::primitives::U256>| values.into_iter().map(PyU256::from).collect();
+
+        Self {
+            erc20_tokens: addresses(data.erc20Tokens),
+            erc20_amounts: amounts(data.erc20Amounts),
+            erc721_tokens: addresses(data.erc721Tokens),
+            erc721_token_ids: amounts(data.erc721TokenIds),
+            erc1155_tokens: addresses(data.erc1155Tokens),
+            erc1155_token_ids: amounts(data.erc1155TokenIds),
+            erc1155_amounts: amounts(data.erc1155Amounts),
+            payee: format!("{:?}", data.payee),
+        }
+    }
+}
@@ -1,21 +1,241 @@
-use pyo3::PyErr;
+use std::fmt;
 
-/// Maps eyre::Error to PyRuntimeError
+use alloy::{
+    primitives::{Address, Bytes, B256},
+    providers::PendingTransactionError,
+    transports::TransportError,
+};
+use pyo3::{
+    import_exception,
+    types::{PyAnyMethods, PyBytes},
+    PyErr, PyTypeInfo, Python,
+};
+
+use crate::{
+    pending::TxCancelled,
+    reverts::{args_to_py, decode_revert, DecodedRevert},
+};
+
+// The hierarchy lives in alkahest_py/exceptions.py so the address, decode and
+// extension errors can also subclass ValueError / AttributeError.
+import_exception!(alkahest_py.exceptions, AlkahestError);
+import_exception!(alkahest_py.exceptions, RpcError);
+import_exception!(alkahest_py.exceptions, TransactionReverted);
+import_exception!(alkahest_py.exceptions, InsufficientAllowance);
+import_exception!(alkahest_py.exceptions, InvalidAddress);
+import_exception!(alkahest_py.exceptions, DecodeError);
+import_exception!(alkahest_py.exceptions, ExtensionNotAvailable);
+import_exception!(alkahest_py.exceptions, TransactionCancelled);
+
+/// A transaction that reverted, either on chain or while estimating gas.
+#[derive(Debug, Clone)]
+pub struct TxReverted {
+    pub tx_hash: Option<B256>,
+    pub contract_address: Option<Address>,
+    pub revert_data: Option<Bytes>,
+    pub reason: String,
+}
+
+impl fmt::Display for TxReverted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "transaction reverted: {}", self.reason)?;
+        if let Some(tx_hash) = self.tx_hash {
+            write!(f, " (tx {})", tx_hash)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for TxReverted {}
+
+/// A string that doesn't parse as an address.
+#[derive(Debug, Clone)]
+pub struct AddressError {
+    pub value: String,
+    pub reason: String,
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid address {:?}: {}", self.value, self.reason)
+    }
+}
+
+impl std::error::Error for AddressError {}
+
+impl From<AddressError> for PyErr {
+    fn from(err: AddressError) -> Self {
+        Python::with_gil(|py| {
+            let py_err = InvalidAddress::new_err(err.to_string());
+            let _ = py_err.value(py).setattr("value", err.value);
+            py_err
+        })
+    }
+}
+
+pub fn parse_address(value: &str) -> Result<Address, AddressError> {
+    value.parse::<Address>().map_err(|e| AddressError {
+        value: value.to_string(),
+        reason: e.to_string(),
+    })
+}
+
+/// Maps eyre::Error to the matching AlkahestError subclass
 pub fn map_eyre_to_pyerr(err: eyre::Error) -> PyErr {
-    pyo3::exceptions::PyRuntimeError::new_err(format!("{}", err))
+    let message = format!("{}", err);
+    classify_eyre(&err, message)
 }
 
-/// Maps parse errors to PyValueError
+/// Like [`map_eyre_to_pyerr`], prefixing the message with what was being attempted
+pub fn map_eyre_with_context(context: &str, err: eyre::Error) -> PyErr {
+    let message = format!("{}: {}", context, err);
+    classify_eyre(&err, message)
+}
+
+fn classify_eyre(err: &eyre::Error, message: String) -> PyErr {
+    for cause in err.chain() {
+        // Raised by a Python callback, e.g. an oracle's decision function
+        if let Some(py_err) = cause.downcast_ref::<PyErr>() {
+            return Python::with_gil(|py| py_err.clone_ref(py));
+        }
+        if let Some(reverted) = cause.downcast_ref::<TxReverted>() {
+            return reverted_to_pyerr(reverted);
+        }
+        if let Some(cancelled) = cause.downcast_ref::<TxCancelled>() {
+            return with_attrs::<TransactionCancelled>(
+                message,
+                &[
+                    ("tx_hash", Some(cancelled.tx_hash.to_string())),
+                    ("cancelled_by", Some(cancelled.cancelled_by.to_string())),
+                ],
+            );
+        }
+        if let Some(address) = cause.downcast_ref::<AddressError>() {
+            return address.clone().into();
+        }
+        if let Some(transport) = cause.downcast_ref::<TransportError>() {
+            return transport_to_pyerr(transport, None);
+        }
+        if let Some(contract) = cause.downcast_ref::<alloy::contract::Error>() {
+            match contract {
+                alloy::contract::Error::TransportError(transport) => {
+                    return transport_to_pyerr(transport, None)
+                }
+                alloy::contract::Error::AbiError(_) => return DecodeError::new_err(message),
+                _ => {}
+            }
+        }
+        if cause.is::<PendingTransactionError>() {
+            return RpcError::new_err(message);
+        }
+        if cause.is::<alloy::sol_types::Error>() {
+            return DecodeError::new_err(message);
+        }
+    }
+    AlkahestError::new_err(message)
+}
+
+/// Maps parse errors, e.g. of a UID or transaction hash, to DecodeError
 pub fn map_parse_to_pyerr<T: std::fmt::Display>(err: T) -> PyErr {
-    pyo3::exceptions::PyValueError::new_err(format!("Parse error: {}", err))
+    DecodeError::new_err(format!("Parse error: {}", err))
 }
 
-/// Maps serde errors to PyValueError
+/// Maps serde errors to DecodeError
 pub fn map_serde_to_pyerr<T: std::fmt::Display>(err: T) -> PyErr {
-    pyo3::exceptions::PyValueError::new_err(format!("Serialization error: {}", err))
+    DecodeError::new_err(format!("Serialization error: {}", err))
 }
 
-/// Maps alloy sol types decode errors to PyValueError
+/// Maps alloy sol types decode errors to DecodeError
 pub fn map_sol_decode_to_pyerr(err: alloy::sol_types::Error) -> PyErr {
-    pyo3::exceptions::PyValueError::new_err(format!("Sol decode error: {}", err))
+    DecodeError::new_err(format!("Sol decode error: {}", err))
+}
+
+/// Raised by the client when an extension wasn't configured
+pub fn extension_not_available(extension: &str, message: String) -> PyErr {
+    with_attrs::<ExtensionNotAvailable>(message, &[("extension", Some(extension.to_string()))])
+}
+
+/// Classifies an RPC failure. Node errors that carry revert data come from gas
+/// estimation or `eth_call` and are reported as reverts.
+pub fn transport_to_pyerr(err: &TransportError, contract_address: Option<Address>) -> PyErr {
+    if let Some(reverted) = revert_from_transport(err, contract_address) {
+        return reverted_to_pyerr(&reverted);
+    }
+    RpcError::new_err(format!("{}", err))
+}
+
+pub fn revert_from_transport(
+    err: &TransportError,
+    contract_address: Option<Address>,
+) -> Option<TxReverted> {
+    let resp = err.as_error_resp()?;
+    let revert_data = resp.as_revert_data();
+    if revert_data.is_none() && !resp.message.contains("revert") {
+        return None;
+    }
+    Some(TxReverted {
+        tx_hash: None,
+        contract_address,
+        revert_data,
+        reason: resp.message.to_string(),
+    })
+}
+
+fn reverted_to_pyerr(reverted: &TxReverted) -> PyErr {
+    let decoded = reverted
+        .revert_data
+        .as_ref()
+        .and_then(|data| decode_revert(data));
+    let message = match &decoded {
+        Some(decoded) => TxReverted {
+            reason: decoded.describe(),
+            ..reverted.clone()
+        }
+        .to_string(),
+        None => reverted.to_string(),
+    };
+    let attrs = [
+        ("tx_hash", reverted.tx_hash.map(|h| h.to_string())),
+        (
+            "contract_address",
+            reverted.contract_address.map(|a| a.to_string()),
+        ),
+        ("error_name", decoded.as_ref().map(|d| d.name.clone())),
+    ];
+    let py_err = if is_insufficient_allowance(reverted, decoded.as_ref()) {
+        with_attrs::<InsufficientAllowance>(message, &attrs)
+    } else {
+        with_attrs::<TransactionReverted>(message, &attrs)
+    };
+    Python::with_gil(|py| {
+        let value = py_err.value(py);
+        let revert_data = reverted
+            .revert_data
+            .as_ref()
+            .map(|data| PyBytes::new(py, data));
+        let _ = value.setattr("revert_data", revert_data);
+        if let Some(decoded) = &decoded {
+            if let Ok(args) = args_to_py(py, &decoded.args) {
+                let _ = value.setattr("error_args", args);
+            }
+        }
+    });
+    py_err
+}
+
+fn is_insufficient_allowance(reverted: &TxReverted, decoded: Option<&DecodedRevert>) -> bool {
+    // OpenZeppelin 5 custom error, or the v4 require message
+    decoded.is_some_and(|d| d.name == "ERC20InsufficientAllowance")
+        || reverted.reason.contains("insufficient allowance")
+}
+
+fn with_attrs<T: PyTypeInfo>(message: String, attrs: &[(&str, Option<String>)]) -> PyErr {
+    Python::with_gil(|py| {
+        let py_err = PyErr::new::<T, _>(message);
+        let value = py_err.value(py);
+        for (name, attr) in attrs {
+            let _ = value.setattr(*name, attr.clone());
+        }
+        py_err
+    })
 }
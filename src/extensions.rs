@@ -0,0 +1,286 @@
+use alkahest_rs::{
+    clients::{attestation, erc1155, erc20, erc721, oracle, string_obligation, token_bundle},
+    extensions::{
+        AlkahestExtension, AttestationModule, Erc1155Module, Erc20Module, Erc721Module,
+        HasAttestation, HasErc1155, HasErc20, HasErc721, HasOracle, HasStringObligation,
+        HasTokenBundle, NoExtension, OracleModule, StringObligationModule, TokenBundleModule,
+    },
+    AlkahestClient, DefaultExtensionConfig,
+};
+use pyo3::{exceptions::PyValueError, PyErr};
+
+use crate::tx::TxSender;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionKind {
+    Erc20,
+    Erc721,
+    Erc1155,
+    TokenBundle,
+    Attestation,
+    StringObligation,
+    Oracle,
+}
+
+impl ExtensionKind {
+    pub const ALL: [ExtensionKind; 7] = [
+        ExtensionKind::Erc20,
+        ExtensionKind::Erc721,
+        ExtensionKind::Erc1155,
+        ExtensionKind::TokenBundle,
+        ExtensionKind::Attestation,
+        ExtensionKind::StringObligation,
+        ExtensionKind::Oracle,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ExtensionKind::Erc20 => "erc20",
+            ExtensionKind::Erc721 => "erc721",
+            ExtensionKind::Erc1155 => "erc1155",
+            ExtensionKind::TokenBundle => "token_bundle",
+            ExtensionKind::Attestation => "attestation",
+            ExtensionKind::StringObligation => "string_obligation",
+            ExtensionKind::Oracle => "oracle",
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Self, PyErr> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.name() == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|kind| kind.name()).collect();
+                PyValueError::new_err(format!(
+                    "Unknown extension {:?}, expected one of: {}",
+                    name,
+                    names.join(", ")
+                ))
+            })
+    }
+}
+
+/// The alkahest-rs extension clients wired into a `PyAlkahestClient`.
+///
+/// Every handle is built on the providers of the client's [`TxSender`], so
+/// they all share its failover connection and none opens one of its own.
+#[derive(Clone, Default)]
+pub struct ExtensionHandles {
+    pub erc20: Option<erc20::Erc20Client>,
+    pub erc721: Option<erc721::Erc721Client>,
+    pub erc1155: Option<erc1155::Erc1155Client>,
+    pub token_bundle: Option<token_bundle::TokenBundleClient>,
+    pub attestation: Option<attestation::AttestationClient>,
+    pub string_obligation: Option<string_obligation::StringObligationClient>,
+    pub oracle: Option<oracle::OracleClient>,
+}
+
+impl ExtensionHandles {
+    pub fn from_default(client: &AlkahestClient) -> Self {
+        Self {
+            erc20: Some(client.extensions.erc20().clone()),
+            erc721: Some(client.extensions.erc721().clone()),
+            erc1155: Some(client.extensions.erc1155().clone()),
+            token_bundle: Some(client.extensions.token_bundle().clone()),
+            attestation: Some(client.extensions.attestation().clone()),
+            string_obligation: Some(client.extensions.string_obligation().clone()),
+            oracle: Some(client.extensions.oracle().clone()),
+        }
+    }
+
+    pub fn has(&self, kind: ExtensionKind) -> bool {
+        match kind {
+            ExtensionKind::Erc20 => self.erc20.is_some(),
+            ExtensionKind::Erc721 => self.erc721.is_some(),
+            ExtensionKind::Erc1155 => self.erc1155.is_some(),
+            ExtensionKind::TokenBundle => self.token_bundle.is_some(),
+            ExtensionKind::Attestation => self.attestation.is_some(),
+            ExtensionKind::StringObligation => self.string_obligation.is_some(),
+            ExtensionKind::Oracle => self.oracle.is_some(),
+        }
+    }
+
+    pub fn kinds(&self) -> Vec<ExtensionKind> {
+        ExtensionKind::ALL
+            .into_iter()
+            .filter(|kind| self.has(*kind))
+            .collect()
+    }
+
+    /// Takes every handle `other` has, keeping ours where it has none.
+    pub fn merge(&mut self, other: &Self) {
+        for kind in ExtensionKind::ALL {
+            self.take_from(other, kind);
+        }
+    }
+
+    /// Copies the `kind` handle from `other`, if it has one.
+    pub fn take_from(&mut self, other: &Self, kind: ExtensionKind) {
+        match kind {
+            ExtensionKind::Erc20 => self.erc20 = other.erc20.clone().or(self.erc20.take()),
+            ExtensionKind::Erc721 => self.erc721 = other.erc721.clone().or(self.erc721.take()),
+            ExtensionKind::Erc1155 => self.erc1155 = other.erc1155.clone().or(self.erc1155.take()),
+            ExtensionKind::TokenBundle => {
+                self.token_bundle = other.token_bundle.clone().or(self.token_bundle.take())
+            }
+            ExtensionKind::Attestation => {
+                self.attestation = other.attestation.clone().or(self.attestation.take())
+            }
+            ExtensionKind::StringObligation => {
+                self.string_obligation = other
+                    .string_obligation
+                    .clone()
+                    .or(self.string_obligation.take())
+            }
+            ExtensionKind::Oracle => self.oracle = other.oracle.clone().or(self.oracle.take()),
+        }
+    }
+
+    /// Points the matching handle at `addresses`. The handle keeps its
+    /// provider, so no new connection is made.
+    pub fn set_addresses(&mut self, addresses: ExtensionAddresses) {
+        match addresses {
+            ExtensionAddresses::Erc20(addresses) => {
+                if let Some(client) = &mut self.erc20 {
+                    client.addresses = addresses;
+                }
+            }
+            ExtensionAddresses::Erc721(addresses) => {
+                if let Some(client) = &mut self.erc721 {
+                    client.addresses = addresses;
+                }
+            }
+            ExtensionAddresses::Erc1155(addresses) => {
+                if let Some(client) = &mut self.erc1155 {
+                    client.addresses = addresses;
+                }
+            }
+            ExtensionAddresses::TokenBundle(addresses) => {
+                if let Some(client) = &mut self.token_bundle {
+                    client.addresses = addresses;
+                }
+            }
+            ExtensionAddresses::Attestation(addresses) => {
+                if let Some(client) = &mut self.attestation {
+                    client.addresses = addresses;
+                }
+            }
+            ExtensionAddresses::StringObligation(addresses) => {
+                if let Some(client) = &mut self.string_obligation {
+                    client.addresses = addresses;
+                }
+            }
+            ExtensionAddresses::Oracle(addresses) => {
+                if let Some(client) = &mut self.oracle {
+                    client.addresses = addresses;
+                }
+            }
+        }
+    }
+
+    /// Builds a `kind` handle for a client that doesn't have one to share, on
+    /// `tx`'s providers. The handle is only used for addresses, decoding and
+    /// reads; transactions still go through `tx`.
+    pub async fn init(
+        kind: ExtensionKind,
+        tx: &TxSender,
+        addresses: Option<ExtensionAddresses>,
+    ) -> eyre::Result<Self> {
+        let providers = tx.alkahest_providers();
+
+        macro_rules! init {
+            ($module:ty, $variant:ident) => {{
+                let addresses = match addresses {
+                    Some(ExtensionAddresses::$variant(addresses)) => Some(addresses),
+                    _ => None,
+                };
+                <$module>::init_with_providers(providers, addresses)
+                    .await?
+                    .extension_handles()
+            }};
+        }
+
+        Ok(match kind {
+            ExtensionKind::Erc20 => init!(Erc20Module, Erc20),
+            ExtensionKind::Erc721 => init!(Erc721Module, Erc721),
+            ExtensionKind::Erc1155 => init!(Erc1155Module, Erc1155),
+            ExtensionKind::TokenBundle => init!(TokenBundleModule, TokenBundle),
+            ExtensionKind::Attestation => init!(AttestationModule, Attestation),
+            ExtensionKind::StringObligation => init!(StringObligationModule, StringObligation),
+            ExtensionKind::Oracle => init!(OracleModule, Oracle),
+        })
+    }
+}
+
+/// Contract addresses for a single extension.
+#[derive(Clone)]
+pub enum ExtensionAddresses {
+    Erc20(erc20::Erc20Addresses),
+    Erc721(erc721::Erc721Addresses),
+    Erc1155(erc1155::Erc1155Addresses),
+    TokenBundle(token_bundle::TokenBundleAddresses),
+    Attestation(attestation::AttestationAddresses),
+    StringObligation(string_obligation::StringObligationAddresses),
+    Oracle(oracle::OracleAddresses),
+}
+
+impl ExtensionAddresses {
+    /// The `kind` section of `config`. DefaultExtensionConfig has no oracle
+    /// section, so the oracle takes the EAS and trusted oracle arbiter
+    /// addresses from the arbiters.
+    pub fn from_config(config: &DefaultExtensionConfig, kind: ExtensionKind) -> Option<Self> {
+        match kind {
+            ExtensionKind::Erc20 => config.erc20_addresses.clone().map(Self::Erc20),
+            ExtensionKind::Erc721 => config.erc721_addresses.clone().map(Self::Erc721),
+            ExtensionKind::Erc1155 => config.erc1155_addresses.clone().map(Self::Erc1155),
+            ExtensionKind::TokenBundle => {
+                config.token_bundle_addresses.clone().map(Self::TokenBundle)
+            }
+            ExtensionKind::Attestation => {
+                config.attestation_addresses.clone().map(Self::Attestation)
+            }
+            ExtensionKind::StringObligation => config
+                .string_obligation_addresses
+                .clone()
+                .map(Self::StringObligation),
+            ExtensionKind::Oracle => config.arbiters_addresses.as_ref().map(|arbiters| {
+                Self::Oracle(oracle::OracleAddresses {
+                    eas: arbiters.eas,
+                    trusted_oracle_arbiter: arbiters.trusted_oracle_arbiter,
+                })
+            }),
+        }
+    }
+}
+
+/// Extracts the extension handles out of a typed `AlkahestClient<T>`'s extensions.
+pub trait IntoExtensionHandles {
+    fn extension_handles(&self) -> ExtensionHandles;
+}
+
+impl IntoExtensionHandles for NoExtension {
+    fn extension_handles(&self) -> ExtensionHandles {
+        ExtensionHandles::default()
+    }
+}
+
+macro_rules! module_handles {
+    ($module:ty, $field:ident) => {
+        impl IntoExtensionHandles for $module {
+            fn extension_handles(&self) -> ExtensionHandles {
+                ExtensionHandles {
+                    $field: Some(self.client.clone()),
+                    ..Default::default()
+                }
+            }
+        }
+    };
+}
+
+module_handles!(Erc20Module, erc20);
+module_handles!(Erc721Module, erc721);
+module_handles!(Erc1155Module, erc1155);
+module_handles!(TokenBundleModule, token_bundle);
+module_handles!(AttestationModule, attestation);
+module_handles!(StringObligationModule, string_obligation);
+module_handles!(OracleModule, oracle);
@@ -1,4 +1,11 @@
-use crate::{utils::PyWalletProvider, PyAlkahestClient};
+use crate::{
+    error_handling::{map_eyre_to_pyerr, parse_address},
+    receipt::PyTransactionReceipt,
+    runtime::block_on,
+    types::PyU256,
+    utils::PyWalletProvider,
+    PyAlkahestClient,
+};
 use alkahest_rs::{
     extensions::NoExtension,
     fixtures::{MockERC1155, MockERC20Permit, MockERC721},
@@ -16,9 +23,7 @@ pub struct PyMockERC20 {
 impl PyMockERC20 {
     #[new]
     pub fn new(address: String, provider: &PyWalletProvider) -> PyResult<Self> {
-        let addr = address
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        let addr = parse_address(&address)?;
 
         let contract = MockERC20Permit::MockERC20PermitInstance::new(addr, provider.inner.clone());
 
@@ -30,90 +35,64 @@ impl PyMockERC20 {
         format!("{:?}", self.inner.address())
     }
 
-    pub fn transfer(&self, to: String, value: u64) -> PyResult<()> {
-        let to_addr = to
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-        let rt = tokio::runtime::Runtime::new()?;
-        rt.block_on(async {
-            self.inner
-                .transfer(to_addr, U256::from(value))
+    pub fn transfer(&self, to: String, value: PyU256) -> PyResult<PyTransactionReceipt> {
+        let to_addr = parse_address(&to)?;
+        block_on(async {
+            let receipt = self
+                .inner
+                .transfer(to_addr, value.into())
                 .send()
                 .await
                 .map_err(|e| {
                     eprintln!("Transfer failed: {e}");
-                    pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+                    map_eyre_to_pyerr(e.into())
                 })?
                 .get_receipt()
                 .await
                 .map_err(|e| {
                     eprintln!("Get receipt failed: {e}");
-                    pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+                    map_eyre_to_pyerr(e.into())
                 })?;
 
-            Ok(())
+            Ok(receipt.into())
         })
     }
 
-    pub fn balance_of(&self, address: String) -> PyResult<u128> {
-        let addr = address
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+    pub fn balance_of(&self, address: String) -> PyResult<PyU256> {
+        let addr = parse_address(&address)?;
 
-        let rt = tokio::runtime::Runtime::new()?;
-        let balance = rt
-            .block_on(async { self.inner.balanceOf(addr).call().await })
-            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+        let balance = block_on(async { self.inner.balanceOf(addr).call().await })
+            .map_err(|e| map_eyre_to_pyerr(e.into()))?;
 
-        let balance_u128 = balance.try_into().map_err(|_| {
-            pyo3::exceptions::PyOverflowError::new_err("Balance too large for u128")
-        })?;
-
-        Ok(balance_u128)
+        Ok(balance.into())
     }
 
-    pub fn allowance(&self, owner: String, spender: String) -> PyResult<u128> {
-        let owner_addr = owner
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-        let spender_addr = spender
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-
-        let rt = tokio::runtime::Runtime::new()?;
-        let allowance = rt
-            .block_on(async { self.inner.allowance(owner_addr, spender_addr).call().await })
-            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    pub fn allowance(&self, owner: String, spender: String) -> PyResult<PyU256> {
+        let owner_addr = parse_address(&owner)?;
+        let spender_addr = parse_address(&spender)?;
 
-        let allowance_u128 = allowance.try_into().map_err(|_| {
-            pyo3::exceptions::PyOverflowError::new_err("Allowance too large for u128")
-        })?;
+        let allowance =
+            block_on(async { self.inner.allowance(owner_addr, spender_addr).call().await })
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
 
-        Ok(allowance_u128)
+        Ok(allowance.into())
     }
 }
 
 #[pyclass]
 pub struct PyMockERC721 {
     inner: MockERC721::MockERC721Instance<WalletProvider>,
-    runtime: std::sync::Arc<tokio::runtime::Runtime>,
 }
 
 #[pymethods]
 impl PyMockERC721 {
     #[new]
     pub fn new(address: String, provider: &PyWalletProvider) -> PyResult<Self> {
-        let addr = address
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        let addr = parse_address(&address)?;
 
         let contract = MockERC721::MockERC721Instance::new(addr, provider.inner.clone());
-        let runtime = std::sync::Arc::new(tokio::runtime::Runtime::new()?);
 
-        Ok(Self {
-            inner: contract,
-            runtime,
-        })
+        Ok(Self { inner: contract })
     }
 
     #[getter]
@@ -121,11 +100,9 @@ impl PyMockERC721 {
         format!("{:?}", self.inner.address())
     }
 
-    pub fn mint(&self, to: String) -> PyResult<u64> {
-        let to_addr = to
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-        self.runtime.block_on(async {
+    pub fn mint(&self, to: String) -> PyResult<PyU256> {
+        let to_addr = parse_address(&to)?;
+        block_on(async {
             let _token_id = self
                 .inner
                 .mint(to_addr)
@@ -133,133 +110,124 @@ impl PyMockERC721 {
                 .await
                 .map_err(|e| {
                     eprintln!("Mint failed: {e}");
-                    pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+                    map_eyre_to_pyerr(e.into())
                 })?
                 .get_receipt()
                 .await
                 .map_err(|e| {
                     eprintln!("Get receipt failed: {e}");
-                    pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+                    map_eyre_to_pyerr(e.into())
                 })?;
 
             // Return a placeholder token ID for now since the actual implementation would extract it from logs
-            Ok(1u64)
+            Ok(U256::from(1).into())
         })
     }
 
-    pub fn transfer_from(&self, from: String, to: String, token_id: u64) -> PyResult<()> {
-        let from_addr = from
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-        let to_addr = to
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-        self.runtime.block_on(async {
-            self.inner
-                .transferFrom(from_addr, to_addr, U256::from(token_id))
+    pub fn transfer_from(
+        &self,
+        from: String,
+        to: String,
+        token_id: PyU256,
+    ) -> PyResult<PyTransactionReceipt> {
+        let from_addr = parse_address(&from)?;
+        let to_addr = parse_address(&to)?;
+        block_on(async {
+            let receipt = self
+                .inner
+                .transferFrom(from_addr, to_addr, token_id.into())
                 .send()
                 .await
                 .map_err(|e| {
                     eprintln!("Transfer failed: {e}");
-                    pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+                    map_eyre_to_pyerr(e.into())
                 })?
                 .get_receipt()
                 .await
                 .map_err(|e| {
                     eprintln!("Get receipt failed: {e}");
-                    pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+                    map_eyre_to_pyerr(e.into())
                 })?;
 
-            Ok(())
+            Ok(receipt.into())
         })
     }
 
-    pub fn approve(&self, approved: String, token_id: u64) -> PyResult<()> {
-        let approved_addr = approved
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-        self.runtime.block_on(async {
-            self.inner
-                .approve(approved_addr, U256::from(token_id))
+    pub fn approve(&self, approved: String, token_id: PyU256) -> PyResult<PyTransactionReceipt> {
+        let approved_addr = parse_address(&approved)?;
+        block_on(async {
+            let receipt = self
+                .inner
+                .approve(approved_addr, token_id.into())
                 .send()
                 .await
                 .map_err(|e| {
                     eprintln!("Approve failed: {e}");
-                    pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+                    map_eyre_to_pyerr(e.into())
                 })?
                 .get_receipt()
                 .await
                 .map_err(|e| {
                     eprintln!("Get receipt failed: {e}");
-                    pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+                    map_eyre_to_pyerr(e.into())
                 })?;
 
-            Ok(())
+            Ok(receipt.into())
         })
     }
 
-    pub fn owner_of(&self, token_id: u64) -> PyResult<String> {
-        self.runtime.block_on(async {
+    pub fn owner_of(&self, token_id: PyU256) -> PyResult<String> {
+        block_on(async {
             let owner = self
                 .inner
-                .ownerOf(U256::from(token_id))
+                .ownerOf(token_id.into())
                 .call()
                 .await
-                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
 
             Ok(format!("{:?}", owner))
         })
     }
 
-    pub fn balance_of(&self, owner: String) -> PyResult<u128> {
-        let owner_addr = owner
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+    pub fn balance_of(&self, owner: String) -> PyResult<PyU256> {
+        let owner_addr = parse_address(&owner)?;
 
-        self.runtime.block_on(async {
+        block_on(async {
             let balance = self
                 .inner
                 .balanceOf(owner_addr)
                 .call()
                 .await
-                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
-
-            let balance_u128 = balance.try_into().map_err(|_| {
-                pyo3::exceptions::PyOverflowError::new_err("Balance too large for u128")
-            })?;
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
 
-            Ok(balance_u128)
+            Ok(balance.into())
         })
     }
 
-    pub fn get_approved(&self, token_id: u64) -> PyResult<String> {
-        self.runtime.block_on(async {
+    pub fn get_approved(&self, token_id: PyU256) -> PyResult<String> {
+        block_on(async {
             let approved = self
                 .inner
-                .getApproved(U256::from(token_id))
+                .getApproved(token_id.into())
                 .call()
                 .await
-                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
 
             Ok(format!("{:?}", approved))
         })
     }
 
     pub fn is_approved_for_all(&self, account: String, operator: String) -> PyResult<bool> {
-        let account_addr = account
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-        let operator_addr = operator
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-
-        self.runtime.block_on(async {
+        let account_addr = parse_address(&account)?;
+        let operator_addr = parse_address(&operator)?;
+
+        block_on(async {
             let approved = self
                 .inner
                 .isApprovedForAll(account_addr, operator_addr)
                 .call()
                 .await
-                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
 
             Ok(approved)
         })
@@ -269,24 +237,17 @@ impl PyMockERC721 {
 #[pyclass]
 pub struct PyMockERC1155 {
     inner: MockERC1155::MockERC1155Instance<WalletProvider>,
-    runtime: std::sync::Arc<tokio::runtime::Runtime>,
 }
 
 #[pymethods]
 impl PyMockERC1155 {
     #[new]
     pub fn new(address: String, provider: &PyWalletProvider) -> PyResult<Self> {
-        let addr = address
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        let addr = parse_address(&address)?;
 
         let contract = MockERC1155::MockERC1155Instance::new(addr, provider.inner.clone());
-        let runtime = std::sync::Arc::new(tokio::runtime::Runtime::new()?);
 
-        Ok(Self {
-            inner: contract,
-            runtime,
-        })
+        Ok(Self { inner: contract })
     }
 
     #[getter]
@@ -294,27 +255,31 @@ impl PyMockERC1155 {
         format!("{:?}", self.inner.address())
     }
 
-    pub fn mint(&self, to: String, token_id: u64, amount: u64) -> PyResult<()> {
-        let to_addr = to
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-        self.runtime.block_on(async {
-            self.inner
-                .mint(to_addr, U256::from(token_id), U256::from(amount))
+    pub fn mint(
+        &self,
+        to: String,
+        token_id: PyU256,
+        amount: PyU256,
+    ) -> PyResult<PyTransactionReceipt> {
+        let to_addr = parse_address(&to)?;
+        block_on(async {
+            let receipt = self
+                .inner
+                .mint(to_addr, token_id.into(), amount.into())
                 .send()
                 .await
                 .map_err(|e| {
                     eprintln!("Mint failed: {e}");
-                    pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+                    map_eyre_to_pyerr(e.into())
                 })?
                 .get_receipt()
                 .await
                 .map_err(|e| {
                     eprintln!("Get receipt failed: {e}");
-                    pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+                    map_eyre_to_pyerr(e.into())
                 })?;
 
-            Ok(())
+            Ok(receipt.into())
         })
     }
 
@@ -322,35 +287,32 @@ impl PyMockERC1155 {
         &self,
         from: String,
         to: String,
-        token_ids: Vec<u64>,
-        amounts: Vec<u64>,
+        token_ids: Vec<PyU256>,
+        amounts: Vec<PyU256>,
         data: Vec<u8>,
-    ) -> PyResult<()> {
-        let from_addr = from
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-        let to_addr = to
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+    ) -> PyResult<PyTransactionReceipt> {
+        let from_addr = parse_address(&from)?;
+        let to_addr = parse_address(&to)?;
         let ids: Vec<U256> = token_ids.into_iter().map(U256::from).collect();
         let amts: Vec<U256> = amounts.into_iter().map(U256::from).collect();
-        self.runtime.block_on(async {
-            self.inner
+        block_on(async {
+            let receipt = self
+                .inner
                 .safeBatchTransferFrom(from_addr, to_addr, ids, amts, data.into())
                 .send()
                 .await
                 .map_err(|e| {
                     eprintln!("Safe batch transfer failed: {e}");
-                    pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+                    map_eyre_to_pyerr(e.into())
                 })?
                 .get_receipt()
                 .await
                 .map_err(|e| {
                     eprintln!("Get receipt failed: {e}");
-                    pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+                    map_eyre_to_pyerr(e.into())
                 })?;
 
-            Ok(())
+            Ok(receipt.into())
         })
     }
 
@@ -358,68 +320,59 @@ impl PyMockERC1155 {
         &self,
         from: String,
         to: String,
-        token_id: u64,
-        amount: u64,
+        token_id: PyU256,
+        amount: PyU256,
         data: Vec<u8>,
-    ) -> PyResult<()> {
-        let from_addr = from
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-        let to_addr = to
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-        self.runtime.block_on(async {
-            self.inner
+    ) -> PyResult<PyTransactionReceipt> {
+        let from_addr = parse_address(&from)?;
+        let to_addr = parse_address(&to)?;
+        block_on(async {
+            let receipt = self
+                .inner
                 .safeTransferFrom(
                     from_addr,
                     to_addr,
-                    U256::from(token_id),
-                    U256::from(amount),
+                    token_id.into(),
+                    amount.into(),
                     data.into(),
                 )
                 .send()
                 .await
                 .map_err(|e| {
                     eprintln!("Safe transfer failed: {e}");
-                    pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+                    map_eyre_to_pyerr(e.into())
                 })?
                 .get_receipt()
                 .await
                 .map_err(|e| {
                     eprintln!("Get receipt failed: {e}");
-                    pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+                    map_eyre_to_pyerr(e.into())
                 })?;
 
-            Ok(())
+            Ok(receipt.into())
         })
     }
 
-    pub fn balance_of(&self, account: String, token_id: u64) -> PyResult<u128> {
-        let account_addr = account
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+    pub fn balance_of(&self, account: String, token_id: PyU256) -> PyResult<PyU256> {
+        let account_addr = parse_address(&account)?;
 
-        self.runtime.block_on(async {
+        block_on(async {
             let balance = self
                 .inner
-                .balanceOf(account_addr, U256::from(token_id))
+                .balanceOf(account_addr, token_id.into())
                 .call()
                 .await
-                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
-
-            let balance_u128 = balance.try_into().map_err(|_| {
-                pyo3::exceptions::PyOverflowError::new_err("Balance too large for u128")
-            })?;
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
 
-            Ok(balance_u128)
+            Ok(balance.into())
         })
     }
 
     pub fn balance_of_batch(
         &self,
         accounts: Vec<String>,
-        token_ids: Vec<u64>,
-    ) -> PyResult<Vec<u128>> {
+        token_ids: Vec<PyU256>,
+    ) -> PyResult<Vec<PyU256>> {
         if accounts.len() != token_ids.len() {
             return Err(pyo3::exceptions::PyValueError::new_err(
                 "Accounts and token_ids arrays must have the same length",
@@ -428,71 +381,61 @@ impl PyMockERC1155 {
 
         let account_addrs: Result<Vec<Address>, _> = accounts
             .into_iter()
-            .map(|addr| addr.parse::<Address>())
+            .map(|addr| parse_address(&addr))
             .collect();
-        let account_addrs =
-            account_addrs.map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        let account_addrs = account_addrs?;
         let ids: Vec<U256> = token_ids.into_iter().map(U256::from).collect();
 
-        self.runtime.block_on(async {
+        block_on(async {
             let balances = self
                 .inner
                 .balanceOfBatch(account_addrs, ids)
                 .call()
                 .await
-                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
-
-            let balances_u128: Result<Vec<u128>, _> = balances
-                .into_iter()
-                .map(|balance| balance.try_into())
-                .collect();
-            let balances_u128 = balances_u128.map_err(|_| {
-                pyo3::exceptions::PyOverflowError::new_err("Balance too large for u128")
-            })?;
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
 
-            Ok(balances_u128)
+            Ok(balances.into_iter().map(PyU256::from).collect())
         })
     }
 
-    pub fn set_approval_for_all(&self, operator: String, approved: bool) -> PyResult<()> {
-        let operator_addr = operator
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-        self.runtime.block_on(async {
-            self.inner
+    pub fn set_approval_for_all(
+        &self,
+        operator: String,
+        approved: bool,
+    ) -> PyResult<PyTransactionReceipt> {
+        let operator_addr = parse_address(&operator)?;
+        block_on(async {
+            let receipt = self
+                .inner
                 .setApprovalForAll(operator_addr, approved)
                 .send()
                 .await
                 .map_err(|e| {
                     eprintln!("Set approval for all failed: {e}");
-                    pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+                    map_eyre_to_pyerr(e.into())
                 })?
                 .get_receipt()
                 .await
                 .map_err(|e| {
                     eprintln!("Get receipt failed: {e}");
-                    pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+                    map_eyre_to_pyerr(e.into())
                 })?;
 
-            Ok(())
+            Ok(receipt.into())
         })
     }
 
     pub fn is_approved_for_all(&self, account: String, operator: String) -> PyResult<bool> {
-        let account_addr = account
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-        let operator_addr = operator
-            .parse::<Address>()
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
-
-        self.runtime.block_on(async {
+        let account_addr = parse_address(&account)?;
+        let operator_addr = parse_address(&operator)?;
+
+        block_on(async {
             let approved = self
                 .inner
                 .isApprovedForAll(account_addr, operator_addr)
                 .call()
                 .await
-                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
 
             Ok(approved)
         })
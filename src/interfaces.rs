@@ -0,0 +1,47 @@
+// Minimal interfaces for the calls the extension clients send directly.
+
+use alloy::sol;
+
+sol! {
+    #[sol(rpc)]
+    interface IERC20 {
+        function approve(address spender, uint256 value) external returns (bool);
+        function allowance(address owner, address spender) external view returns (uint256);
+    }
+
+    #[sol(rpc)]
+    interface IERC20Permit {
+        function nonces(address owner) external view returns (uint256);
+        function DOMAIN_SEPARATOR() external view returns (bytes32);
+        function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external;
+    }
+
+    #[sol(rpc)]
+    interface IERC721 {
+        function approve(address to, uint256 tokenId) external;
+        function setApprovalForAll(address operator, bool approved) external;
+    }
+
+    #[sol(rpc)]
+    interface IERC1155 {
+        function setApprovalForAll(address operator, bool approved) external;
+    }
+
+    #[sol(rpc)]
+    interface ISchemaRegistry {
+        function register(string schema, address resolver, bool revocable) external returns (bytes32);
+    }
+
+    #[sol(rpc)]
+    interface ITrustedOracleArbiter {
+        event ArbitrationMade(bytes32 indexed obligation, address indexed oracle, bool decision);
+        event ArbitrationRequested(bytes32 indexed obligation, address indexed oracle);
+        function arbitrate(bytes32 obligation, bool decision) external;
+    }
+
+    // The arbiter and demand every escrow obligation's data starts with
+    struct EscrowArbiter {
+        address arbiter;
+        bytes demand;
+    }
+}
@@ -1,23 +1,11 @@
-use std::str::FromStr;
-
 use alkahest_rs::{
-    clients::{
-        attestation::AttestationAddresses, erc1155::Erc1155Addresses, erc20::Erc20Addresses,
-        erc721::Erc721Addresses, oracle::OracleAddresses,
-        string_obligation::StringObligationAddresses, token_bundle::TokenBundleAddresses,
-    },
     contracts::IEAS::Attested,
-    extensions::{
-        AlkahestExtension, AttestationModule, Erc1155Module, Erc20Module, Erc721Module,
-        HasAttestation, HasErc1155, HasErc20, HasErc721, HasOracle, HasStringObligation,
-        HasTokenBundle, NoExtension, OracleModule, StringObligationModule, TokenBundleModule,
-    },
+    extensions::{AlkahestExtension, NoExtension},
     AlkahestClient,
 };
 use alloy::{
     primitives::{Address, FixedBytes, Log},
     rpc::types::TransactionReceipt,
-    signers::local::PrivateKeySigner,
     sol_types::SolEvent,
 };
 use clients::{
@@ -26,185 +14,268 @@ use clients::{
     token_bundle::TokenBundleClient,
 };
 use pyo3::{
+    exceptions::PyValueError,
     pyclass, pymethods, pymodule,
-    types::{PyAnyMethods, PyModule, PyModuleMethods},
-    Bound, FromPyObject, PyAny, PyResult, Python,
+    types::{PyAnyMethods, PyModule, PyModuleMethods, PyTuple},
+    Bound, FromPyObject, PyAny, PyRef, PyResult, Python,
 };
-use tokio::runtime::Runtime;
 use types::{DefaultExtensionConfig, EscowClaimedLog};
 
 use crate::{
     clients::{
         erc1155::{PyERC1155EscrowObligationData, PyERC1155PaymentObligationData},
-        erc20::{PyERC20EscrowObligationData, PyERC20PaymentObligationData},
+        erc20::{PyERC20EscrowObligationData, PyERC20PaymentObligationData, PyPermitSignature},
         erc721::{PyERC721EscrowObligationData, PyERC721PaymentObligationData},
         oracle::{
             PyArbitrateOptions, PyArbitrationResult, PyAttestationFilter, PyDecision,
-            PyEscrowArbitrationResult, PyEscrowParams, PyFulfillmentParams, PyOracleAddresses,
-            PyOracleAttestation, PySubscriptionResult, PyTrustedOracleArbiterDemandData,
+            PyDecisionStream, PyEscrowArbitrationResult, PyEscrowParams,
+            PyEscrowSubscriptionResult, PyFulfillmentParams, PyOracleAddresses,
+            PyOracleAttestation, PySubscriptionResult, PyTrustedOracleArbiterDemandData, PyVerdict,
         },
         string_obligation::PyStringObligationData,
+        token_bundle::PyTokenBundlePaymentObligationData,
     },
     contract::{
         PyAttestation, PyAttestationRequest, PyAttestationRequestData, PyAttested,
         PyRevocationRequest, PyRevocationRequestData, PyRevoked, PyTimestamped,
     },
+    error_handling::{
+        extension_not_available, map_eyre_to_pyerr, map_eyre_with_context, map_parse_to_pyerr,
+        parse_address,
+    },
+    extensions::{ExtensionAddresses, ExtensionHandles, ExtensionKind, IntoExtensionHandles},
     fixtures::{PyMockERC1155, PyMockERC20, PyMockERC721},
+    pending::PyPendingTransaction,
+    receipt::PyTransactionReceipt,
+    runtime::block_on,
+    signer::{AlkahestSigner, PySigner},
+    simulate::PySimulationResult,
+    sync::{PySyncClient, PySyncMethod},
+    transport::{PyRetryConfig, RetryConfig, RpcEndpoints},
+    tx::{PyTxOptions, TxSender},
     types::PyErc20Data,
+    unsigned::{PyUnsignedTransaction, RawTx},
     utils::{EnvTestManager, PyWalletProvider},
 };
 
+pub mod arbitration;
+pub mod checkpoint;
 pub mod clients;
 pub mod contract;
 pub mod error_handling;
+pub mod extensions;
 pub mod fixtures;
+pub mod interfaces;
+pub mod nonce;
+pub mod pending;
+pub mod receipt;
+pub mod reverts;
+pub mod runtime;
+pub mod signer;
+pub mod simulate;
+pub mod sync;
+pub mod transport;
+pub mod tx;
 pub mod types;
+pub mod unsigned;
 pub mod utils;
 
 #[pyclass]
 #[derive(Clone)]
 pub struct PyAlkahestClient {
     inner: std::sync::Arc<dyn std::any::Any + Send + Sync>,
-    // Store connection info to create new extension clients
-    private_key: Option<String>,
-    rpc_url: Option<String>,
-    erc20: Option<Erc20Client>,
-    erc721: Option<Erc721Client>,
-    erc1155: Option<Erc1155Client>,
-    token_bundle: Option<TokenBundleClient>,
-    attestation: Option<AttestationClient>,
-    string_obligation: Option<StringObligationClient>,
-    oracle: Option<OracleClient>,
+    // Signer and provider shared by every extension, including ones added by with_*
+    tx: TxSender,
+    extensions: ExtensionHandles,
 }
 
 impl PyAlkahestClient {
-    pub fn from_client(client: AlkahestClient) -> Self {
+    pub fn from_client(client: AlkahestClient, tx: TxSender) -> Self {
         Self {
-            inner: std::sync::Arc::new(client.clone()),
-            private_key: None, // Not available when creating from existing client
-            rpc_url: None,     // Not available when creating from existing client
-            erc20: Some(Erc20Client::new(client.extensions.erc20().clone())),
-            erc721: Some(Erc721Client::new(client.extensions.erc721().clone())),
-            erc1155: Some(Erc1155Client::new(client.extensions.erc1155().clone())),
-            token_bundle: Some(TokenBundleClient::new(
-                client.extensions.token_bundle().clone(),
-            )),
-            attestation: Some(AttestationClient::new(
-                client.extensions.attestation().clone(),
-            )),
-            string_obligation: Some(StringObligationClient::new(
-                client.extensions.string_obligation().clone(),
-            )),
-            oracle: Some(OracleClient::new(client.extensions.oracle().clone())),
+            extensions: ExtensionHandles::from_default(&client),
+            inner: std::sync::Arc::new(client),
+            tx,
         }
     }
 
-    /// Create a PyAlkahestClient from a client with a single extension
+    /// Create a PyAlkahestClient from a client built with a single extension module
     pub fn from_client_with_single_extension<T>(
         client: alkahest_rs::AlkahestClient<T>,
-        extension_type: &str,
+        tx: TxSender,
     ) -> Self
     where
-        T: AlkahestExtension + Clone + Send + Sync + 'static,
+        T: AlkahestExtension + IntoExtensionHandles + Clone + Send + Sync + 'static,
     {
-        // For now, we'll leave all extensions as None since extracting the specific
-        // extension from the generic client type is complex. In a full implementation,
-        // we would need to match on the extension type and extract the appropriate
-        // extension to create the wrapper client.
-
-        // The client still has the extension functionality in the inner client,
-        // but the Python wrapper doesn't expose it through the .erc20, .erc721, etc. properties
         Self {
+            extensions: client.extensions.extension_handles(),
             inner: std::sync::Arc::new(client),
-            private_key: None, // Connection info not available when creating from existing client
-            rpc_url: None,     // Connection info not available when creating from existing client
-            erc20: None,       // TODO: Extract if extension_type == "erc20"
-            erc721: None,      // TODO: Extract if extension_type == "erc721"
-            erc1155: None,     // TODO: Extract if extension_type == "erc1155"
-            token_bundle: None, // TODO: Extract if extension_type == "token_bundle"
-            attestation: None, // TODO: Extract if extension_type == "attestation"
-            string_obligation: None, // TODO: Extract if extension_type == "string_obligation"
-            oracle: None,      // TODO: Extract if extension_type == "oracle"
+            tx,
         }
     }
+
+    fn ensure_open(&self) -> PyResult<()> {
+        self.tx.ensure_open().map_err(map_eyre_to_pyerr)
+    }
+
+    /// Handles this client can hand out without connecting: its own, plus any
+    /// the inner client was built with.
+    fn shareable_extensions(&self) -> ExtensionHandles {
+        let mut handles = match self.inner.downcast_ref::<AlkahestClient>() {
+            Some(client) => ExtensionHandles::from_default(client),
+            None => ExtensionHandles::default(),
+        };
+        handles.merge(&self.extensions);
+        handles
+    }
+
+    /// Returns a copy of this client with the requested extensions wired up.
+    /// Existing handles are reused and re-pointed at new addresses where
+    /// given; only extensions nothing can share are initialized.
+    fn extend<'py>(
+        &self,
+        py: Python<'py>,
+        requests: Vec<(ExtensionKind, Option<ExtensionAddresses>)>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        self.ensure_open()?;
+        let inner = self.inner.clone();
+        let tx = self.tx.clone();
+        let mut extensions = self.extensions.clone();
+        let shareable = self.shareable_extensions();
+
+        crate::runtime::future_into_py(py, async move {
+            for (kind, addresses) in requests {
+                if shareable.has(kind) {
+                    extensions.take_from(&shareable, kind);
+                    if let Some(addresses) = addresses {
+                        extensions.set_addresses(addresses);
+                    }
+                } else {
+                    let created =
+                        ExtensionHandles::init(kind, &tx, addresses)
+                            .await
+                            .map_err(|e| {
+                                map_eyre_with_context(
+                                    &format!("Failed to create {} extension", kind.name()),
+                                    e,
+                                )
+                            })?;
+                    extensions.merge(&created);
+                }
+            }
+
+            Ok(Self {
+                inner,
+                tx,
+                extensions,
+            })
+        })
+    }
 }
 
 #[pymethods]
 impl PyAlkahestClient {
+    /// `rpc_url` is one endpoint or a list of them to fail over between, in
+    /// order of preference. `retry` sets how failed requests are retried.
     #[new]
-    #[pyo3(signature = (private_key, rpc_url, address_config=None))]
+    #[pyo3(signature = (private_key, rpc_url, address_config=None, retry=None))]
     pub fn __new__(
-        private_key: String,
-        rpc_url: String,
+        private_key: AlkahestSigner,
+        rpc_url: RpcEndpoints,
         address_config: Option<DefaultExtensionConfig>,
+        retry: Option<RetryConfig>,
     ) -> PyResult<Self> {
         let address_config = address_config.map(|x| x.try_into()).transpose()?;
-
-        // Convert private_key String to LocalSigner
-        let signer = PrivateKeySigner::from_str(&private_key)
-            .map_err(|e| eyre::eyre!("Failed to parse private key: {}", e))?;
-
-        // Create a shared runtime
-        let runtime = std::sync::Arc::new(Runtime::new()?);
+        let signer = private_key;
 
         // Since new is async, we must block_on it
-        let client: alkahest_rs::AlkahestClient = runtime.clone().block_on(async {
-            alkahest_rs::AlkahestClient::new(signer.clone(), rpc_url.clone(), address_config).await
+        let (client, tx) = block_on(async {
+            let tx = TxSender::connect(signer, &rpc_url, &retry.unwrap_or_default()).await?;
+            let client: alkahest_rs::AlkahestClient = alkahest_rs::AlkahestClient::from_providers(
+                tx.alkahest_providers(),
+                address_config,
+            )
+            .await?;
+            eyre::Ok((client, tx))
         })?;
 
-        let client = Self {
-            inner: std::sync::Arc::new(client.clone()),
-            private_key: Some(private_key.clone()),
-            rpc_url: Some(rpc_url.clone()),
-            erc20: Some(Erc20Client::new(client.extensions.erc20().clone())),
-            erc721: Some(Erc721Client::new(client.extensions.erc721().clone())),
-            erc1155: Some(Erc1155Client::new(client.extensions.erc1155().clone())),
-            token_bundle: Some(TokenBundleClient::new(
-                client.extensions.token_bundle().clone(),
-            )),
-            attestation: Some(AttestationClient::new(
-                client.extensions.attestation().clone(),
-            )),
-            string_obligation: Some(StringObligationClient::new(
-                client.extensions.string_obligation().clone(),
-            )),
-            oracle: Some(OracleClient::new(client.extensions.oracle().clone())),
-        };
+        Ok(Self::from_client(client, tx))
+    }
+
+    /// Create a client that can query and decode but never sends transactions.
+    ///
+    /// Every extension is available; methods that would send a transaction
+    /// raise an error saying the client is read-only.
+    #[staticmethod]
+    #[pyo3(signature = (rpc_url, address_config=None, retry=None))]
+    pub fn read_only(
+        rpc_url: RpcEndpoints,
+        address_config: Option<DefaultExtensionConfig>,
+        retry: Option<RetryConfig>,
+    ) -> PyResult<Self> {
+        let address_config = address_config.map(|x| x.try_into()).transpose()?;
+
+        let (client, tx) = block_on(async {
+            let tx = TxSender::connect_read_only(&rpc_url, &retry.unwrap_or_default()).await?;
+            let client: alkahest_rs::AlkahestClient = alkahest_rs::AlkahestClient::from_providers(
+                tx.alkahest_providers(),
+                address_config,
+            )
+            .await?;
+            eyre::Ok((client, tx))
+        })?;
 
-        Ok(client)
+        Ok(Self::from_client(client, tx))
     }
 
     /// Create a PyAlkahestClient with no extensions
     #[staticmethod]
-    #[pyo3(signature = (private_key, rpc_url))]
-    pub fn with_no_extensions(private_key: String, rpc_url: String) -> PyResult<Self> {
-        // Convert private_key String to LocalSigner
-        let signer = PrivateKeySigner::from_str(&private_key)
-            .map_err(|e| eyre::eyre!("Failed to parse private key: {}", e))?;
-
-        // Create a shared runtime
-        let runtime = std::sync::Arc::new(Runtime::new()?);
+    #[pyo3(signature = (private_key, rpc_url, retry=None))]
+    pub fn with_no_extensions(
+        private_key: AlkahestSigner,
+        rpc_url: RpcEndpoints,
+        retry: Option<RetryConfig>,
+    ) -> PyResult<Self> {
+        let signer = private_key;
 
         // Create client with NoExtension
-        let client = runtime.clone().block_on(async {
-            alkahest_rs::AlkahestClient::<NoExtension>::new(signer.clone(), rpc_url.clone(), None)
-                .await
+        let (client, tx) = block_on(async {
+            let tx = TxSender::connect(signer, &rpc_url, &retry.unwrap_or_default()).await?;
+            let client = alkahest_rs::AlkahestClient::<NoExtension>::from_providers(
+                tx.alkahest_providers(),
+                None,
+            )
+            .await?;
+            eyre::Ok((client, tx))
         })?;
 
-        let py_client = Self {
-            inner: std::sync::Arc::new(client),
-            private_key: Some(private_key.clone()),
-            rpc_url: Some(rpc_url.clone()),
-            erc20: None,
-            erc721: None,
-            erc1155: None,
-            token_bundle: None,
-            attestation: None,
-            string_obligation: None,
-            oracle: None,
-        };
+        Ok(Self::from_client_with_single_extension(client, tx))
+    }
 
-        Ok(py_client)
+    /// Add several extensions at once and return a new client instance with them.
+    ///
+    /// All extensions share this client's signer, provider and nonces.
+    /// `config` supplies addresses for any of them, as in the constructor.
+    #[pyo3(signature = (extensions, config=None))]
+    pub fn with_extensions<'py>(
+        &self,
+        py: Python<'py>,
+        extensions: Vec<String>,
+        config: Option<DefaultExtensionConfig>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let config: Option<alkahest_rs::DefaultExtensionConfig> =
+            config.map(|x| x.try_into()).transpose()?;
+        let requests = extensions
+            .iter()
+            .map(|name| {
+                let kind = ExtensionKind::parse(name)?;
+                let addresses = config
+                    .as_ref()
+                    .and_then(|config| ExtensionAddresses::from_config(config, kind));
+                Ok((kind, addresses))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+
+        self.extend(py, requests)
     }
 
     /// Add ERC20 extension to the client and return a new client instance with that extension
@@ -214,76 +285,11 @@ impl PyAlkahestClient {
         py: Python<'py>,
         config: Option<crate::types::Erc20Addresses>,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
-        let inner = self.inner.clone();
-        let private_key = self.private_key.clone();
-        let rpc_url = self.rpc_url.clone();
-        let erc721 = self.erc721.clone();
-        let erc1155 = self.erc1155.clone();
-        let token_bundle = self.token_bundle.clone();
-        let attestation = self.attestation.clone();
-        let string_obligation = self.string_obligation.clone();
-        let oracle = self.oracle.clone();
-        let existing_erc20 = self.erc20.clone();
-
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            // If we already have ERC20 extension, just return a copy with the same extension
-            if let Some(erc20_client) = existing_erc20 {
-                let new_client = Self {
-                    inner,
-                    private_key,
-                    rpc_url,
-                    erc20: Some(erc20_client),
-                    erc721,
-                    erc1155,
-                    token_bundle,
-                    attestation,
-                    string_obligation,
-                    oracle,
-                };
-                return Ok(new_client);
-            }
-
-            // Create ERC20 extension using stored connection info
-            if let (Some(pk), Some(url)) = (private_key, rpc_url) {
-                // Create independent ERC20 client using init_with_config
-                let signer = PrivateKeySigner::from_str(&pk).map_err(|e| {
-                    pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Failed to parse private key: {}",
-                        e
-                    ))
-                })?;
-
-                let addresses: Option<Erc20Addresses> =
-                    config.and_then(|addr| addr.try_into().ok());
-                let erc20_extension = Erc20Module::init_with_config(signer, url.clone(), addresses)
-                    .await
-                    .map_err(|e| {
-                        pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                            "Failed to create ERC20 extension: {}",
-                            e
-                        ))
-                    })?;
-
-                let new_client = Self {
-                    inner,
-                    private_key: Some(pk),
-                    rpc_url: Some(url),
-                    erc20: Some(Erc20Client::new(erc20_extension.client)),
-                    erc721,
-                    erc1155,
-                    token_bundle,
-                    attestation,
-                    string_obligation,
-                    oracle,
-                };
-                return Ok(new_client);
-            }
-
-            // If no connection info available, return error
-            Err(pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                "Cannot add ERC20 extension: no connection information available. Use AlkahestClient.with_no_extensions() to create a client with stored connection info.",
-            ))
-        })
+        let addresses = config
+            .map(|x| x.try_into())
+            .transpose()?
+            .map(ExtensionAddresses::Erc20);
+        self.extend(py, vec![(ExtensionKind::Erc20, addresses)])
     }
 
     /// Add ERC721 extension to the client and return a new client instance with that extension
@@ -293,59 +299,11 @@ impl PyAlkahestClient {
         py: Python<'py>,
         config: Option<crate::types::Erc721Addresses>,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
-        let inner = self.inner.clone();
-        let private_key = self.private_key.clone();
-        let rpc_url = self.rpc_url.clone();
-        let erc20 = self.erc20.clone();
-        let erc1155 = self.erc1155.clone();
-        let token_bundle = self.token_bundle.clone();
-        let attestation = self.attestation.clone();
-        let string_obligation = self.string_obligation.clone();
-        let oracle = self.oracle.clone();
-
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            // Create ERC721 extension using stored connection info
-            if let (Some(pk), Some(url)) = (private_key, rpc_url) {
-                // Create independent ERC721 client using init_with_config
-                let signer = PrivateKeySigner::from_str(&pk).map_err(|e| {
-                    pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Failed to parse private key: {}",
-                        e
-                    ))
-                })?;
-
-                let addresses: Option<Erc721Addresses> =
-                    config.and_then(|addr| addr.try_into().ok());
-                let erc721_extension =
-                    Erc721Module::init_with_config(signer, url.clone(), addresses)
-                        .await
-                        .map_err(|e| {
-                            pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                                "Failed to create ERC721 extension: {}",
-                                e
-                            ))
-                        })?;
-
-                let new_client = Self {
-                    inner,
-                    private_key: Some(pk),
-                    rpc_url: Some(url),
-                    erc20,
-                    erc721: Some(Erc721Client::new(erc721_extension.client)),
-                    erc1155,
-                    token_bundle,
-                    attestation,
-                    string_obligation,
-                    oracle,
-                };
-                return Ok(new_client);
-            }
-
-            // If no connection info available, return error
-            Err(pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                "Cannot add ERC721 extension: no connection information available. Use AlkahestClient.with_no_extensions() to create a client with stored connection info.",
-            ))
-        })
+        let addresses = config
+            .map(|x| x.try_into())
+            .transpose()?
+            .map(ExtensionAddresses::Erc721);
+        self.extend(py, vec![(ExtensionKind::Erc721, addresses)])
     }
 
     /// Add ERC1155 extension to the client and return a new client instance with that extension
@@ -355,59 +313,11 @@ impl PyAlkahestClient {
         py: Python<'py>,
         config: Option<crate::types::Erc1155Addresses>,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
-        let inner = self.inner.clone();
-        let private_key = self.private_key.clone();
-        let rpc_url = self.rpc_url.clone();
-        let erc20 = self.erc20.clone();
-        let erc721 = self.erc721.clone();
-        let token_bundle = self.token_bundle.clone();
-        let attestation = self.attestation.clone();
-        let string_obligation = self.string_obligation.clone();
-        let oracle = self.oracle.clone();
-
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            // Create ERC1155 extension using stored connection info
-            if let (Some(pk), Some(url)) = (private_key, rpc_url) {
-                // Create independent ERC1155 client using init_with_config
-                let signer = PrivateKeySigner::from_str(&pk).map_err(|e| {
-                    pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Failed to parse private key: {}",
-                        e
-                    ))
-                })?;
-
-                let addresses: Option<Erc1155Addresses> =
-                    config.and_then(|addr| addr.try_into().ok());
-                let erc1155_extension =
-                    Erc1155Module::init_with_config(signer, url.clone(), addresses)
-                        .await
-                        .map_err(|e| {
-                            pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                                "Failed to create ERC1155 extension: {}",
-                                e
-                            ))
-                        })?;
-
-                let new_client = Self {
-                    inner,
-                    private_key: Some(pk),
-                    rpc_url: Some(url),
-                    erc20,
-                    erc721,
-                    erc1155: Some(Erc1155Client::new(erc1155_extension.client)),
-                    token_bundle,
-                    attestation,
-                    string_obligation,
-                    oracle,
-                };
-                return Ok(new_client);
-            }
-
-            // If no connection info available, return error
-            Err(pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                "Cannot add ERC1155 extension: no connection information available. Use AlkahestClient.with_no_extensions() to create a client with stored connection info.",
-            ))
-        })
+        let addresses = config
+            .map(|x| x.try_into())
+            .transpose()?
+            .map(ExtensionAddresses::Erc1155);
+        self.extend(py, vec![(ExtensionKind::Erc1155, addresses)])
     }
 
     /// Add TokenBundle extension to the client and return a new client instance with that extension
@@ -417,59 +327,11 @@ impl PyAlkahestClient {
         py: Python<'py>,
         config: Option<crate::types::TokenBundleAddresses>,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
-        let inner = self.inner.clone();
-        let private_key = self.private_key.clone();
-        let rpc_url = self.rpc_url.clone();
-        let erc20 = self.erc20.clone();
-        let erc721 = self.erc721.clone();
-        let erc1155 = self.erc1155.clone();
-        let attestation = self.attestation.clone();
-        let string_obligation = self.string_obligation.clone();
-        let oracle = self.oracle.clone();
-
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            // Create TokenBundle extension using stored connection info
-            if let (Some(pk), Some(url)) = (private_key, rpc_url) {
-                // Create independent TokenBundle client using init_with_config
-                let signer = PrivateKeySigner::from_str(&pk).map_err(|e| {
-                    pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Failed to parse private key: {}",
-                        e
-                    ))
-                })?;
-
-                let addresses: Option<TokenBundleAddresses> =
-                    config.and_then(|addr| addr.try_into().ok());
-                let token_bundle_extension =
-                    TokenBundleModule::init_with_config(signer, url.clone(), addresses)
-                        .await
-                        .map_err(|e| {
-                            pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                                "Failed to create TokenBundle extension: {}",
-                                e
-                            ))
-                        })?;
-
-                let new_client = Self {
-                    inner,
-                    private_key: Some(pk),
-                    rpc_url: Some(url),
-                    erc20,
-                    erc721,
-                    erc1155,
-                    token_bundle: Some(TokenBundleClient::new(token_bundle_extension.client)),
-                    attestation,
-                    string_obligation,
-                    oracle,
-                };
-                return Ok(new_client);
-            }
-
-            // If no connection info available, return error
-            Err(pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                "Cannot add TokenBundle extension: no connection information available. Use AlkahestClient.with_no_extensions() to create a client with stored connection info.",
-            ))
-        })
+        let addresses = config
+            .map(|x| x.try_into())
+            .transpose()?
+            .map(ExtensionAddresses::TokenBundle);
+        self.extend(py, vec![(ExtensionKind::TokenBundle, addresses)])
     }
 
     /// Add Attestation extension to the client and return a new client instance with that extension
@@ -479,59 +341,11 @@ impl PyAlkahestClient {
         py: Python<'py>,
         config: Option<crate::types::AttestationAddresses>,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
-        let inner = self.inner.clone();
-        let private_key = self.private_key.clone();
-        let rpc_url = self.rpc_url.clone();
-        let erc20 = self.erc20.clone();
-        let erc721 = self.erc721.clone();
-        let erc1155 = self.erc1155.clone();
-        let token_bundle = self.token_bundle.clone();
-        let string_obligation = self.string_obligation.clone();
-        let oracle = self.oracle.clone();
-
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            // Create Attestation extension using stored connection info
-            if let (Some(pk), Some(url)) = (private_key, rpc_url) {
-                // Create independent Attestation client using init_with_config
-                let signer = PrivateKeySigner::from_str(&pk).map_err(|e| {
-                    pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Failed to parse private key: {}",
-                        e
-                    ))
-                })?;
-
-                let addresses: Option<AttestationAddresses> =
-                    config.and_then(|addr| addr.try_into().ok());
-                let attestation_extension =
-                    AttestationModule::init_with_config(signer, url.clone(), addresses)
-                        .await
-                        .map_err(|e| {
-                            pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                                "Failed to create Attestation extension: {}",
-                                e
-                            ))
-                        })?;
-
-                let new_client = Self {
-                    inner,
-                    private_key: Some(pk),
-                    rpc_url: Some(url),
-                    erc20,
-                    erc721,
-                    erc1155,
-                    token_bundle,
-                    attestation: Some(AttestationClient::new(attestation_extension.client)),
-                    string_obligation,
-                    oracle,
-                };
-                return Ok(new_client);
-            }
-
-            // If no connection info available, return error
-            Err(pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                "Cannot add Attestation extension: no connection information available. Use AlkahestClient.with_no_extensions() to create a client with stored connection info.",
-            ))
-        })
+        let addresses = config
+            .map(|x| x.try_into())
+            .transpose()?
+            .map(ExtensionAddresses::Attestation);
+        self.extend(py, vec![(ExtensionKind::Attestation, addresses)])
     }
 
     /// Add StringObligation extension to the client and return a new client instance with that extension
@@ -541,61 +355,11 @@ impl PyAlkahestClient {
         py: Python<'py>,
         config: Option<crate::types::StringObligationAddresses>,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
-        let inner = self.inner.clone();
-        let private_key = self.private_key.clone();
-        let rpc_url = self.rpc_url.clone();
-        let erc20 = self.erc20.clone();
-        let erc721 = self.erc721.clone();
-        let erc1155 = self.erc1155.clone();
-        let token_bundle = self.token_bundle.clone();
-        let attestation = self.attestation.clone();
-        let oracle = self.oracle.clone();
-
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            // Create StringObligation extension using stored connection info
-            if let (Some(pk), Some(url)) = (private_key, rpc_url) {
-                // Create independent StringObligation client using init_with_config
-                let signer = PrivateKeySigner::from_str(&pk).map_err(|e| {
-                    pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Failed to parse private key: {}",
-                        e
-                    ))
-                })?;
-
-                let addresses: Option<StringObligationAddresses> =
-                    config.and_then(|addr| addr.try_into().ok());
-                let string_obligation_extension =
-                    StringObligationModule::init_with_config(signer, url.clone(), addresses)
-                        .await
-                        .map_err(|e| {
-                            pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                                "Failed to create StringObligation extension: {}",
-                                e
-                            ))
-                        })?;
-
-                let new_client = Self {
-                    inner,
-                    private_key: Some(pk),
-                    rpc_url: Some(url),
-                    erc20,
-                    erc721,
-                    erc1155,
-                    token_bundle,
-                    attestation,
-                    string_obligation: Some(StringObligationClient::new(
-                        string_obligation_extension.client,
-                    )),
-                    oracle,
-                };
-                return Ok(new_client);
-            }
-
-            // If no connection info available, return error
-            Err(pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                "Cannot add StringObligation extension: no connection information available. Use AlkahestClient.with_no_extensions() to create a client with stored connection info.",
-            ))
-        })
+        let addresses = config
+            .map(|x| x.try_into())
+            .transpose()?
+            .map(ExtensionAddresses::StringObligation);
+        self.extend(py, vec![(ExtensionKind::StringObligation, addresses)])
     }
 
     /// Add Oracle extension to the client and return a new client instance with that extension
@@ -605,149 +369,260 @@ impl PyAlkahestClient {
         py: Python<'py>,
         config: Option<crate::types::OracleAddresses>,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
-        let inner = self.inner.clone();
-        let private_key = self.private_key.clone();
-        let rpc_url = self.rpc_url.clone();
-        let erc20 = self.erc20.clone();
-        let erc721 = self.erc721.clone();
-        let erc1155 = self.erc1155.clone();
-        let token_bundle = self.token_bundle.clone();
-        let attestation = self.attestation.clone();
-        let string_obligation = self.string_obligation.clone();
-
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            // Create Oracle extension using stored connection info
-            if let (Some(pk), Some(url)) = (private_key, rpc_url) {
-                // Create independent Oracle client using init_with_config
-                let signer = PrivateKeySigner::from_str(&pk).map_err(|e| {
-                    pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                        "Failed to parse private key: {}",
-                        e
-                    ))
-                })?;
-
-                let addresses: Option<OracleAddresses> =
-                    config.and_then(|addr| addr.try_into().ok());
-                let oracle_extension =
-                    OracleModule::init_with_config(signer, url.clone(), addresses)
-                        .await
-                        .map_err(|e| {
-                            pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
-                                "Failed to create Oracle extension: {}",
-                                e
-                            ))
-                        })?;
-
-                let new_client = Self {
-                    inner,
-                    private_key: Some(pk),
-                    rpc_url: Some(url),
-                    erc20,
-                    erc721,
-                    erc1155,
-                    token_bundle,
-                    attestation,
-                    string_obligation,
-                    oracle: Some(OracleClient::new(oracle_extension.client)),
-                };
-                return Ok(new_client);
-            }
+        let addresses = config
+            .map(|x| x.try_into())
+            .transpose()?
+            .map(ExtensionAddresses::Oracle);
+        self.extend(py, vec![(ExtensionKind::Oracle, addresses)])
+    }
 
-            // If no connection info available, return error
-            Err(pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                "Cannot add Oracle extension: no connection information available. Use AlkahestClient.with_no_extensions() to create a client with stored connection info.",
-            ))
+    #[getter]
+    pub fn is_read_only(&self) -> bool {
+        self.tx.is_read_only()
+    }
+
+    /// Blocking versions of every method, e.g. `client.sync.erc20.approve(...)`.
+    /// They release the GIL while waiting and share this client's connection.
+    #[getter]
+    pub fn sync(slf: Bound<'_, Self>) -> PySyncClient {
+        PySyncClient::new(slf.into_any().unbind())
+    }
+
+    /// A view of this client whose sending methods don't send. Each one runs
+    /// the exact transactions it would have sent, in order, with
+    /// `eth_simulateV1` and returns a `SimulationResult` instead.
+    #[getter]
+    pub fn simulate(&self) -> Self {
+        Self {
+            tx: self.tx.simulating(),
+            ..self.clone()
+        }
+    }
+
+    #[getter]
+    pub fn is_simulating(&self) -> bool {
+        self.tx.is_simulating()
+    }
+
+    /// A view of this client whose sending methods return a
+    /// `PendingTransaction` as soon as the transaction is broadcast, instead
+    /// of waiting for it to be mined.
+    #[getter]
+    pub fn no_wait(&self) -> Self {
+        Self {
+            tx: self.tx.not_waiting(),
+            ..self.clone()
+        }
+    }
+
+    /// A view of this client whose sending methods return an
+    /// `UnsignedTransaction` from `from_address` instead of sending, for keys
+    /// held by a hardware signer or multisig. Defaults to the signer's address;
+    /// read-only clients have to pass one.
+    #[pyo3(signature = (from_address=None))]
+    pub fn unsigned(&self, from_address: Option<String>) -> PyResult<Self> {
+        let from = match from_address {
+            Some(address) => parse_address(&address)?,
+            None => self.tx.address().map_err(|_| {
+                PyValueError::new_err("from_address is required for a read-only client")
+            })?,
+        };
+        Ok(Self {
+            tx: self.tx.unsigned_for(from),
+            ..self.clone()
+        })
+    }
+
+    /// Broadcast a transaction signed outside the client, e.g. one built with
+    /// `client.unsigned`, and return its `TransactionReceipt` once mined.
+    pub fn broadcast_signed<'py>(
+        &self,
+        py: Python<'py>,
+        raw_tx: RawTx,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let tx = self.tx.clone();
+        crate::runtime::future_into_py(py, async move {
+            let receipt = tx
+                .broadcast_raw(&raw_tx.0)
+                .await
+                .map_err(map_eyre_to_pyerr)?;
+            Ok(PyTransactionReceipt::from(receipt))
         })
     }
 
-    /// List available extensions
+    /// Re-read the account's nonce from the node before the next send. Sends
+    /// from this client and its extension clients share one nonce sequence;
+    /// call this after sending from the same account somewhere else.
+    pub fn reset_nonce(&self) -> PyResult<()> {
+        self.ensure_open()?;
+        self.tx.reset_nonce();
+        Ok(())
+    }
+
+    /// Release the client's connections. Extension clients taken from it, and
+    /// clients made from it with `with_*`, can no longer send once it's closed.
+    /// Closing twice is a no-op.
+    pub fn close(&mut self) {
+        self.tx.close();
+        self.extensions = ExtensionHandles::default();
+        self.inner = std::sync::Arc::new(());
+    }
+
+    #[getter]
+    pub fn is_closed(&self) -> bool {
+        self.tx.ensure_open().is_err()
+    }
+
+    pub fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    #[pyo3(signature = (*_args))]
+    pub fn __exit__(&mut self, _args: &Bound<'_, PyTuple>) -> bool {
+        self.close();
+        false
+    }
+
+    pub fn __aenter__<'py>(slf: Bound<'py, Self>) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        let py = slf.py();
+        let client = slf.unbind();
+        crate::runtime::future_into_py(py, async move { Ok(client) })
+    }
+
+    #[pyo3(signature = (*_args))]
+    pub fn __aexit__<'py>(
+        &mut self,
+        py: Python<'py>,
+        _args: &Bound<'py, PyTuple>,
+    ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        self.close();
+        crate::runtime::future_into_py(py, async move { Ok(false) })
+    }
+
+    /// List the extensions wired into this client
     pub fn list_extensions(&self) -> Vec<String> {
-        vec![
-            "erc20".to_string(),
-            "erc721".to_string(),
-            "erc1155".to_string(),
-            "token_bundle".to_string(),
-            "attestation".to_string(),
-            "string_obligation".to_string(),
-            "oracle".to_string(),
-        ]
+        self.extensions
+            .kinds()
+            .into_iter()
+            .map(|kind| kind.name().to_string())
+            .collect()
     }
 
     /// Check if a specific extension is available
     pub fn has_extension(&self, extension_type: String) -> bool {
-        match extension_type.as_str() {
-            "erc20" => self.erc20.is_some(),
-            "erc721" => self.erc721.is_some(),
-            "erc1155" => self.erc1155.is_some(),
-            "token_bundle" => self.token_bundle.is_some(),
-            "attestation" => self.attestation.is_some(),
-            "string_obligation" => self.string_obligation.is_some(),
-            "oracle" => self.oracle.is_some(),
-            _ => false,
-        }
+        ExtensionKind::parse(&extension_type).is_ok_and(|kind| self.extensions.has(kind))
     }
 
     #[getter]
     pub fn erc20(&self) -> PyResult<Erc20Client> {
-        self.erc20.clone().ok_or_else(|| {
-            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
-                "ERC20 extension is not available in this client",
-            )
-        })
+        self.ensure_open()?;
+        let tx = self.tx.clone();
+        self.extensions
+            .erc20
+            .clone()
+            .map(|inner| Erc20Client::new(inner, tx))
+            .ok_or_else(|| {
+                extension_not_available(
+                    "erc20",
+                    "ERC20 extension is not available in this client".to_string(),
+                )
+            })
     }
 
     #[getter]
     pub fn erc721(&self) -> PyResult<Erc721Client> {
-        self.erc721.clone().ok_or_else(|| {
-            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
-                "ERC721 extension is not available in this client",
-            )
-        })
+        self.ensure_open()?;
+        let tx = self.tx.clone();
+        self.extensions
+            .erc721
+            .clone()
+            .map(|inner| Erc721Client::new(inner, tx))
+            .ok_or_else(|| {
+                extension_not_available(
+                    "erc721",
+                    "ERC721 extension is not available in this client".to_string(),
+                )
+            })
     }
 
     #[getter]
     pub fn erc1155(&self) -> PyResult<Erc1155Client> {
-        self.erc1155.clone().ok_or_else(|| {
-            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
-                "ERC1155 extension is not available in this client",
-            )
-        })
+        self.ensure_open()?;
+        let tx = self.tx.clone();
+        self.extensions
+            .erc1155
+            .clone()
+            .map(|inner| Erc1155Client::new(inner, tx))
+            .ok_or_else(|| {
+                extension_not_available(
+                    "erc1155",
+                    "ERC1155 extension is not available in this client".to_string(),
+                )
+            })
     }
 
     #[getter]
     pub fn token_bundle(&self) -> PyResult<TokenBundleClient> {
-        self.token_bundle.clone().ok_or_else(|| {
-            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
-                "TokenBundle extension is not available in this client",
-            )
-        })
+        self.ensure_open()?;
+        let tx = self.tx.clone();
+        self.extensions
+            .token_bundle
+            .clone()
+            .map(|inner| TokenBundleClient::new(inner, tx))
+            .ok_or_else(|| {
+                extension_not_available(
+                    "token_bundle",
+                    "TokenBundle extension is not available in this client".to_string(),
+                )
+            })
     }
 
     #[getter]
     pub fn attestation(&self) -> PyResult<AttestationClient> {
-        self.attestation.clone().ok_or_else(|| {
-            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
-                "Attestation extension is not available in this client",
-            )
-        })
+        self.ensure_open()?;
+        let tx = self.tx.clone();
+        self.extensions
+            .attestation
+            .clone()
+            .map(|inner| AttestationClient::new(inner, tx))
+            .ok_or_else(|| {
+                extension_not_available(
+                    "attestation",
+                    "Attestation extension is not available in this client".to_string(),
+                )
+            })
     }
 
     #[getter]
     pub fn string_obligation(&self) -> PyResult<StringObligationClient> {
-        self.string_obligation.clone().ok_or_else(|| {
-            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
-                "StringObligation extension is not available in this client",
-            )
-        })
+        self.ensure_open()?;
+        let tx = self.tx.clone();
+        self.extensions
+            .string_obligation
+            .clone()
+            .map(|inner| StringObligationClient::new(inner, tx))
+            .ok_or_else(|| {
+                extension_not_available(
+                    "string_obligation",
+                    "StringObligation extension is not available in this client".to_string(),
+                )
+            })
     }
 
     #[getter]
     pub fn oracle(&self) -> PyResult<OracleClient> {
-        self.oracle.clone().ok_or_else(|| {
-            pyo3::PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
-                "Oracle extension is not available in this client",
-            )
-        })
+        self.ensure_open()?;
+        let tx = self.tx.clone();
+        self.extensions
+            .oracle
+            .clone()
+            .map(|inner| OracleClient::new(inner, tx))
+            .ok_or_else(|| {
+                extension_not_available(
+                    "oracle",
+                    "Oracle extension is not available in this client".to_string(),
+                )
+            })
     }
 
     #[pyo3(signature = (contract_address, buy_attestation, from_block=None))]
@@ -758,36 +633,28 @@ impl PyAlkahestClient {
         buy_attestation: String,
         from_block: Option<u64>,
     ) -> PyResult<pyo3::Bound<'py, PyAny>> {
+        self.ensure_open()?;
         let inner = self.inner.clone();
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let contract_address: Address = contract_address.parse().map_err(|e| {
-                pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Parse error: {}", e))
-            })?;
-            let buy_attestation: FixedBytes<32> = buy_attestation.parse().map_err(|e| {
-                pyo3::PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Parse error: {}", e))
-            })?;
+        crate::runtime::future_into_py(py, async move {
+            let contract_address: Address = parse_address(&contract_address)?;
+            let buy_attestation: FixedBytes<32> =
+                buy_attestation.parse().map_err(map_parse_to_pyerr)?;
 
             // Try to downcast to the appropriate client type
             let res = if let Some(client) = inner.downcast_ref::<AlkahestClient>() {
                 client
                     .wait_for_fulfillment(contract_address, buy_attestation, from_block)
                     .await
-                    .map_err(|e| {
-                        pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e))
-                    })?
+                    .map_err(map_eyre_to_pyerr)?
             } else if let Some(client) =
                 inner.downcast_ref::<alkahest_rs::AlkahestClient<NoExtension>>()
             {
                 client
                     .wait_for_fulfillment(contract_address, buy_attestation, from_block)
                     .await
-                    .map_err(|e| {
-                        pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{}", e))
-                    })?
+                    .map_err(map_eyre_to_pyerr)?
             } else {
-                return Err(pyo3::PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
-                    "Unknown client type",
-                ));
+                return Err(map_eyre_to_pyerr(eyre::eyre!("Unknown client type")));
             };
 
             let result: EscowClaimedLog = res.data.into();
@@ -813,6 +680,16 @@ pub fn get_attested_event(receipt: TransactionReceipt) -> eyre::Result<Log<Attes
 #[pymodule]
 fn alkahest_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyAlkahestClient>()?;
+    m.add_class::<PySigner>()?;
+    m.add_class::<PySyncClient>()?;
+    m.add_class::<PySyncMethod>()?;
+    m.add_class::<PyTxOptions>()?;
+    m.add_class::<PyRetryConfig>()?;
+    m.add_class::<PySimulationResult>()?;
+    m.add_class::<PyTransactionReceipt>()?;
+    m.add_class::<PyPendingTransaction>()?;
+    m.add_class::<PyUnsignedTransaction>()?;
+    m.add_function(pyo3::wrap_pyfunction!(reverts::py_decode_revert, m)?)?;
     m.add_class::<StringObligationClient>()?;
     m.add_class::<OracleClient>()?;
     m.add_class::<PyOracleAddresses>()?;
@@ -826,6 +703,9 @@ fn alkahest_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyTrustedOracleArbiterDemandData>()?;
     m.add_class::<PyEscrowParams>()?;
     m.add_class::<PyEscrowArbitrationResult>()?;
+    m.add_class::<PyEscrowSubscriptionResult>()?;
+    m.add_class::<PyVerdict>()?;
+    m.add_class::<PyDecisionStream>()?;
     m.add_class::<EnvTestManager>()?;
     m.add_class::<PyWalletProvider>()?;
     m.add_class::<PyMockERC20>()?;
@@ -833,11 +713,13 @@ fn alkahest_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyMockERC1155>()?;
     m.add_class::<PyERC20EscrowObligationData>()?;
     m.add_class::<PyERC20PaymentObligationData>()?;
+    m.add_class::<PyPermitSignature>()?;
     m.add_class::<PyERC721EscrowObligationData>()?;
     m.add_class::<PyERC721PaymentObligationData>()?;
     m.add_class::<PyERC1155EscrowObligationData>()?;
     m.add_class::<PyERC1155PaymentObligationData>()?;
     m.add_class::<PyStringObligationData>()?;
+    m.add_class::<PyTokenBundlePaymentObligationData>()?;
     m.add_class::<PyErc20Data>()?;
 
     // Address Configuration Classes
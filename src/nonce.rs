@@ -0,0 +1,121 @@
+use std::{
+    collections::BTreeSet,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use alloy::{
+    primitives::Address,
+    providers::{DynProvider, Provider},
+    transports::TransportError,
+};
+use tokio::sync::Mutex;
+
+/// Hands out nonces for one account so concurrent sends don't collide.
+///
+/// Every clone shares the same state, so the extension clients of an
+/// `AlkahestClient` (and the clients derived from it with `with_*`) allocate
+/// from one sequence. Whenever nothing is in flight the next nonce is re-read
+/// from the node, which picks up transactions sent elsewhere and ones that were
+/// dropped from the mempool.
+#[derive(Clone, Default)]
+pub struct NonceManager {
+    state: Arc<Mutex<NonceState>>,
+    in_flight: Arc<AtomicUsize>,
+    stale: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+struct NonceState {
+    next: Option<u64>,
+    // Reserved but never broadcast; handed out again before `next`
+    released: BTreeSet<u64>,
+}
+
+impl NonceManager {
+    /// Reserves the next nonce for `address`. The nonce counts as in flight
+    /// until the returned lease is dropped.
+    pub async fn reserve(
+        &self,
+        provider: &DynProvider,
+        address: Address,
+    ) -> eyre::Result<NonceLease> {
+        let mut state = self.state.lock().await;
+        let idle = self.in_flight.load(Ordering::SeqCst) == 0;
+        if idle || self.stale.swap(false, Ordering::SeqCst) || state.next.is_none() {
+            state.next = Some(provider.get_transaction_count(address).pending().await?);
+            state.released.clear();
+        }
+
+        let nonce = match state.released.pop_first() {
+            Some(nonce) => nonce,
+            None => {
+                let nonce = state.next.unwrap_or_default();
+                state.next = Some(nonce + 1);
+                nonce
+            }
+        };
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+
+        Ok(NonceLease {
+            nonce,
+            in_flight: self.in_flight.clone(),
+        })
+    }
+
+    /// Returns a nonce whose transaction never reached the node, so the next
+    /// send fills the gap instead of leaving later transactions stuck behind it.
+    pub async fn release(&self, lease: NonceLease) {
+        let mut state = self.state.lock().await;
+        if state.next == Some(lease.nonce + 1) {
+            state.next = Some(lease.nonce);
+            // Anything released just below can be folded back in as well
+            while let Some(&last) = state.released.last() {
+                if Some(last + 1) != state.next {
+                    break;
+                }
+                state.released.pop_last();
+                state.next = Some(last);
+            }
+        } else if state.next.is_some_and(|next| lease.nonce < next) {
+            state.released.insert(lease.nonce);
+        }
+    }
+
+    /// Forces the next reservation to re-read the account's nonce from the node.
+    pub fn resync(&self) {
+        self.stale.store(true, Ordering::SeqCst);
+    }
+}
+
+/// A reserved nonce, see [`NonceManager::reserve`].
+pub struct NonceLease {
+    pub nonce: u64,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Drop for NonceLease {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Whether the node rejected a transaction because its nonce was already used,
+/// by one of ours that raced it or by a transaction sent from somewhere else.
+pub fn is_nonce_conflict(err: &TransportError) -> bool {
+    let Some(resp) = err.as_error_resp() else {
+        return false;
+    };
+    let message = resp.message.to_lowercase();
+    [
+        "nonce too low",
+        "already known",
+        "known transaction",
+        "replacement transaction underpriced",
+        "nonce has already been used",
+    ]
+    .iter()
+    .any(|pattern| message.contains(pattern))
+}
@@ -0,0 +1,387 @@
+use std::{
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use alloy::{
+    consensus::Transaction,
+    primitives::{TxHash, U256},
+    providers::Provider,
+    rpc::types::{TransactionReceipt, TransactionRequest},
+};
+use futures::{
+    future::{BoxFuture, Shared},
+    FutureExt,
+};
+use pyo3::{
+    exceptions::{PyRuntimeError, PyTimeoutError, PyValueError},
+    pyclass, pymethods, Bound, IntoPyObjectExt, PyAny, PyObject, PyResult, Python,
+};
+use tokio::sync::oneshot;
+
+use crate::{
+    error_handling::map_eyre_to_pyerr,
+    runtime::runtime,
+    tx::TxSender,
+    unsigned::{PyUnsignedTransaction, UnsignedCall},
+};
+
+tokio::task_local! {
+    // Set around every client call by runtime::future_into_py
+    static BROADCAST: BroadcastSlot;
+}
+
+/// Where a call made through `client.no_wait` or `client.unsigned` reports its
+/// first transaction.
+#[derive(Clone)]
+pub struct BroadcastSlot(Arc<Mutex<Option<oneshot::Sender<Announcement>>>>);
+
+impl BroadcastSlot {
+    /// Lets the call announce one more transaction, for unsigned calls that
+    /// send several in turn.
+    pub fn rearm(&self) -> oneshot::Receiver<Announcement> {
+        let (sender, receiver) = oneshot::channel();
+        *self.0.lock().unwrap() = Some(sender);
+        receiver
+    }
+}
+
+/// What a call hands back to Python before it finishes.
+pub enum Announcement {
+    Broadcast(Broadcast),
+    Unsigned(UnsignedCall),
+}
+
+/// A transaction that has been sent but not mined, as announced by
+/// [`TxSender::send`] in no-wait mode.
+pub struct Broadcast {
+    pub tx: TxSender,
+    pub request: TransactionRequest,
+    pub state: Arc<ReplaceableTx>,
+}
+
+/// Hands `announcement` to the call's Python handle. Only the first
+/// transaction of a call gets one, unless the slot is re-armed; returns false
+/// for the rest.
+pub fn announce(announcement: Announcement) -> bool {
+    let sender = BROADCAST
+        .try_with(|slot| slot.0.lock().ok().and_then(|mut sender| sender.take()))
+        .ok()
+        .flatten();
+    match sender {
+        Some(sender) => sender.send(announcement).is_ok(),
+        None => false,
+    }
+}
+
+/// Runs a client call. If it announces a transaction before finishing, the
+/// rest of the call moves to the background and a [`PyPendingTransaction`] or
+/// [`PyUnsignedTransaction`] for it is returned instead of its result.
+pub async fn capture<F>(fut: F) -> PyResult<PyObject>
+where
+    F: Future<Output = PyResult<PyObject>> + Send + 'static,
+{
+    let (sender, receiver) = oneshot::channel();
+    let slot = BroadcastSlot(Arc::new(Mutex::new(Some(sender))));
+    let mut call = Box::pin(BROADCAST.scope(slot.clone(), fut));
+
+    tokio::select! {
+        biased;
+        result = &mut call => result,
+        Ok(announcement) = receiver => {
+            let result = runtime()
+                .spawn(call)
+                .map(|joined| {
+                    Arc::new(joined.unwrap_or_else(|e| Err(PyRuntimeError::new_err(e.to_string()))))
+                })
+                .boxed()
+                .shared();
+            Python::with_gil(|py| match announcement {
+                Announcement::Broadcast(broadcast) => {
+                    PyPendingTransaction::new(broadcast, result).into_py_any(py)
+                }
+                Announcement::Unsigned(call) => {
+                    PyUnsignedTransaction::suspended(call, result, slot).into_py_any(py)
+                }
+            })
+        }
+    }
+}
+
+/// The original transaction for a nonce and anything sent to replace it.
+pub struct ReplaceableTx {
+    pub nonce: u64,
+    sent: Mutex<Vec<TxHash>>,
+    cancelled_by: Mutex<Option<TxHash>>,
+    mined: Mutex<Option<(TxHash, u64)>>,
+}
+
+impl ReplaceableTx {
+    pub fn new(hash: TxHash, nonce: u64) -> Self {
+        Self {
+            nonce,
+            sent: Mutex::new(vec![hash]),
+            cancelled_by: Mutex::new(None),
+            mined: Mutex::new(None),
+        }
+    }
+
+    fn latest(&self) -> TxHash {
+        *self.sent.lock().unwrap().last().unwrap()
+    }
+
+    fn hashes(&self) -> Vec<TxHash> {
+        self.sent.lock().unwrap().clone()
+    }
+
+    /// Polls until one of the transactions sent for this nonce is mined. A
+    /// mined cancellation is returned as [`TxCancelled`].
+    pub async fn wait_mined(&self, tx: &TxSender) -> eyre::Result<TransactionReceipt> {
+        let provider = tx.provider();
+        loop {
+            for hash in self.hashes() {
+                let Some(receipt) = provider.get_transaction_receipt(hash).await? else {
+                    continue;
+                };
+                if let Some(block) = receipt.block_number {
+                    *self.mined.lock().unwrap() = Some((hash, block));
+                }
+                if *self.cancelled_by.lock().unwrap() == Some(hash) {
+                    return Err(TxCancelled {
+                        tx_hash: self.hashes()[0],
+                        cancelled_by: hash,
+                    }
+                    .into());
+                }
+                return Ok(receipt);
+            }
+            tokio::time::sleep(provider.client().poll_interval()).await;
+        }
+    }
+}
+
+/// A pending transaction that was replaced by its cancellation.
+#[derive(Debug, Clone)]
+pub struct TxCancelled {
+    pub tx_hash: TxHash,
+    pub cancelled_by: TxHash,
+}
+
+impl fmt::Display for TxCancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "transaction {} was cancelled by {}",
+            self.tx_hash, self.cancelled_by
+        )
+    }
+}
+
+impl std::error::Error for TxCancelled {}
+
+pub type CallResult = Shared<BoxFuture<'static, Arc<PyResult<PyObject>>>>;
+
+/// Returned by the methods of `client.no_wait` as soon as their transaction is
+/// broadcast. `wait()` gives the method's usual result once it's mined.
+#[pyclass]
+pub struct PyPendingTransaction {
+    tx: TxSender,
+    request: TransactionRequest,
+    state: Arc<ReplaceableTx>,
+    result: CallResult,
+}
+
+impl PyPendingTransaction {
+    fn new(broadcast: Broadcast, result: CallResult) -> Self {
+        Self {
+            tx: broadcast.tx,
+            request: broadcast.request,
+            state: broadcast.state,
+            result,
+        }
+    }
+
+    /// Sends `request` in place of the pending transaction, with the fees of
+    /// the latest one it replaces scaled by `fee_multiplier`.
+    async fn replace(
+        tx: &TxSender,
+        state: &ReplaceableTx,
+        request: TransactionRequest,
+        fee_multiplier: f64,
+        cancel: bool,
+    ) -> eyre::Result<TxHash> {
+        let latest = tx
+            .provider()
+            .get_transaction_by_hash(state.latest())
+            .await?
+            .ok_or_else(|| eyre::eyre!("transaction {} is no longer known", state.latest()))?;
+        let bump = |fee: u128| (fee as f64 * fee_multiplier).ceil() as u128;
+
+        let gas_limit = if cancel { 21_000 } else { latest.gas_limit() };
+        let request = request.nonce(state.nonce).gas_limit(gas_limit);
+        let request = match latest.max_priority_fee_per_gas() {
+            Some(priority) => request
+                .max_fee_per_gas(bump(latest.max_fee_per_gas()))
+                .max_priority_fee_per_gas(bump(priority)),
+            None => request.gas_price(bump(latest.max_fee_per_gas())),
+        };
+        let hash = tx.broadcast(request).await?;
+        // Marked before it's waited on, so a mined cancellation is never
+        // mistaken for the call's own transaction
+        if cancel {
+            *state.cancelled_by.lock().unwrap() = Some(hash);
+        }
+        state.sent.lock().unwrap().push(hash);
+        Ok(hash)
+    }
+}
+
+fn check_multiplier(fee_multiplier: f64) -> PyResult<()> {
+    // Nodes only accept a replacement that pays meaningfully more
+    if fee_multiplier <= 1.0 {
+        return Err(PyValueError::new_err(
+            "fee_multiplier must be greater than 1.0",
+        ));
+    }
+    Ok(())
+}
+
+#[pymethods]
+impl PyPendingTransaction {
+    /// Hash of the transaction most recently sent for this nonce
+    #[getter]
+    pub fn transaction_hash(&self) -> String {
+        self.state.latest().to_string()
+    }
+
+    /// The original transaction followed by any replacements
+    #[getter]
+    pub fn transaction_hashes(&self) -> Vec<String> {
+        self.state.hashes().iter().map(|h| h.to_string()).collect()
+    }
+
+    #[getter]
+    pub fn nonce(&self) -> u64 {
+        self.state.nonce
+    }
+
+    /// Whether one of the transactions has been mined
+    #[getter]
+    pub fn is_mined(&self) -> bool {
+        self.state.mined.lock().unwrap().is_some()
+    }
+
+    /// Waits for the transaction to be mined and then for `confirmations`
+    /// blocks in total, and returns what the method would have returned.
+    /// Raises `TimeoutError` after `timeout` seconds; the handle stays usable.
+    #[pyo3(signature = (confirmations=1, timeout=None))]
+    pub fn wait<'py>(
+        &self,
+        py: Python<'py>,
+        confirmations: u64,
+        timeout: Option<f64>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let tx = self.tx.clone();
+        let state = self.state.clone();
+        let result = self.result.clone();
+        crate::runtime::future_into_py(py, async move {
+            let wait = async move {
+                let result = result.await;
+                let value = Python::with_gil(|py| match &*result {
+                    Ok(value) => Ok(value.clone_ref(py)),
+                    Err(err) => Err(err.clone_ref(py)),
+                })?;
+
+                let mined = *state.mined.lock().unwrap();
+                if let Some((hash, block)) = mined {
+                    let target = block + confirmations.max(1) - 1;
+                    let provider = tx.provider();
+                    while provider
+                        .get_block_number()
+                        .await
+                        .map_err(|e| map_eyre_to_pyerr(e.into()))?
+                        < target
+                    {
+                        tokio::time::sleep(provider.client().poll_interval()).await;
+                    }
+                    let receipt = provider
+                        .get_transaction_receipt(hash)
+                        .await
+                        .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+                    if receipt.is_none() {
+                        return Err(map_eyre_to_pyerr(eyre::eyre!(
+                            "transaction {} was removed from the chain by a reorg",
+                            hash
+                        )));
+                    }
+                }
+                Ok(value)
+            };
+
+            match timeout {
+                Some(seconds) => tokio::time::timeout(Duration::from_secs_f64(seconds), wait)
+                    .await
+                    .map_err(|_| {
+                        PyTimeoutError::new_err(format!(
+                            "transaction not confirmed after {} seconds",
+                            seconds
+                        ))
+                    })?,
+                None => wait.await,
+            }
+        })
+    }
+
+    /// Re-sends the transaction with its fees multiplied by `fee_multiplier`
+    /// and returns the new hash. Whichever version is mined first completes
+    /// the call.
+    #[pyo3(signature = (fee_multiplier=1.2))]
+    pub fn speed_up<'py>(
+        &self,
+        py: Python<'py>,
+        fee_multiplier: f64,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        check_multiplier(fee_multiplier)?;
+        let tx = self.tx.clone();
+        let state = self.state.clone();
+        let request = self.request.clone();
+        crate::runtime::future_into_py(py, async move {
+            let hash = Self::replace(&tx, &state, request, fee_multiplier, false)
+                .await
+                .map_err(map_eyre_to_pyerr)?;
+            Ok(hash.to_string())
+        })
+    }
+
+    /// Replaces the transaction with an empty transfer to yourself at the same
+    /// nonce, paying `fee_multiplier` times its fees. If the cancellation is
+    /// mined first, `wait()` raises `TransactionCancelled`.
+    #[pyo3(signature = (fee_multiplier=1.2))]
+    pub fn cancel<'py>(&self, py: Python<'py>, fee_multiplier: f64) -> PyResult<Bound<'py, PyAny>> {
+        check_multiplier(fee_multiplier)?;
+        let tx = self.tx.clone();
+        let state = self.state.clone();
+        crate::runtime::future_into_py(py, async move {
+            let address = tx.address().map_err(map_eyre_to_pyerr)?;
+            let request = TransactionRequest::default()
+                .from(address)
+                .to(address)
+                .value(U256::ZERO);
+            let hash = Self::replace(&tx, &state, request, fee_multiplier, true)
+                .await
+                .map_err(map_eyre_to_pyerr)?;
+            Ok(hash.to_string())
+        })
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "PendingTransaction(transaction_hash={:?}, nonce={}, mined={})",
+            self.transaction_hash(),
+            self.state.nonce,
+            self.is_mined()
+        )
+    }
+}
@@ -0,0 +1,155 @@
+use alkahest_rs::{contracts::IEAS::Attested, sol_types::EscrowClaimed};
+use alloy::{
+    primitives::U256,
+    rpc::types::{Log, TransactionReceipt},
+    sol_types::SolEvent,
+};
+use pyo3::{
+    pyclass, pymethods,
+    types::{PyDict, PyDictMethods},
+    Bound, IntoPyObjectExt, PyAny, PyResult, Python,
+};
+
+use crate::types::{AttestedLog, EscowClaimedLog, PyU256};
+
+/// An Alkahest or EAS event found in a receipt's logs.
+#[derive(Clone, Debug)]
+pub enum DecodedLog {
+    Attested(AttestedLog),
+    EscrowClaimed(EscowClaimedLog),
+}
+
+impl DecodedLog {
+    fn decode(log: &Log) -> Option<Self> {
+        match log.topic0()? {
+            topic if *topic == Attested::SIGNATURE_HASH => log
+                .log_decode::<Attested>()
+                .ok()
+                .map(|log| Self::Attested(log.inner.data.into())),
+            topic if *topic == EscrowClaimed::SIGNATURE_HASH => log
+                .log_decode::<EscrowClaimed>()
+                .ok()
+                .map(|log| Self::EscrowClaimed(log.inner.data.into())),
+            _ => None,
+        }
+    }
+
+    fn event(&self) -> &'static str {
+        match self {
+            Self::Attested(_) => "Attested",
+            Self::EscrowClaimed(_) => "EscrowClaimed",
+        }
+    }
+}
+
+/// The mined result of a transaction sent by one of the clients.
+#[pyclass]
+#[derive(Clone)]
+pub struct PyTransactionReceipt {
+    #[pyo3(get)]
+    pub transaction_hash: String,
+    #[pyo3(get)]
+    pub block_number: Option<u64>,
+    #[pyo3(get)]
+    pub status: bool,
+    #[pyo3(get)]
+    pub from_address: String,
+    #[pyo3(get)]
+    pub to_address: Option<String>,
+    #[pyo3(get)]
+    pub gas_used: u64,
+    #[pyo3(get)]
+    pub effective_gas_price: u128,
+    #[pyo3(get)]
+    pub fee_paid: PyU256,
+    logs: Vec<(String, DecodedLog)>,
+}
+
+impl From<&TransactionReceipt> for PyTransactionReceipt {
+    fn from(receipt: &TransactionReceipt) -> Self {
+        let blob_fee = U256::from(receipt.blob_gas_used.unwrap_or_default())
+            * U256::from(receipt.blob_gas_price.unwrap_or_default());
+        let fee_paid =
+            U256::from(receipt.gas_used) * U256::from(receipt.effective_gas_price) + blob_fee;
+
+        let logs = receipt
+            .inner
+            .logs()
+            .iter()
+            .filter_map(|log| Some((log.address().to_string(), DecodedLog::decode(log)?)))
+            .collect();
+
+        Self {
+            transaction_hash: receipt.transaction_hash.to_string(),
+            block_number: receipt.block_number,
+            status: receipt.status(),
+            from_address: receipt.from.to_string(),
+            to_address: receipt.to.map(|to| to.to_string()),
+            gas_used: receipt.gas_used,
+            effective_gas_price: receipt.effective_gas_price,
+            fee_paid: fee_paid.into(),
+            logs,
+        }
+    }
+}
+
+impl From<TransactionReceipt> for PyTransactionReceipt {
+    fn from(receipt: TransactionReceipt) -> Self {
+        Self::from(&receipt)
+    }
+}
+
+#[pymethods]
+impl PyTransactionReceipt {
+    /// Decoded Alkahest and EAS events, in log order. Each entry is a dict with
+    /// the emitting `address`, the `event` name and its decoded `args`.
+    #[getter]
+    pub fn logs<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyAny>>> {
+        self.logs
+            .iter()
+            .map(|(address, log)| {
+                let entry = PyDict::new(py);
+                entry.set_item("address", address)?;
+                entry.set_item("event", log.event())?;
+                match log {
+                    DecodedLog::Attested(data) => entry.set_item("args", data.clone())?,
+                    DecodedLog::EscrowClaimed(data) => entry.set_item("args", data.clone())?,
+                }
+                entry.into_bound_py_any(py)
+            })
+            .collect()
+    }
+
+    /// The first `Attested` event, if the transaction made an attestation
+    #[getter]
+    pub fn attested(&self) -> Option<AttestedLog> {
+        self.logs.iter().find_map(|(_, log)| match log {
+            DecodedLog::Attested(data) => Some(data.clone()),
+            _ => None,
+        })
+    }
+
+    /// The first `EscrowClaimed` event, if the transaction collected an escrow
+    #[getter]
+    pub fn escrow_claimed(&self) -> Option<EscowClaimedLog> {
+        self.logs.iter().find_map(|(_, log)| match log {
+            DecodedLog::EscrowClaimed(data) => Some(data.clone()),
+            _ => None,
+        })
+    }
+
+    pub fn __str__(&self) -> String {
+        self.transaction_hash.clone()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "TransactionReceipt(transaction_hash={:?}, block_number={:?}, status={}, gas_used={}, fee_paid={})",
+            self.transaction_hash,
+            self.block_number,
+            self.status,
+            self.gas_used,
+            self.fee_paid
+        )
+    }
+}
@@ -0,0 +1,171 @@
+use alkahest_rs::{
+    clients::arbiters::TrustedOracleArbiter,
+    contracts::{
+        AttestationBarterUtils, AttestationEscrowObligation, AttestationEscrowObligation2,
+        ERC1155BarterCrossToken, ERC1155EscrowObligation, ERC1155PaymentObligation,
+        ERC20BarterCrossToken, ERC20EscrowObligation, ERC20PaymentObligation,
+        ERC721BarterCrossToken, ERC721EscrowObligation, ERC721PaymentObligation, StringObligation,
+        TokenBundleBarterUtils, TokenBundleEscrowObligation, TokenBundlePaymentObligation,
+    },
+    fixtures::{MockERC1155, MockERC20Permit, MockERC721},
+};
+use alloy::{
+    dyn_abi::{DynSolType, DynSolValue},
+    sol_types::{Panic, Revert, SolError, SolInterface},
+};
+use pyo3::{
+    pyfunction,
+    types::{PyBytes, PyInt, PyList, PyTuple},
+    IntoPyObjectExt, PyObject, PyResult, PyTypeInfo, Python,
+};
+
+use crate::types::PyU256;
+
+/// A revert decoded as one of the errors the Alkahest contracts declare.
+#[derive(Debug, Clone)]
+pub struct DecodedRevert {
+    pub name: String,
+    pub args: Vec<DynSolValue>,
+}
+
+impl DecodedRevert {
+    /// `Error(string)` reverts are shown as their message, everything else as
+    /// `Name(arg, ...)`.
+    pub fn describe(&self) -> String {
+        match (self.name.as_str(), self.args.first()) {
+            ("Error", Some(DynSolValue::String(message))) => message.clone(),
+            _ => {
+                let args: Vec<String> = self.args.iter().map(format_value).collect();
+                format!("{}({})", self.name, args.join(", "))
+            }
+        }
+    }
+
+    /// Decodes `data` as an error with `signature`, e.g. `Name(address,uint256)`.
+    fn from_signature(signature: &str, data: &[u8]) -> Option<Self> {
+        let (name, params) = signature.split_at(signature.find('(')?);
+        let DynSolValue::Tuple(args) = DynSolType::parse(params)
+            .ok()?
+            .abi_decode_params(data.get(4..)?)
+            .ok()?
+        else {
+            return None;
+        };
+        Some(Self {
+            name: name.to_string(),
+            args,
+        })
+    }
+}
+
+/// Tries the `sol!`-generated `*Errors` enum of each contract in turn, so the
+/// errors recognised are exactly the ones alkahest-rs's bindings declare:
+/// those of the escrow, payment, barter and arbiter contracts, the EAS
+/// resolver errors they inherit, and the OpenZeppelin token errors of the
+/// mock tokens.
+macro_rules! decode_contract_errors {
+    ($data:expr, $($errors:ty),* $(,)?) => {{
+        let data: &[u8] = $data;
+        None$(.or_else(|| {
+            let error = <$errors>::abi_decode(data).ok()?;
+            let index = <$errors>::SELECTORS
+                .iter()
+                .position(|selector| *selector == error.selector())?;
+            DecodedRevert::from_signature(<$errors>::SIGNATURES[index], data)
+        }))*
+    }};
+}
+
+pub fn decode_revert(data: &[u8]) -> Option<DecodedRevert> {
+    // The compiler's built-in reverts: require messages and panics
+    if Revert::abi_decode(data).is_ok() {
+        return DecodedRevert::from_signature(Revert::SIGNATURE, data);
+    }
+    if Panic::abi_decode(data).is_ok() {
+        return DecodedRevert::from_signature(Panic::SIGNATURE, data);
+    }
+    decode_contract_errors!(
+        data,
+        ERC20EscrowObligation::ERC20EscrowObligationErrors,
+        ERC20PaymentObligation::ERC20PaymentObligationErrors,
+        ERC20BarterCrossToken::ERC20BarterCrossTokenErrors,
+        ERC721EscrowObligation::ERC721EscrowObligationErrors,
+        ERC721PaymentObligation::ERC721PaymentObligationErrors,
+        ERC721BarterCrossToken::ERC721BarterCrossTokenErrors,
+        ERC1155EscrowObligation::ERC1155EscrowObligationErrors,
+        ERC1155PaymentObligation::ERC1155PaymentObligationErrors,
+        ERC1155BarterCrossToken::ERC1155BarterCrossTokenErrors,
+        TokenBundleEscrowObligation::TokenBundleEscrowObligationErrors,
+        TokenBundlePaymentObligation::TokenBundlePaymentObligationErrors,
+        TokenBundleBarterUtils::TokenBundleBarterUtilsErrors,
+        AttestationEscrowObligation::AttestationEscrowObligationErrors,
+        AttestationEscrowObligation2::AttestationEscrowObligation2Errors,
+        AttestationBarterUtils::AttestationBarterUtilsErrors,
+        StringObligation::StringObligationErrors,
+        TrustedOracleArbiter::TrustedOracleArbiterErrors,
+        MockERC20Permit::MockERC20PermitErrors,
+        MockERC721::MockERC721Errors,
+        MockERC1155::MockERC1155Errors,
+    )
+}
+
+/// Decode raw revert data into `(error_name, error_args)`, with the arguments
+/// as a tuple in declaration order, or `None` if it doesn't match a known error.
+#[pyfunction]
+#[pyo3(name = "decode_revert")]
+pub fn py_decode_revert(py: Python<'_>, data: Vec<u8>) -> PyResult<Option<(String, PyObject)>> {
+    decode_revert(&data)
+        .map(|decoded| Ok((decoded.name, args_to_py(py, &decoded.args)?)))
+        .transpose()
+}
+
+/// Converts decoded arguments to a tuple, in declaration order.
+pub fn args_to_py(py: Python<'_>, args: &[DynSolValue]) -> PyResult<PyObject> {
+    let items = args
+        .iter()
+        .map(|value| value_to_py(py, value))
+        .collect::<PyResult<Vec<_>>>()?;
+    PyTuple::new(py, items)?.into_py_any(py)
+}
+
+// Follows the conventions used elsewhere in the bindings: addresses and 32-byte
+// words are hex strings, integers are Python ints and other bytes are `bytes`.
+fn value_to_py(py: Python<'_>, value: &DynSolValue) -> PyResult<PyObject> {
+    match value {
+        DynSolValue::Bool(b) => b.into_py_any(py),
+        DynSolValue::Uint(n, _) => PyU256(*n).into_py_any(py),
+        DynSolValue::Int(n, _) => PyInt::type_object(py)
+            .call1((n.to_string(),))?
+            .into_py_any(py),
+        DynSolValue::Address(a) => a.to_string().into_py_any(py),
+        DynSolValue::FixedBytes(word, 32) => word.to_string().into_py_any(py),
+        DynSolValue::FixedBytes(word, size) => PyBytes::new(py, &word[..*size]).into_py_any(py),
+        DynSolValue::Bytes(b) => PyBytes::new(py, b).into_py_any(py),
+        DynSolValue::String(s) => s.into_py_any(py),
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => {
+            let items = values
+                .iter()
+                .map(|v| value_to_py(py, v))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, items)?.into_py_any(py)
+        }
+        DynSolValue::Tuple(values) => {
+            let items = values
+                .iter()
+                .map(|v| value_to_py(py, v))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyTuple::new(py, items)?.into_py_any(py)
+        }
+        other => format!("{:?}", other).into_py_any(py),
+    }
+}
+
+fn format_value(value: &DynSolValue) -> String {
+    match value {
+        DynSolValue::Uint(n, _) => n.to_string(),
+        DynSolValue::Int(n, _) => n.to_string(),
+        DynSolValue::Address(a) => a.to_string(),
+        DynSolValue::String(s) => format!("{:?}", s),
+        other => format!("{:?}", other),
+    }
+}
@@ -0,0 +1,139 @@
+use std::{cell::Cell, future::Future, sync::Arc};
+
+use futures::{future::BoxFuture, FutureExt};
+use pyo3::{
+    exceptions::PyRuntimeError,
+    types::{PyAnyMethods, PyModule, PyTuple},
+    Bound, IntoPyObject, IntoPyObjectExt, PyAny, PyObject, PyResult, Python,
+};
+use pyo3_async_runtimes::TaskLocals;
+use tokio::runtime::Runtime;
+
+use crate::{pending, simulate};
+
+thread_local! {
+    // Set while a call goes through the `client.sync` facade
+    static BLOCKING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The process-wide runtime. It's the one pyo3_async_runtimes drives Python
+/// awaitables on, so connections opened while blocking stay usable from async
+/// methods and no extra worker threads are started.
+pub fn runtime() -> &'static Runtime {
+    pyo3_async_runtimes::tokio::get_runtime()
+}
+
+/// Runs `fut` to completion on the shared runtime, for the constructors and
+/// test fixtures that can't hand an awaitable back to Python.
+pub fn block_on<F: Future>(fut: F) -> F::Output {
+    match tokio::runtime::Handle::try_current() {
+        // Called from a runtime thread, e.g. a Python callback run by a listener
+        Ok(handle) => tokio::task::block_in_place(|| handle.block_on(fut)),
+        Err(_) => runtime().block_on(fut),
+    }
+}
+
+/// Runs `f` with [`future_into_py`] in blocking mode on this thread.
+pub fn blocking<R>(f: impl FnOnce() -> R) -> R {
+    struct Reset(bool);
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            BLOCKING.with(|blocking| blocking.set(self.0));
+        }
+    }
+
+    let _reset = Reset(BLOCKING.with(|blocking| blocking.replace(true)));
+    f()
+}
+
+/// Every client method returns its result through here. Normally that's an
+/// asyncio awaitable; under [`blocking`] the future is run to completion with
+/// the GIL released and the result is returned directly.
+pub fn future_into_py<F, T>(py: Python<'_>, fut: F) -> PyResult<Bound<'_, PyAny>>
+where
+    F: Future<Output = PyResult<T>> + Send + 'static,
+    T: for<'py> IntoPyObject<'py> + Send + 'static,
+{
+    let fut = async move {
+        let value = fut.await?;
+        Python::with_gil(|py| value.into_py_any(py))
+    };
+    // A simulating client's methods return what their transactions would do,
+    let fut = simulate::capture(fut);
+    // and a no-wait client's methods return early with a pending transaction
+    let fut = pending::capture(fut);
+
+    if BLOCKING.with(Cell::get) {
+        let value = py.allow_threads(|| block_on(fut))?;
+        return Ok(value.into_bound(py));
+    }
+    pyo3_async_runtimes::tokio::future_into_py(py, fut)
+}
+
+/// A Python function handed to a long-running method, e.g. an oracle's
+/// decision function, which may be `async def`. A plain function runs on the
+/// blocking pool so a slow one doesn't hold up the runtime. A coroutine is
+/// awaited on the event loop the method was called from, or with `asyncio.run`
+/// when there's none to use, as under `client.sync`. The GIL is only held
+/// while calling into Python, never while a coroutine waits.
+#[derive(Clone)]
+pub struct PyFunction {
+    func: Arc<PyObject>,
+    locals: Option<Arc<TaskLocals>>,
+}
+
+impl PyFunction {
+    /// Wraps `func`, capturing the caller's event loop. Call it from the
+    /// method itself, not from the future it returns.
+    pub fn new(py: Python<'_>, func: PyObject) -> Self {
+        // Under `client.sync` the caller's loop, if any, is blocked on us
+        let locals = if BLOCKING.with(Cell::get) {
+            None
+        } else {
+            pyo3_async_runtimes::tokio::get_current_locals(py).ok()
+        };
+        Self {
+            func: Arc::new(func),
+            locals: locals.map(Arc::new),
+        }
+    }
+
+    /// Calls the function with the arguments `args` builds, awaiting the
+    /// result if it's awaitable.
+    pub fn call<A>(&self, args: A) -> impl Future<Output = PyResult<PyObject>> + Send + 'static
+    where
+        A: for<'py> FnOnce(Python<'py>) -> PyResult<Bound<'py, PyTuple>> + Send + 'static,
+    {
+        let func = self.func.clone();
+        let locals = self.locals.clone();
+        async move {
+            let called = tokio::task::spawn_blocking(move || {
+                Python::with_gil(|py| -> PyResult<BoxFuture<'static, PyResult<PyObject>>> {
+                    let result = func.bind(py).call1(args(py)?)?;
+                    let awaitable = PyModule::import(py, "inspect")?
+                        .call_method1("isawaitable", (&result,))?
+                        .extract::<bool>()?;
+                    if !awaitable {
+                        let result = result.unbind();
+                        return Ok(async move { Ok(result) }.boxed());
+                    }
+                    match &locals {
+                        Some(locals) => Ok(pyo3_async_runtimes::into_future_with_locals(
+                            locals, result,
+                        )?
+                        .boxed()),
+                        None => {
+                            let result = PyModule::import(py, "asyncio")?
+                                .call_method1("run", (result,))?
+                                .unbind();
+                            Ok(async move { Ok(result) }.boxed())
+                        }
+                    }
+                })
+            })
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))??;
+            called.await
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use std::{str::FromStr, sync::Arc};
+
+use alloy::{
+    consensus::SignableTransaction,
+    network::{EthereumWallet, TxSigner},
+    primitives::{Address, Signature, B256},
+    signers::{
+        local::{coins_bip39::English, MnemonicBuilder, PrivateKeySigner},
+        Signer, SignerSync,
+    },
+};
+use async_trait::async_trait;
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    pyclass, pymethods,
+    types::{PyAnyMethods, PyBytes},
+    Bound, FromPyObject, PyAny, PyObject, PyResult, Python,
+};
+
+use crate::error_handling::parse_address;
+
+/// The key material a client signs with.
+///
+/// Keystores and mnemonics both decrypt to an in-memory key, so they end up as
+/// `Local`; anything else is a Python object that signs on our behalf.
+#[derive(Clone, Debug)]
+pub enum AlkahestSigner {
+    Local(PrivateKeySigner),
+    External(ExternalSigner),
+}
+
+impl AlkahestSigner {
+    pub fn address(&self) -> Address {
+        match self {
+            Self::Local(signer) => signer.address(),
+            Self::External(signer) => signer.address,
+        }
+    }
+
+    pub async fn sign_hash(&self, hash: &B256) -> eyre::Result<Signature> {
+        match self {
+            Self::Local(signer) => Ok(signer.sign_hash(hash).await?),
+            Self::External(signer) => signer.sign_hash(hash).await,
+        }
+    }
+
+    pub fn wallet(&self) -> EthereumWallet {
+        match self {
+            Self::Local(signer) => EthereumWallet::from(signer.clone()),
+            Self::External(signer) => EthereumWallet::from(signer.clone()),
+        }
+    }
+
+    pub fn local(&self) -> Option<&PrivateKeySigner> {
+        match self {
+            Self::Local(signer) => Some(signer),
+            Self::External(_) => None,
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for AlkahestSigner {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(private_key) = ob.extract::<String>() {
+            return parse_private_key(&private_key).map(Self::Local);
+        }
+        if let Ok(signer) = ob.extract::<PySigner>() {
+            return Ok(signer.inner);
+        }
+        ExternalSigner::new(ob).map(Self::External)
+    }
+}
+
+fn parse_private_key(private_key: &str) -> PyResult<PrivateKeySigner> {
+    PrivateKeySigner::from_str(private_key)
+        .map_err(|e| PyValueError::new_err(format!("Failed to parse private key: {}", e)))
+}
+
+/// Adapter for Python objects implementing the signing protocol: an `address`
+/// attribute and a `sign_hash(digest: bytes) -> bytes` method returning a
+/// 65-byte `r || s || v` signature.
+#[derive(Clone, Debug)]
+pub struct ExternalSigner {
+    address: Address,
+    inner: Arc<PyObject>,
+}
+
+impl ExternalSigner {
+    pub fn new(ob: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if !ob.hasattr("address")? || !ob.hasattr("sign_hash")? {
+            return Err(PyTypeError::new_err(
+                "expected a private key, a Signer, or an object with `address` and `sign_hash(digest)`",
+            ));
+        }
+        let address: String = ob.getattr("address")?.extract()?;
+
+        Ok(Self {
+            address: parse_address(&address)?,
+            inner: Arc::new(ob.clone().unbind()),
+        })
+    }
+
+    pub async fn sign_hash(&self, hash: &B256) -> eyre::Result<Signature> {
+        let inner = self.inner.clone();
+        let digest = *hash;
+
+        // The Python side may block on a remote service, so keep it off the reactor.
+        let raw = tokio::task::spawn_blocking(move || {
+            Python::with_gil(|py| {
+                inner
+                    .call_method1(py, "sign_hash", (PyBytes::new(py, digest.as_slice()),))?
+                    .extract::<Vec<u8>>(py)
+            })
+        })
+        .await??;
+
+        let signature = Signature::from_raw(&raw)?;
+        let recovered = signature.recover_address_from_prehash(hash)?;
+        if recovered != self.address {
+            eyre::bail!(
+                "signer returned a signature for {} but claims to be {}",
+                recovered,
+                self.address
+            );
+        }
+
+        Ok(signature)
+    }
+}
+
+#[async_trait]
+impl TxSigner<Signature> for ExternalSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> alloy::signers::Result<Signature> {
+        self.sign_hash(&tx.signature_hash())
+            .await
+            .map_err(|e| alloy::signers::Error::other(e.to_string()))
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PySigner {
+    inner: AlkahestSigner,
+}
+
+impl PySigner {
+    pub fn inner(&self) -> &AlkahestSigner {
+        &self.inner
+    }
+}
+
+#[pymethods]
+impl PySigner {
+    #[staticmethod]
+    pub fn from_private_key(private_key: String) -> PyResult<Self> {
+        Ok(Self {
+            inner: AlkahestSigner::Local(parse_private_key(&private_key)?),
+        })
+    }
+
+    /// Decrypt an encrypted JSON keystore (as written by geth, foundry, etc.)
+    #[staticmethod]
+    pub fn from_keystore(path: String, password: String) -> PyResult<Self> {
+        let signer = PrivateKeySigner::decrypt_keystore(&path, password).map_err(|e| {
+            PyValueError::new_err(format!("Failed to decrypt keystore {}: {}", path, e))
+        })?;
+        Ok(Self {
+            inner: AlkahestSigner::Local(signer),
+        })
+    }
+
+    /// Derive a key from a BIP-39 mnemonic. Without a derivation path the key at
+    /// `m/44'/60'/0'/0/{index}` is used.
+    #[staticmethod]
+    #[pyo3(signature = (mnemonic, derivation_path=None, index=0, password=None))]
+    pub fn from_mnemonic(
+        mnemonic: String,
+        derivation_path: Option<String>,
+        index: u32,
+        password: Option<String>,
+    ) -> PyResult<Self> {
+        let mut builder = MnemonicBuilder::<English>::default().phrase(mnemonic);
+        builder = match derivation_path {
+            Some(path) => builder.derivation_path(path),
+            None => builder.index(index),
+        }
+        .map_err(|e| PyValueError::new_err(format!("Invalid derivation path: {}", e)))?;
+        if let Some(password) = password {
+            builder = builder.password(password);
+        }
+
+        let signer = builder
+            .build()
+            .map_err(|e| PyValueError::new_err(format!("Failed to derive key: {}", e)))?;
+        Ok(Self {
+            inner: AlkahestSigner::Local(signer),
+        })
+    }
+
+    /// Wrap any object implementing the signing protocol
+    #[staticmethod]
+    pub fn from_protocol(signer: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Self {
+            inner: AlkahestSigner::External(ExternalSigner::new(signer)?),
+        })
+    }
+
+    /// Sign a 32-byte digest, returning `r || s || v`. This makes a local
+    /// `Signer` itself satisfy the signing protocol.
+    pub fn sign_hash(&self, digest: Vec<u8>) -> PyResult<Vec<u8>> {
+        let Some(signer) = self.inner.local() else {
+            return Err(PyTypeError::new_err(
+                "external signers sign through the object they wrap",
+            ));
+        };
+        if digest.len() != 32 {
+            return Err(PyValueError::new_err("digest must be 32 bytes"));
+        }
+        let signature = signer
+            .sign_hash_sync(&B256::from_slice(&digest))
+            .map_err(|e| PyValueError::new_err(format!("Failed to sign: {}", e)))?;
+        Ok(signature.as_bytes().to_vec())
+    }
+
+    #[getter]
+    pub fn address(&self) -> String {
+        format!("{:?}", self.inner.address())
+    }
+
+    #[getter]
+    pub fn is_local(&self) -> bool {
+        self.inner.local().is_some()
+    }
+
+    pub fn __repr__(&self) -> String {
+        let kind = if self.is_local() { "local" } else { "external" };
+        format!("PySigner(address='{}', kind='{}')", self.address(), kind)
+    }
+}
@@ -0,0 +1,278 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use alkahest_rs::contracts::IEAS::Attested;
+use alloy::{
+    consensus::{Eip658Value, Receipt, ReceiptEnvelope, ReceiptWithBloom},
+    primitives::{Bytes, TxHash},
+    providers::{DynProvider, Provider},
+    rpc::types::{
+        simulate::{SimBlock, SimulatePayload},
+        Log, TransactionReceipt, TransactionRequest,
+    },
+    sol_types::SolEvent,
+};
+use eyre::WrapErr;
+use pyo3::{
+    pyclass, pymethods, types::PyBytes, Bound, IntoPyObjectExt, PyObject, PyResult, Python,
+};
+
+use crate::{
+    error_handling::TxReverted,
+    reverts::{args_to_py, decode_revert},
+    types::AttestedLog,
+};
+
+tokio::task_local! {
+    // Set around every client call by runtime::future_into_py
+    static SEQUENCE: Sequence;
+}
+
+/// The transactions a call made through `client.simulate` would have sent so
+/// far, and how they went when last simulated.
+#[derive(Clone, Default)]
+struct Sequence(Arc<Mutex<SequenceState>>);
+
+#[derive(Default)]
+struct SequenceState {
+    calls: Vec<TransactionRequest>,
+    outcome: Option<Simulation>,
+}
+
+/// What a call's transactions would do if they were sent now.
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    pub transactions: usize,
+    pub return_data: Bytes,
+    pub gas_estimate: Option<u64>,
+    pub revert: Option<TxReverted>,
+    pub attested: Option<AttestedLog>,
+}
+
+/// Runs a client call. If it simulated any transactions, the [`Simulation`]
+/// of all of them is returned in place of its result, unless the call failed
+/// for some other reason after they succeeded.
+pub async fn capture<F>(fut: F) -> PyResult<PyObject>
+where
+    F: Future<Output = PyResult<PyObject>>,
+{
+    let sequence = Sequence::default();
+    let result = SEQUENCE.scope(sequence.clone(), fut).await;
+    let outcome = sequence.0.lock().unwrap().outcome.take();
+    match outcome {
+        Some(simulation) if simulation.revert.is_some() || result.is_ok() => {
+            Python::with_gil(|py| PySimulationResult::from(simulation).into_py_any(py))
+        }
+        _ => result,
+    }
+}
+
+/// Simulates `tx` after the transactions the call simulated before it, in one
+/// `eth_simulateV1` block against the latest state, so each one sees what the
+/// ones before it did. Nothing is sent. The simulated receipt is returned for
+/// the call to carry on with; a revert ends it with [`TxReverted`].
+pub async fn simulate(
+    provider: &DynProvider,
+    tx: TransactionRequest,
+) -> eyre::Result<TransactionReceipt> {
+    let sequence = SEQUENCE
+        .try_with(Clone::clone)
+        .map_err(|_| eyre::eyre!("transactions can only be simulated by a client method"))?;
+    let calls = {
+        let mut state = sequence.0.lock().unwrap();
+        state.calls.push(tx.clone());
+        state.calls.clone()
+    };
+    let transactions = calls.len();
+    let payload = SimulatePayload {
+        block_state_calls: vec![SimBlock {
+            calls,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let block = provider
+        .simulate(&payload)
+        .await
+        .wrap_err("simulating needs a node that supports eth_simulateV1")?
+        .into_iter()
+        .next()
+        .ok_or_else(|| eyre::eyre!("eth_simulateV1 returned no block"))?;
+    let Some(call) = block.calls.last() else {
+        eyre::bail!("eth_simulateV1 returned no calls");
+    };
+
+    let to = tx.to.and_then(|kind| kind.to().copied());
+    let revert = (!call.status).then(|| TxReverted {
+        tx_hash: None,
+        contract_address: to,
+        revert_data: (!call.return_data.is_empty()).then(|| call.return_data.clone()),
+        reason: call
+            .error
+            .as_ref()
+            .map(|error| error.message.clone())
+            .unwrap_or_else(|| "execution reverted".to_string()),
+    });
+    let simulation = Simulation {
+        transactions,
+        return_data: match revert {
+            Some(_) => Bytes::new(),
+            None => call.return_data.clone(),
+        },
+        gas_estimate: revert
+            .is_none()
+            .then(|| block.calls.iter().map(|call| call.gas_used).sum()),
+        revert: revert.clone(),
+        // The last one the call would make, e.g. the escrow after an approval
+        attested: block
+            .calls
+            .iter()
+            .rev()
+            .find_map(|call| find_attested(&call.logs)),
+    };
+    sequence.0.lock().unwrap().outcome = Some(simulation);
+    if let Some(revert) = revert {
+        return Err(revert.into());
+    }
+
+    let receipt = Receipt {
+        status: Eip658Value::Eip658(true),
+        cumulative_gas_used: call.gas_used,
+        logs: call.logs.clone(),
+    };
+    Ok(TransactionReceipt {
+        inner: ReceiptEnvelope::Eip1559(ReceiptWithBloom {
+            receipt,
+            logs_bloom: Default::default(),
+        }),
+        // It was never sent, so it has no hash or block
+        transaction_hash: TxHash::ZERO,
+        transaction_index: None,
+        block_hash: None,
+        block_number: None,
+        gas_used: call.gas_used,
+        effective_gas_price: 0,
+        blob_gas_used: None,
+        blob_gas_price: None,
+        from: tx.from.unwrap_or_default(),
+        to,
+        contract_address: None,
+    })
+}
+
+fn find_attested(logs: &[Log]) -> Option<AttestedLog> {
+    logs.iter()
+        .find(|log| log.topic0() == Some(&Attested::SIGNATURE_HASH))
+        .and_then(|log| log.log_decode::<Attested>().ok())
+        .map(|log| log.inner.data.into())
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PySimulationResult {
+    #[pyo3(get)]
+    pub success: bool,
+    /// How many transactions the call would send; the others describe them all
+    #[pyo3(get)]
+    pub transactions: usize,
+    /// Gas the transactions used between them, None if one reverts
+    #[pyo3(get)]
+    pub gas_estimate: Option<u64>,
+    return_data: Bytes,
+    revert: Option<TxReverted>,
+    attested: Option<AttestedLog>,
+}
+
+impl From<Simulation> for PySimulationResult {
+    fn from(value: Simulation) -> Self {
+        Self {
+            success: value.revert.is_none(),
+            transactions: value.transactions,
+            gas_estimate: value.gas_estimate,
+            return_data: value.return_data,
+            revert: value.revert,
+            attested: value.attested,
+        }
+    }
+}
+
+#[pymethods]
+impl PySimulationResult {
+    #[getter]
+    pub fn return_data<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.return_data)
+    }
+
+    /// The last `Attested` event the call would emit. Its uid is a
+    /// prediction: EAS derives it from the block timestamp, which can differ
+    /// once mined.
+    #[getter]
+    pub fn attested(&self) -> Option<AttestedLog> {
+        self.attested.clone()
+    }
+
+    #[getter]
+    pub fn uid(&self) -> Option<String> {
+        self.attested.as_ref().map(|attested| attested.uid.clone())
+    }
+
+    /// The node's revert message, or the decoded error if it's a known one
+    #[getter]
+    pub fn revert_reason(&self) -> Option<String> {
+        let revert = self.revert.as_ref()?;
+        Some(
+            revert
+                .revert_data
+                .as_ref()
+                .and_then(|data| decode_revert(data))
+                .map(|decoded| decoded.describe())
+                .unwrap_or_else(|| revert.reason.clone()),
+        )
+    }
+
+    #[getter]
+    pub fn revert_data<'py>(&self, py: Python<'py>) -> Option<Bound<'py, PyBytes>> {
+        let data = self.revert.as_ref()?.revert_data.as_ref()?;
+        Some(PyBytes::new(py, data))
+    }
+
+    #[getter]
+    pub fn error_name(&self) -> Option<String> {
+        let data = self.revert.as_ref()?.revert_data.as_ref()?;
+        decode_revert(data).map(|decoded| decoded.name)
+    }
+
+    #[getter]
+    pub fn error_args(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        let decoded = self
+            .revert
+            .as_ref()
+            .and_then(|revert| revert.revert_data.as_ref())
+            .and_then(|data| decode_revert(data));
+        match decoded {
+            Some(decoded) => Ok(Some(args_to_py(py, &decoded.args)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn __bool__(&self) -> bool {
+        self.success
+    }
+
+    pub fn __repr__(&self) -> String {
+        match self.revert_reason() {
+            Some(reason) => format!(
+                "SimulationResult(success=False, revert_reason={:?})",
+                reason
+            ),
+            None => format!(
+                "SimulationResult(success=True, transactions={}, gas_estimate={:?}, uid={:?})",
+                self.transactions,
+                self.gas_estimate,
+                self.uid()
+            ),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use pyo3::{
+    pyclass, pymethods,
+    types::{PyAnyMethods, PyDict, PyList, PyListMethods, PyTuple},
+    Bound, IntoPyObjectExt, PyAny, PyObject, PyResult, Python,
+};
+
+use crate::{
+    clients::{
+        attestation::AttestationClient, erc1155::Erc1155Client, erc20::Erc20Client,
+        erc721::Erc721Client, oracle::OracleClient, string_obligation::StringObligationClient,
+        token_bundle::TokenBundleClient,
+    },
+    pending::PyPendingTransaction,
+    runtime::blocking,
+    unsigned::PyUnsignedTransaction,
+    PyAlkahestClient,
+};
+
+/// Blocking view of a client, returned by `AlkahestClient.sync`.
+///
+/// Attributes mirror the wrapped object: extension clients come back wrapped
+/// again, and methods run to completion with the GIL released instead of
+/// returning an awaitable. Clients a method returns, like the one from
+/// `with_erc20()`, are wrapped too. Nothing is copied, so the blocking and async views
+/// share one connection and signer.
+#[pyclass]
+pub struct PySyncClient {
+    target: PyObject,
+}
+
+impl PySyncClient {
+    pub fn new(target: PyObject) -> Self {
+        Self { target }
+    }
+}
+
+#[pymethods]
+impl PySyncClient {
+    pub fn __getattr__(&self, py: Python<'_>, name: &str) -> PyResult<PyObject> {
+        wrap(self.target.bind(py).getattr(name)?)
+    }
+
+    pub fn __dir__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.target.bind(py).dir()?.into_py_any(py)
+    }
+
+    pub fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!("SyncClient({})", self.target.bind(py).repr()?))
+    }
+}
+
+/// A client method called through [`PySyncClient`].
+#[pyclass]
+pub struct PySyncMethod {
+    method: PyObject,
+}
+
+#[pymethods]
+impl PySyncMethod {
+    #[pyo3(signature = (*args, **kwargs))]
+    pub fn __call__(
+        &self,
+        py: Python<'_>,
+        args: &Bound<'_, PyTuple>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<PyObject> {
+        let result = blocking(|| self.method.bind(py).call(args, kwargs))?;
+        wrap_result(result)
+    }
+
+    pub fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!("SyncMethod({})", self.method.bind(py).repr()?))
+    }
+}
+
+fn wrap(value: Bound<'_, PyAny>) -> PyResult<PyObject> {
+    let py = value.py();
+    if is_client(&value) {
+        return PySyncClient::new(value.unbind()).into_py_any(py);
+    }
+    if value.is_callable() {
+        return PySyncMethod {
+            method: value.unbind(),
+        }
+        .into_py_any(py);
+    }
+    Ok(value.unbind())
+}
+
+// Like `wrap`, but leaves plain callables alone: a method's result is only
+// wrapped when it's a client, or a list of them like `token_bundle.approve`'s
+// unsigned transactions
+fn wrap_result(value: Bound<'_, PyAny>) -> PyResult<PyObject> {
+    let py = value.py();
+    if is_client(&value) {
+        return PySyncClient::new(value.unbind()).into_py_any(py);
+    }
+    if let Ok(list) = value.downcast::<PyList>() {
+        if list.iter().any(|item| is_client(&item)) {
+            let items = list.iter().map(wrap_result).collect::<PyResult<Vec<_>>>()?;
+            return PyList::new(py, items)?.into_py_any(py);
+        }
+    }
+    Ok(value.unbind())
+}
+
+fn is_client(value: &Bound<'_, PyAny>) -> bool {
+    value.is_instance_of::<PyAlkahestClient>()
+        || value.is_instance_of::<Erc20Client>()
+        || value.is_instance_of::<Erc721Client>()
+        || value.is_instance_of::<Erc1155Client>()
+        || value.is_instance_of::<TokenBundleClient>()
+        || value.is_instance_of::<AttestationClient>()
+        || value.is_instance_of::<StringObligationClient>()
+        || value.is_instance_of::<OracleClient>()
+        || value.is_instance_of::<PyPendingTransaction>()
+        || value.is_instance_of::<PyUnsignedTransaction>()
+}
@@ -0,0 +1,329 @@
+use std::{
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use alloy::{
+    rpc::{
+        client::{BuiltInConnectionString, ClientBuilder, RpcClient},
+        json_rpc::{RequestPacket, ResponsePacket},
+    },
+    transports::{
+        BoxTransport, TransportConnect, TransportError, TransportErrorKind, TransportFut,
+    },
+};
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    pyclass, pymethods,
+    types::{PyAnyMethods, PyDict, PyDictMethods, PyString},
+    Bound, FromPyObject, PyAny, PyResult,
+};
+use tower::Service;
+
+pub const CLOSED: &str = "this client has been closed";
+// The one write that isn't safe to repeat: the node signs, so every attempt
+// would be a new transaction. Raw transactions re-send under the same hash.
+const NODE_SIGNED: &str = "eth_sendTransaction";
+
+/// The endpoints a client talks to, in order of preference. Accepts a URL or a
+/// list of them.
+#[derive(Clone, Debug)]
+pub struct RpcEndpoints(Vec<String>);
+
+impl RpcEndpoints {
+    pub fn single(url: &str) -> Self {
+        Self(vec![url.to_string()])
+    }
+}
+
+impl<'py> FromPyObject<'py> for RpcEndpoints {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if ob.is_instance_of::<PyString>() {
+            return Ok(Self(vec![ob.extract()?]));
+        }
+        let urls: Vec<String> = ob
+            .extract()
+            .map_err(|_| PyTypeError::new_err("rpc_url must be a URL or a list of URLs"))?;
+        if urls.is_empty() {
+            return Err(PyValueError::new_err(
+                "rpc_url must list at least one endpoint",
+            ));
+        }
+        Ok(Self(urls))
+    }
+}
+
+/// How requests that fail at the transport level (connection errors, HTTP
+/// 5xx, rate limits) are retried.
+#[derive(Clone, Debug)]
+pub struct RetryConfig {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub unhealthy_cooldown: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(8),
+            unhealthy_cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryConfig {
+    fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff)
+    }
+}
+
+impl TryFrom<PyRetryConfig> for RetryConfig {
+    type Error = pyo3::PyErr;
+
+    fn try_from(value: PyRetryConfig) -> PyResult<Self> {
+        let seconds = |name: &str, value: f64| {
+            Duration::try_from_secs_f64(value)
+                .map_err(|_| PyValueError::new_err(format!("{} must be a positive number", name)))
+        };
+        Ok(Self {
+            max_retries: value.max_retries,
+            initial_backoff: seconds("initial_backoff", value.initial_backoff)?,
+            max_backoff: seconds("max_backoff", value.max_backoff)?,
+            unhealthy_cooldown: seconds("unhealthy_cooldown", value.unhealthy_cooldown)?,
+        })
+    }
+}
+
+// Accepts a RetryConfig object or a dict with the same keys
+impl<'py> FromPyObject<'py> for RetryConfig {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(config) = ob.extract::<PyRetryConfig>() {
+            return config.try_into();
+        }
+
+        let dict = ob.downcast::<PyDict>()?;
+        let mut config = PyRetryConfig::default();
+        for (key, value) in dict.iter() {
+            let key: String = key.extract()?;
+            match key.as_str() {
+                "max_retries" => config.max_retries = value.extract()?,
+                "initial_backoff" => config.initial_backoff = value.extract()?,
+                "max_backoff" => config.max_backoff = value.extract()?,
+                "unhealthy_cooldown" => config.unhealthy_cooldown = value.extract()?,
+                _ => {
+                    return Err(PyValueError::new_err(format!(
+                        "Unknown retry option {:?}",
+                        key
+                    )))
+                }
+            }
+        }
+        config.try_into()
+    }
+}
+
+/// Retry settings for `AlkahestClient`. Times are in seconds.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct PyRetryConfig {
+    #[pyo3(get, set)]
+    pub max_retries: u32,
+    #[pyo3(get, set)]
+    pub initial_backoff: f64,
+    #[pyo3(get, set)]
+    pub max_backoff: f64,
+    #[pyo3(get, set)]
+    pub unhealthy_cooldown: f64,
+}
+
+impl Default for PyRetryConfig {
+    fn default() -> Self {
+        let config = RetryConfig::default();
+        Self {
+            max_retries: config.max_retries,
+            initial_backoff: config.initial_backoff.as_secs_f64(),
+            max_backoff: config.max_backoff.as_secs_f64(),
+            unhealthy_cooldown: config.unhealthy_cooldown.as_secs_f64(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyRetryConfig {
+    #[new]
+    #[pyo3(signature = (max_retries=3, initial_backoff=0.25, max_backoff=8.0, unhealthy_cooldown=30.0))]
+    pub fn new(
+        max_retries: u32,
+        initial_backoff: f64,
+        max_backoff: f64,
+        unhealthy_cooldown: f64,
+    ) -> Self {
+        Self {
+            max_retries,
+            initial_backoff,
+            max_backoff,
+            unhealthy_cooldown,
+        }
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "RetryConfig(max_retries={}, initial_backoff={}, max_backoff={}, unhealthy_cooldown={})",
+            self.max_retries, self.initial_backoff, self.max_backoff, self.unhealthy_cooldown
+        )
+    }
+}
+
+struct Endpoint {
+    transport: BoxTransport,
+    // Set after a failure; the endpoint is tried last until it passes
+    unhealthy_until: Mutex<Option<Instant>>,
+}
+
+impl Endpoint {
+    fn is_healthy(&self) -> bool {
+        match *self.unhealthy_until.lock().unwrap() {
+            Some(until) => Instant::now() >= until,
+            None => true,
+        }
+    }
+}
+
+/// Spreads requests over several endpoints. Each request goes to the first
+/// healthy endpoint, moving down the list when one fails; after a full round
+/// of failures it waits with exponential backoff and starts again.
+#[derive(Clone)]
+pub struct FailoverTransport {
+    endpoints: Connections,
+    retry: RetryConfig,
+}
+
+/// The connections behind a [`FailoverTransport`], shared by every clone of it
+/// and of the providers built on it. Closing drops them, which ends websocket
+/// sessions and HTTP connection pools once in-flight requests finish, rather
+/// than waiting for the last provider to be dropped.
+#[derive(Clone)]
+pub struct Connections(Arc<Mutex<Option<Arc<[Endpoint]>>>>);
+
+impl Connections {
+    pub fn close(&self) {
+        self.0.lock().unwrap().take();
+    }
+
+    fn get(&self) -> Option<Arc<[Endpoint]>> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl FailoverTransport {
+    /// Endpoints in the order to try them: healthy ones first, each group in
+    /// the configured order.
+    fn order(endpoints: &[Endpoint]) -> Vec<&Endpoint> {
+        let (mut healthy, unhealthy): (Vec<_>, Vec<_>) =
+            endpoints.iter().partition(|endpoint| endpoint.is_healthy());
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    async fn request(self, request: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        let repeatable = match &request {
+            RequestPacket::Single(req) => req.method() != NODE_SIGNED,
+            RequestPacket::Batch(reqs) => reqs.iter().all(|req| req.method() != NODE_SIGNED),
+        };
+
+        let Some(endpoints) = self.endpoints.get() else {
+            return Err(TransportErrorKind::custom_str(CLOSED));
+        };
+
+        let mut retry = 0;
+        loop {
+            let mut last_error = None;
+            for endpoint in Self::order(&endpoints) {
+                let error = match endpoint.transport.clone().call(request.clone()).await {
+                    // Rate limits come back as an error response rather than a
+                    // transport error, but mean the same
+                    Ok(response) if response.iter_errors().any(|e| e.is_retry_err()) => {
+                        TransportErrorKind::custom_str("endpoint is rate limiting requests")
+                    }
+                    Ok(response) => {
+                        *endpoint.unhealthy_until.lock().unwrap() = None;
+                        return Ok(response);
+                    }
+                    Err(e) => e,
+                };
+                *endpoint.unhealthy_until.lock().unwrap() =
+                    Some(Instant::now() + self.retry.unhealthy_cooldown);
+                if !repeatable {
+                    return Err(error);
+                }
+                last_error = Some(error);
+            }
+
+            let error = last_error.expect("at least one endpoint");
+            if retry >= self.retry.max_retries {
+                return Err(error);
+            }
+            tokio::time::sleep(self.retry.backoff(retry)).await;
+            retry += 1;
+        }
+    }
+}
+
+impl Service<RequestPacket> for FailoverTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Connections are checked per request
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        Box::pin(self.clone().request(request))
+    }
+}
+
+/// Connects to the endpoints and returns a client that fails over between
+/// them, retrying as `retry` says, and the handle that closes its connections.
+/// Endpoints that can't be reached now are left out, as long as one can.
+pub async fn connect(
+    endpoints: &RpcEndpoints,
+    retry: &RetryConfig,
+) -> eyre::Result<(RpcClient, Connections)> {
+    let mut connected = Vec::with_capacity(endpoints.0.len());
+    let mut is_local = true;
+    let mut last_error = None;
+    for url in &endpoints.0 {
+        let connection: BuiltInConnectionString = url.parse()?;
+        match connection.connect_boxed().await {
+            Ok(transport) => {
+                is_local &= connection.is_local();
+                connected.push(Endpoint {
+                    transport,
+                    unhealthy_until: Mutex::new(None),
+                });
+            }
+            Err(e) => last_error = Some(eyre::eyre!("failed to connect to {}: {}", url, e)),
+        }
+    }
+    if connected.is_empty() {
+        return Err(last_error.unwrap_or_else(|| eyre::eyre!("no RPC endpoints given")));
+    }
+
+    let connections = Connections(Arc::new(Mutex::new(Some(connected.into()))));
+    let transport = FailoverTransport {
+        endpoints: connections.clone(),
+        retry: retry.clone(),
+    };
+    Ok((
+        ClientBuilder::default().transport(transport, is_local),
+        connections,
+    ))
+}
@@ -0,0 +1,626 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use alkahest_rs::extensions::ProviderContext;
+use alloy::{
+    consensus::{SignableTransaction, Transaction, TxEnvelope},
+    eips::eip2718::{Decodable2718, Encodable2718},
+    network::{Ethereum, EthereumWallet, NetworkWallet, TxSigner},
+    primitives::{Address, Signature, TxHash, U256},
+    providers::{
+        DynProvider, PendingTransactionBuilder, PendingTransactionError, Provider, ProviderBuilder,
+        WatchTxError,
+    },
+    rpc::types::{TransactionReceipt, TransactionRequest},
+    transports::TransportError,
+};
+use async_trait::async_trait;
+use pyo3::{
+    exceptions::PyValueError,
+    pyclass, pymethods,
+    types::{PyAnyMethods, PyDict, PyDictMethods},
+    Bound, FromPyObject, PyAny, PyResult,
+};
+
+use crate::{
+    arbitration::Listeners,
+    error_handling::{revert_from_transport, TxReverted},
+    get_attested_event,
+    nonce::{is_nonce_conflict, NonceManager},
+    pending::{announce, Announcement, Broadcast, ReplaceableTx},
+    signer::AlkahestSigner,
+    simulate::simulate,
+    transport::{self, Connections, RetryConfig, RpcEndpoints, CLOSED},
+    types::{AttestedLog, LogWithHash, PyU256},
+    unsigned::{await_signed, UnsignedTx},
+};
+
+const READ_ONLY: &str =
+    "this client is read-only; construct AlkahestClient with a signer to send transactions";
+// Times a send is retried after the node reports its nonce as already used
+const MAX_NONCE_CONFLICTS: usize = 3;
+// How long to wait for a receipt before checking the transaction wasn't dropped
+const DROP_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The wallet of a read-only client's providers. alkahest-rs needs one to build
+/// its clients, but signing anything would be a write, so it refuses.
+#[derive(Clone, Copy, Debug)]
+struct ReadOnlySigner;
+
+#[async_trait]
+impl TxSigner<Signature> for ReadOnlySigner {
+    fn address(&self) -> Address {
+        Address::ZERO
+    }
+
+    async fn sign_transaction(
+        &self,
+        _tx: &mut dyn SignableTransaction<Signature>,
+    ) -> alloy::signers::Result<Signature> {
+        Err(alloy::signers::Error::other(READ_ONLY))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SendMode {
+    Wait,
+    Simulate,
+    NoWait,
+    // Sends are built for this account and signed outside the client
+    Unsigned(Address),
+}
+
+/// Submits transactions for the extension clients.
+///
+/// Writes are built from the contract bindings and sent from here, so every
+/// one gets the client's nonce handling, `tx_options` and send modes. The
+/// alkahest-rs clients are still used for addresses, decoding and reads. They
+/// are built on the same failover connection and wallet, so they never hold a
+/// key of their own: see [`TxSender::alkahest_providers`].
+#[derive(Clone)]
+pub struct TxSender {
+    provider: DynProvider,
+    // The providers the alkahest-rs clients are built on, sharing `provider`'s
+    // RPC client
+    alkahest: ProviderContext,
+    // None for read-only clients
+    signer: Option<AlkahestSigner>,
+    // Shared by every clone, so closing a client also stops the extension
+    // clients and with_* clients that came from it
+    closed: Arc<AtomicBool>,
+    // Shared the same way; closing drops the connections for all of them
+    connections: Connections,
+    // Changed on the copies behind `client.simulate`, `client.no_wait` and
+    // `client.unsigned`
+    mode: SendMode,
+    // Shared by every clone, like `closed`
+    nonces: NonceManager,
+    // The oracle listeners spawned through any clone, stopped on close
+    listeners: Listeners,
+}
+
+impl TxSender {
+    pub async fn connect(
+        signer: AlkahestSigner,
+        endpoints: &RpcEndpoints,
+        retry: &RetryConfig,
+    ) -> eyre::Result<Self> {
+        let wallet = signer.wallet();
+        Self::with_wallet(wallet, Some(signer), endpoints, retry).await
+    }
+
+    pub async fn connect_read_only(
+        endpoints: &RpcEndpoints,
+        retry: &RetryConfig,
+    ) -> eyre::Result<Self> {
+        let wallet = EthereumWallet::from(ReadOnlySigner);
+        Self::with_wallet(wallet, None, endpoints, retry).await
+    }
+
+    // Opens the one failover connection every provider of the client shares
+    async fn with_wallet(
+        wallet: EthereumWallet,
+        signer: Option<AlkahestSigner>,
+        endpoints: &RpcEndpoints,
+        retry: &RetryConfig,
+    ) -> eyre::Result<Self> {
+        let (client, connections) = transport::connect(endpoints, retry).await?;
+        let wallet_provider = ProviderBuilder::new()
+            .wallet(wallet)
+            .connect_client(client.clone());
+        let alkahest = ProviderContext {
+            wallet: Arc::new(wallet_provider.clone()),
+            public: Arc::new(ProviderBuilder::new().connect_client(client)),
+        };
+
+        Ok(Self {
+            provider: wallet_provider.erased(),
+            alkahest,
+            signer,
+            closed: Arc::new(AtomicBool::new(false)),
+            connections,
+            mode: SendMode::Wait,
+            nonces: NonceManager::default(),
+            listeners: Listeners::default(),
+        })
+    }
+
+    pub fn provider(&self) -> &DynProvider {
+        &self.provider
+    }
+
+    /// Providers for building alkahest-rs clients. They go through the same
+    /// failover transport as everything else the client sends.
+    pub fn alkahest_providers(&self) -> ProviderContext {
+        self.alkahest.clone()
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.signer.is_none()
+    }
+
+    /// A copy whose sends are simulated instead, see [`TxSender::send`].
+    pub fn simulating(&self) -> Self {
+        Self {
+            mode: SendMode::Simulate,
+            ..self.clone()
+        }
+    }
+
+    /// A copy whose calls hand back a pending transaction once their first
+    /// transaction is broadcast, see [`crate::pending`].
+    pub fn not_waiting(&self) -> Self {
+        Self {
+            mode: SendMode::NoWait,
+            ..self.clone()
+        }
+    }
+
+    /// A copy whose calls hand back the transaction they would send from
+    /// `from`, for signing elsewhere, see [`crate::unsigned`].
+    pub fn unsigned_for(&self, from: Address) -> Self {
+        Self {
+            mode: SendMode::Unsigned(from),
+            ..self.clone()
+        }
+    }
+
+    pub fn is_unsigned(&self) -> bool {
+        matches!(self.mode, SendMode::Unsigned(_))
+    }
+
+    pub fn is_simulating(&self) -> bool {
+        self.mode == SendMode::Simulate
+    }
+
+    /// Stops every clone from sending, stops the listeners spawned through
+    /// them and drops the connections they share.
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.listeners.stop_all();
+        self.connections.close();
+    }
+
+    pub fn listeners(&self) -> &Listeners {
+        &self.listeners
+    }
+
+    pub fn ensure_open(&self) -> eyre::Result<()> {
+        if self.closed.load(Ordering::SeqCst) {
+            eyre::bail!(CLOSED);
+        }
+        Ok(())
+    }
+
+    pub fn signer(&self) -> eyre::Result<&AlkahestSigner> {
+        self.signer.as_ref().ok_or_else(|| eyre::eyre!(READ_ONLY))
+    }
+
+    /// The account transactions are sent from: the signer's, or the one
+    /// unsigned transactions are being built for.
+    pub fn address(&self) -> eyre::Result<Address> {
+        match self.mode {
+            SendMode::Unsigned(from) => Ok(from),
+            _ => Ok(self.signer()?.address()),
+        }
+    }
+
+    /// Sends `tx` with any `options` applied and waits for its receipt. Reverts,
+    /// whether caught during gas estimation or on chain, come back as
+    /// [`TxReverted`].
+    ///
+    /// When simulating, nothing is sent: the transaction is simulated after
+    /// the ones the call sent before it, and the simulated receipt is returned,
+    /// see [`crate::simulate`]. In no-wait mode the first transaction of a
+    /// call is announced to its pending-transaction handle once broadcast. In
+    /// unsigned mode it's built and announced to its unsigned-transaction
+    /// handle instead, and the call resumes once that handle broadcasts it.
+    pub async fn send(
+        &self,
+        tx: TransactionRequest,
+        options: Option<&TxOptions>,
+    ) -> eyre::Result<TransactionReceipt> {
+        self.ensure_open()?;
+        let tx = match options {
+            Some(options) => options.apply(tx),
+            None => tx,
+        };
+        if self.mode == SendMode::Simulate {
+            let tx = match &self.signer {
+                Some(signer) => tx.from(signer.address()),
+                None => tx,
+            };
+            return simulate(&self.provider, tx).await;
+        }
+        let to = tx.to.and_then(|kind| kind.to().copied());
+        if let SendMode::Unsigned(from) = self.mode {
+            let tx = tx.from(from);
+            let hash = await_signed(self, self.populate(tx.clone()).await?).await?;
+            let receipt = self
+                .wait_for_receipt(hash)
+                .await?
+                .ok_or_else(|| eyre::eyre!("transaction {} was dropped by the node", hash))?;
+            return self.check_status(receipt, tx, to).await;
+        }
+        let address = self.address()?;
+        // A nonce pinned through TxOptions is used as is
+        let managed = tx.nonce.is_none();
+        let mut conflicts = 0;
+        let mut dropped: Option<TxHash> = None;
+        let receipt = loop {
+            let lease = if managed {
+                Some(self.nonces.reserve(&self.provider, address).await?)
+            } else {
+                None
+            };
+            let request = match &lease {
+                Some(lease) => tx.clone().nonce(lease.nonce),
+                None => tx.clone(),
+            };
+
+            let nonce = request.nonce;
+            let hash = match self.broadcast(request.clone()).await {
+                Ok(hash) => hash,
+                Err(e)
+                    if managed
+                        && e.downcast_ref::<TransportError>()
+                            .is_some_and(is_nonce_conflict)
+                        && conflicts < MAX_NONCE_CONFLICTS =>
+                {
+                    // A rebroadcast after a drop can find the original mined after all
+                    if let Some(hash) = dropped {
+                        if let Some(receipt) = self.provider.get_transaction_receipt(hash).await? {
+                            break receipt;
+                        }
+                    }
+                    conflicts += 1;
+                    self.nonces.resync();
+                    continue;
+                }
+                Err(e) => {
+                    if let Some(lease) = lease {
+                        self.nonces.release(lease).await;
+                    }
+                    let revert = e
+                        .downcast_ref::<TransportError>()
+                        .and_then(|e| revert_from_transport(e, to));
+                    return Err(revert.map(Into::into).unwrap_or(e));
+                }
+            };
+
+            if let (SendMode::NoWait, Some(nonce)) = (self.mode, nonce) {
+                let state = Arc::new(ReplaceableTx::new(hash, nonce));
+                let broadcast = Broadcast {
+                    tx: self.clone(),
+                    request,
+                    state: state.clone(),
+                };
+                if announce(Announcement::Broadcast(broadcast)) {
+                    break state.wait_mined(self).await?;
+                }
+            }
+            match self.wait_for_receipt(hash).await? {
+                Some(receipt) => break receipt,
+                None if managed => {
+                    // Dropped from the mempool: its nonce is free again, so send
+                    // the transaction once more from a fresh count
+                    dropped = Some(hash);
+                    self.nonces.resync();
+                }
+                None => eyre::bail!("transaction {} was dropped by the node", hash),
+            }
+        };
+
+        self.check_status(receipt, tx, to).await
+    }
+
+    /// [`TxSender::send`] for a call that makes an attestation, returning its
+    /// `Attested` event with the transaction hash.
+    pub async fn send_attested(
+        &self,
+        tx: TransactionRequest,
+        options: Option<&TxOptions>,
+    ) -> eyre::Result<LogWithHash<AttestedLog>> {
+        let receipt = self.send(tx, options).await?;
+        let transaction_hash = receipt.transaction_hash.to_string();
+        Ok(LogWithHash {
+            log: get_attested_event(receipt)?.data.into(),
+            transaction_hash,
+        })
+    }
+
+    /// Signs `request` and broadcasts it, returning its hash. The hash is known
+    /// before anything is sent, so when the node errors, e.g. after a retry or
+    /// failover re-sent a transaction whose response was lost, a transaction
+    /// the node does have counts as sent rather than failing the call.
+    pub async fn broadcast(&self, request: TransactionRequest) -> eyre::Result<TxHash> {
+        let request = self.fill(request).await?;
+        let envelope =
+            NetworkWallet::<Ethereum>::sign_request(&self.signer()?.wallet(), request).await?;
+        let hash = *envelope.tx_hash();
+        match self
+            .provider
+            .send_raw_transaction(&envelope.encoded_2718())
+            .await
+        {
+            Ok(_) => Ok(hash),
+            Err(e) => match self.provider.get_transaction_by_hash(hash).await {
+                Ok(Some(_)) => Ok(hash),
+                _ => Err(e.into()),
+            },
+        }
+    }
+
+    /// Turns a failed receipt into [`TxReverted`], replaying `tx` for its
+    /// revert reason.
+    async fn check_status(
+        &self,
+        receipt: TransactionReceipt,
+        tx: TransactionRequest,
+        to: Option<Address>,
+    ) -> eyre::Result<TransactionReceipt> {
+        if !receipt.status() {
+            // Receipts don't carry revert data, so replay the call at its block to get it
+            let replay = match receipt.block_number {
+                Some(block) => self.provider.call(tx).block(block.into()).await.err(),
+                None => None,
+            };
+            let revert = replay.and_then(|e| revert_from_transport(&e, to));
+            return Err(TxReverted {
+                tx_hash: Some(receipt.transaction_hash),
+                contract_address: to,
+                revert_data: revert.as_ref().and_then(|r| r.revert_data.clone()),
+                reason: revert
+                    .map(|r| r.reason)
+                    .unwrap_or_else(|| "execution reverted".to_string()),
+            }
+            .into());
+        }
+
+        Ok(receipt)
+    }
+
+    /// Fills in everything `tx` needs to be signed: nonce, gas, fees and chain
+    /// id. Fields already set, e.g. through `TxOptions`, are kept. A call that
+    /// would revert fails here with [`TxReverted`], as it would when sent.
+    pub async fn populate(&self, tx: TransactionRequest) -> eyre::Result<UnsignedTx> {
+        UnsignedTx::new(self.fill(tx).await?)
+    }
+
+    /// [`TxSender::populate`], without checking the result can be signed
+    async fn fill(&self, tx: TransactionRequest) -> eyre::Result<TransactionRequest> {
+        let from = self.address()?;
+        let to = tx.to.and_then(|kind| kind.to().copied());
+        let mut tx = tx.from(from);
+        if tx.value.is_none() {
+            tx.value = Some(U256::ZERO);
+        }
+        if tx.chain_id.is_none() {
+            tx.chain_id = Some(self.provider.get_chain_id().await?);
+        }
+        if tx.nonce.is_none() {
+            tx.nonce = Some(self.provider.get_transaction_count(from).pending().await?);
+        }
+        if tx.gas.is_none() {
+            tx.gas = match self.provider.estimate_gas(tx.clone()).await {
+                Ok(gas) => Some(gas),
+                Err(e) => match revert_from_transport(&e, to) {
+                    Some(reverted) => return Err(reverted.into()),
+                    None => return Err(e.into()),
+                },
+            };
+        }
+        if tx.max_fee_per_gas.is_none() || tx.max_priority_fee_per_gas.is_none() {
+            let fees = self.provider.estimate_eip1559_fees().await?;
+            tx.max_fee_per_gas.get_or_insert(fees.max_fee_per_gas);
+            tx.max_priority_fee_per_gas
+                .get_or_insert(fees.max_priority_fee_per_gas);
+        }
+        Ok(tx)
+    }
+
+    /// Broadcasts a transaction signed outside the client and returns its hash.
+    pub async fn send_raw(&self, raw: &[u8]) -> eyre::Result<TxHash> {
+        self.ensure_open()?;
+        let envelope = TxEnvelope::decode_2718(&mut &raw[..])?;
+        match self.provider.send_raw_transaction(raw).await {
+            Ok(pending) => Ok(*pending.tx_hash()),
+            Err(e) => match revert_from_transport(&e, envelope.to()) {
+                Some(reverted) => Err(reverted.into()),
+                None => Err(e.into()),
+            },
+        }
+    }
+
+    /// Broadcasts a transaction signed outside the client and waits for its
+    /// receipt, with reverts reported as for [`TxSender::send`].
+    pub async fn broadcast_raw(&self, raw: &[u8]) -> eyre::Result<TransactionReceipt> {
+        let envelope = TxEnvelope::decode_2718(&mut &raw[..])?;
+        let to = envelope.to();
+        let hash = self.send_raw(raw).await?;
+        let receipt = self
+            .wait_for_receipt(hash)
+            .await?
+            .ok_or_else(|| eyre::eyre!("transaction {} was dropped by the node", hash))?;
+        let tx = TransactionRequest::from_transaction_with_sender(envelope, receipt.from);
+        self.check_status(receipt, tx, to).await
+    }
+
+    /// Waits for `hash` to be mined. `None` means the node no longer knows the
+    /// transaction, which happens when it's dropped or evicted from the mempool.
+    async fn wait_for_receipt(&self, hash: TxHash) -> eyre::Result<Option<TransactionReceipt>> {
+        loop {
+            let pending = PendingTransactionBuilder::new(self.provider.root().clone(), hash)
+                .with_timeout(Some(DROP_CHECK_INTERVAL));
+            match pending.get_receipt().await {
+                Ok(receipt) => return Ok(Some(receipt)),
+                Err(PendingTransactionError::TxWatcher(WatchTxError::Timeout)) => {
+                    if self.provider.get_transaction_by_hash(hash).await?.is_none() {
+                        return Ok(None);
+                    }
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Makes the next send re-read the account's nonce from the node, for
+    /// when transactions were sent from the same account outside this client.
+    pub fn reset_nonce(&self) {
+        self.nonces.resync();
+    }
+}
+
+/// Per-call overrides for the transaction a method sends. Anything left unset
+/// is filled in by the provider as usual.
+#[derive(Debug, Clone, Default)]
+pub struct TxOptions {
+    pub gas_limit: Option<u64>,
+    pub max_fee_per_gas: Option<u128>,
+    pub max_priority_fee_per_gas: Option<u128>,
+    pub nonce: Option<u64>,
+    pub value: Option<U256>,
+}
+
+impl TxOptions {
+    pub fn apply(&self, mut tx: TransactionRequest) -> TransactionRequest {
+        if let Some(gas_limit) = self.gas_limit {
+            tx.gas = Some(gas_limit);
+        }
+        if let Some(max_fee_per_gas) = self.max_fee_per_gas {
+            tx.max_fee_per_gas = Some(max_fee_per_gas);
+        }
+        if let Some(max_priority_fee_per_gas) = self.max_priority_fee_per_gas {
+            tx.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+        }
+        if let Some(nonce) = self.nonce {
+            tx.nonce = Some(nonce);
+        }
+        if let Some(value) = self.value {
+            tx.value = Some(value);
+        }
+        tx
+    }
+
+    /// Options for the `index`th of several transactions sent by one call: a
+    /// pinned nonce counts up from the first, everything else is shared.
+    pub fn for_sequence(&self, index: u64) -> Self {
+        Self {
+            nonce: self.nonce.map(|nonce| nonce + index),
+            ..self.clone()
+        }
+    }
+}
+
+impl From<PyTxOptions> for TxOptions {
+    fn from(value: PyTxOptions) -> Self {
+        Self {
+            gas_limit: value.gas_limit,
+            max_fee_per_gas: value.max_fee_per_gas,
+            max_priority_fee_per_gas: value.max_priority_fee_per_gas,
+            nonce: value.nonce,
+            value: value.value.map(Into::into),
+        }
+    }
+}
+
+// Accepts a TxOptions object or a dict with the same keys
+impl<'py> FromPyObject<'py> for TxOptions {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(options) = ob.extract::<PyTxOptions>() {
+            return Ok(options.into());
+        }
+
+        let dict = ob.downcast::<PyDict>()?;
+        let mut options = PyTxOptions::default();
+        for (key, value) in dict.iter() {
+            let key: String = key.extract()?;
+            match key.as_str() {
+                "gas_limit" => options.gas_limit = value.extract()?,
+                "max_fee_per_gas" => options.max_fee_per_gas = value.extract()?,
+                "max_priority_fee_per_gas" => options.max_priority_fee_per_gas = value.extract()?,
+                "nonce" => options.nonce = value.extract()?,
+                "value" => options.value = value.extract()?,
+                _ => {
+                    return Err(PyValueError::new_err(format!(
+                        "Unknown transaction option {:?}",
+                        key
+                    )))
+                }
+            }
+        }
+        Ok(options.into())
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct PyTxOptions {
+    #[pyo3(get, set)]
+    pub gas_limit: Option<u64>,
+    #[pyo3(get, set)]
+    pub max_fee_per_gas: Option<u128>,
+    #[pyo3(get, set)]
+    pub max_priority_fee_per_gas: Option<u128>,
+    #[pyo3(get, set)]
+    pub nonce: Option<u64>,
+    #[pyo3(get, set)]
+    pub value: Option<PyU256>,
+}
+
+#[pymethods]
+impl PyTxOptions {
+    #[new]
+    #[pyo3(signature = (gas_limit=None, max_fee_per_gas=None, max_priority_fee_per_gas=None, nonce=None, value=None))]
+    pub fn new(
+        gas_limit: Option<u64>,
+        max_fee_per_gas: Option<u128>,
+        max_priority_fee_per_gas: Option<u128>,
+        nonce: Option<u64>,
+        value: Option<PyU256>,
+    ) -> Self {
+        Self {
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            nonce,
+            value,
+        }
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "TxOptions(gas_limit={:?}, max_fee_per_gas={:?}, max_priority_fee_per_gas={:?}, nonce={:?}, value={:?})",
+            self.gas_limit,
+            self.max_fee_per_gas,
+            self.max_priority_fee_per_gas,
+            self.nonce,
+            self.value.map(|v| v.0),
+        )
+    }
+}
@@ -1,6 +1,94 @@
 use alkahest_rs::{contracts::IEAS::Attested, sol_types::EscrowClaimed};
 use alloy::primitives::{FixedBytes, U256};
-use pyo3::{exceptions::PyValueError, pyclass, FromPyObject, IntoPyObject, PyErr, PyResult};
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    pyclass,
+    types::{PyAnyMethods, PyBool, PyBytes, PyBytesMethods, PyInt, PyString, PyStringMethods},
+    Bound, FromPyObject, IntoPyObject, PyAny, PyErr, PyResult, Python,
+};
+
+use crate::error_handling::parse_address;
+
+/// A `uint256` that crosses the Python boundary as a plain `int`.
+///
+/// Token amounts, ids and values use this instead of a fixed-width Rust integer so
+/// the whole on-chain range round-trips. Decimal strings are accepted on input for
+/// callers that still pass ids as text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PyU256(pub U256);
+
+impl From<U256> for PyU256 {
+    fn from(value: U256) -> Self {
+        Self(value)
+    }
+}
+
+impl From<PyU256> for U256 {
+    fn from(value: PyU256) -> Self {
+        value.0
+    }
+}
+
+impl std::fmt::Display for PyU256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'py> FromPyObject<'py> for PyU256 {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(text) = ob.downcast::<PyString>() {
+            let text = text.to_cow()?;
+            return text
+                .parse::<U256>()
+                .map(Self)
+                .map_err(|e| PyValueError::new_err(format!("invalid uint256 '{}': {}", text, e)));
+        }
+
+        // bool subclasses int, but True isn't an amount
+        if ob.is_instance_of::<PyBool>() {
+            return Err(PyTypeError::new_err(
+                "expected an int for a uint256 value, not a bool",
+            ));
+        }
+        let int = ob
+            .downcast::<PyInt>()
+            .map_err(|_| PyTypeError::new_err("expected an int for a uint256 value"))?;
+        // `int.to_bytes` raises for negative values and anything wider than 32 bytes,
+        // which is exactly the range check we want.
+        let bytes = int.call_method1("to_bytes", (32, "big")).map_err(|_| {
+            PyValueError::new_err(format!("value {} does not fit in a uint256", int))
+        })?;
+        let bytes = bytes.downcast::<PyBytes>()?;
+        Ok(Self(U256::from_be_slice(bytes.as_bytes())))
+    }
+}
+
+impl<'py> IntoPyObject<'py> for PyU256 {
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Self::Output> {
+        // Small values take the fast path; only genuinely wide values go through bytes.
+        if let Ok(value) = u128::try_from(self.0) {
+            return Ok(value.into_pyobject(py)?.into_any());
+        }
+        let bytes = PyBytes::new(py, &self.0.to_be_bytes::<32>());
+        py.get_type::<PyInt>()
+            .call_method1("from_bytes", (bytes, "big"))
+    }
+}
+
+impl<'py> IntoPyObject<'py> for &PyU256 {
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Self::Output> {
+        (*self).into_pyobject(py)
+    }
+}
 
 macro_rules! client_address_config {
     ($name:ident) => {
@@ -97,14 +185,8 @@ impl TryFrom<StringObligationAddresses>
 
     fn try_from(value: StringObligationAddresses) -> PyResult<Self> {
         Ok(Self {
-            eas: value
-                .eas
-                .parse()
-                .map_err(|_| PyValueError::new_err("invalid address"))?,
-            obligation: value
-                .obligation
-                .parse()
-                .map_err(|_| PyValueError::new_err("invalid address"))?,
+            eas: parse_address(&value.eas)?,
+            obligation: parse_address(&value.obligation)?,
         })
     }
 }
@@ -128,10 +210,7 @@ macro_rules! try_from_address_config {
             fn try_from(value: $from) -> PyResult<Self> {
                 macro_rules! parse_address {
                     ($name:ident) => {
-                        value
-                            .$name
-                            .parse()
-                            .map_err(|_| PyValueError::new_err("invalid address"))?
+                        parse_address(&value.$name)?
                     };
                 }
 
@@ -166,10 +245,7 @@ impl TryFrom<AttestationAddresses> for alkahest_rs::clients::attestation::Attest
     fn try_from(value: AttestationAddresses) -> PyResult<Self> {
         macro_rules! parse_address {
             ($name:ident) => {
-                value
-                    .$name
-                    .parse()
-                    .map_err(|_| PyValueError::new_err("invalid address"))?
+                parse_address(&value.$name)?
             };
         }
 
@@ -189,10 +265,7 @@ impl TryFrom<OracleAddresses> for alkahest_rs::clients::oracle::OracleAddresses
     fn try_from(value: OracleAddresses) -> PyResult<Self> {
         macro_rules! parse_address {
             ($name:ident) => {
-                value
-                    .$name
-                    .parse()
-                    .map_err(|_| PyValueError::new_err("invalid address"))?
+                parse_address(&value.$name)?
             };
         }
 
@@ -228,10 +301,7 @@ impl TryFrom<ArbitersAddresses> for alkahest_rs::clients::arbiters::ArbitersAddr
     fn try_from(value: ArbitersAddresses) -> PyResult<Self> {
         macro_rules! parse_address {
             ($name:ident) => {
-                value
-                    .$name
-                    .parse()
-                    .map_err(|_| PyValueError::new_err("invalid address"))?
+                parse_address(&value.$name)?
             };
         }
 
@@ -315,7 +385,7 @@ impl TryFrom<ArbiterData> for alkahest_rs::types::ArbiterData {
 
     fn try_from(value: ArbiterData) -> eyre::Result<Self> {
         Ok(Self {
-            arbiter: value.arbiter.parse()?,
+            arbiter: parse_address(&value.arbiter)?,
             demand: value.demand.into(),
         })
     }
@@ -325,7 +395,7 @@ impl TryFrom<ArbiterData> for alkahest_rs::types::ArbiterData {
 #[pyo3(from_item_all)]
 pub struct Erc20Data {
     pub address: String,
-    pub value: u64,
+    pub value: PyU256,
 }
 
 impl TryFrom<Erc20Data> for alkahest_rs::types::Erc20Data {
@@ -333,8 +403,8 @@ impl TryFrom<Erc20Data> for alkahest_rs::types::Erc20Data {
 
     fn try_from(value: Erc20Data) -> eyre::Result<Self> {
         Ok(Self {
-            address: value.address.parse()?,
-            value: U256::from(value.value),
+            address: parse_address(&value.address)?,
+            value: value.value.into(),
         })
     }
 }
@@ -348,15 +418,22 @@ pub struct PyErc20Data {
     pub address: String,
 
     #[pyo3(get)]
-    pub value: u64,
+    pub value: PyU256,
 }
 
 #[pymethods]
 impl PyErc20Data {
     #[new]
-    pub fn new(address: String, value: u64) -> Self {
+    pub fn new(address: String, value: PyU256) -> Self {
         Self { address, value }
     }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PyErc20Data(address='{}', value={})",
+            self.address, self.value
+        )
+    }
 }
 
 impl TryFrom<PyErc20Data> for alkahest_rs::types::Erc20Data {
@@ -364,17 +441,26 @@ impl TryFrom<PyErc20Data> for alkahest_rs::types::Erc20Data {
 
     fn try_from(value: PyErc20Data) -> eyre::Result<Self> {
         Ok(Self {
-            address: value.address.parse()?,
-            value: U256::from(value.value),
+            address: parse_address(&value.address)?,
+            value: value.value.into(),
         })
     }
 }
 
+impl From<alkahest_rs::types::Erc20Data> for PyErc20Data {
+    fn from(value: alkahest_rs::types::Erc20Data) -> Self {
+        Self {
+            address: format!("{:?}", value.address),
+            value: value.value.into(),
+        }
+    }
+}
+
 #[derive(FromPyObject)]
 #[pyo3(from_item_all)]
 pub struct Erc721Data {
     pub address: String,
-    pub id: u128,
+    pub id: PyU256,
 }
 
 impl TryFrom<Erc721Data> for alkahest_rs::types::Erc721Data {
@@ -382,8 +468,8 @@ impl TryFrom<Erc721Data> for alkahest_rs::types::Erc721Data {
 
     fn try_from(value: Erc721Data) -> eyre::Result<Self> {
         Ok(Self {
-            address: value.address.parse()?,
-            id: value.id.try_into()?,
+            address: parse_address(&value.address)?,
+            id: value.id.into(),
         })
     }
 }
@@ -392,8 +478,8 @@ impl TryFrom<Erc721Data> for alkahest_rs::types::Erc721Data {
 #[pyo3(from_item_all)]
 pub struct Erc1155Data {
     address: String,
-    id: u128,
-    value: u128,
+    id: PyU256,
+    value: PyU256,
 }
 
 impl TryFrom<Erc1155Data> for alkahest_rs::types::Erc1155Data {
@@ -401,9 +487,9 @@ impl TryFrom<Erc1155Data> for alkahest_rs::types::Erc1155Data {
 
     fn try_from(value: Erc1155Data) -> eyre::Result<Self> {
         Ok(Self {
-            address: value.address.parse()?,
-            id: value.id.try_into()?,
-            value: value.value.try_into()?,
+            address: parse_address(&value.address)?,
+            id: value.id.into(),
+            value: value.value.into(),
         })
     }
 }
@@ -444,7 +530,7 @@ impl TryFrom<TokenBundleData> for alkahest_rs::types::TokenBundleData {
     }
 }
 
-#[derive(IntoPyObject)]
+#[derive(Clone, Debug, IntoPyObject)]
 pub struct EscowClaimedLog {
     pub payment: String,
     pub fulfillment: String,
@@ -461,7 +547,7 @@ impl From<EscrowClaimed> for EscowClaimedLog {
     }
 }
 
-#[derive(IntoPyObject)]
+#[derive(Clone, Debug, IntoPyObject)]
 pub struct AttestedLog {
     pub recipient: String,
     pub attester: String,
@@ -501,7 +587,7 @@ impl TryFrom<AttestationRequestData> for alkahest_rs::contracts::IEAS::Attestati
 
     fn try_from(value: AttestationRequestData) -> eyre::Result<Self> {
         Ok(Self {
-            recipient: value.recipient.parse()?,
+            recipient: parse_address(&value.recipient)?,
             expirationTime: value.expiration_time,
             revocable: value.revocable,
             refUID: value.ref_uid.parse()?,
@@ -0,0 +1,355 @@
+use std::sync::{Arc, Mutex};
+
+use alloy::{
+    consensus::{SignableTransaction, TxEip1559, TxEnvelope, TypedTransaction},
+    eips::eip2718::{Decodable2718, Encodable2718},
+    primitives::{hex, Bytes, TxHash, B256, U256},
+    rpc::types::TransactionRequest,
+};
+use pyo3::{
+    exceptions::{PyRuntimeError, PyValueError},
+    pyclass, pymethods,
+    types::{
+        PyAnyMethods, PyBytes, PyBytesMethods, PyDict, PyDictMethods, PyString, PyStringMethods,
+    },
+    Bound, FromPyObject, IntoPyObjectExt, PyAny, PyObject, PyResult, Python,
+};
+use tokio::sync::oneshot;
+
+use crate::{
+    error_handling::{map_eyre_to_pyerr, map_parse_to_pyerr},
+    pending::{announce, Announcement, BroadcastSlot, CallResult},
+    receipt::PyTransactionReceipt,
+    signer::AlkahestSigner,
+    tx::{TxOptions, TxSender},
+    types::PyU256,
+};
+
+/// A transaction with every field needed to sign it filled in.
+#[derive(Clone, Debug)]
+pub struct UnsignedTx {
+    pub request: TransactionRequest,
+    typed: TxEip1559,
+}
+
+impl UnsignedTx {
+    pub fn new(request: TransactionRequest) -> eyre::Result<Self> {
+        match request.clone().build_typed_tx() {
+            Ok(TypedTransaction::Eip1559(typed)) => Ok(Self { request, typed }),
+            Ok(_) => eyre::bail!("only EIP-1559 transactions can be built unsigned"),
+            Err(_) => eyre::bail!("transaction is missing fields needed to sign it"),
+        }
+    }
+
+    pub fn signature_hash(&self) -> B256 {
+        self.typed.signature_hash()
+    }
+
+    /// Signs with `signer` and returns the EIP-2718 encoded transaction.
+    pub async fn sign(&self, signer: &AlkahestSigner) -> eyre::Result<Vec<u8>> {
+        if self.request.from != Some(signer.address()) {
+            eyre::bail!(
+                "transaction is from {:?}, not the signer's address {}",
+                self.request.from,
+                signer.address()
+            );
+        }
+        let signature = signer.sign_hash(&self.signature_hash()).await?;
+        let envelope = TxEnvelope::from(self.typed.clone().into_signed(signature));
+        Ok(envelope.encoded_2718())
+    }
+
+    /// Decodes a signed raw transaction, checking it is this transaction.
+    pub fn check_signed(&self, raw: &[u8]) -> eyre::Result<()> {
+        let envelope = TxEnvelope::decode_2718(&mut &raw[..])?;
+        if envelope.signature_hash() != self.signature_hash() {
+            eyre::bail!("the signed transaction doesn't match the unsigned one it was built from");
+        }
+        Ok(())
+    }
+}
+
+/// A call made through `client.unsigned`, suspended until its transaction has
+/// been signed elsewhere and broadcast.
+pub struct UnsignedCall {
+    pub tx: TxSender,
+    pub unsigned: UnsignedTx,
+    pub signed: oneshot::Sender<TxHash>,
+}
+
+/// Hands `unsigned` to the call's [`PyUnsignedTransaction`] and waits for the
+/// hash it's broadcast under.
+pub async fn await_signed(tx: &TxSender, unsigned: UnsignedTx) -> eyre::Result<TxHash> {
+    let (signed, receiver) = oneshot::channel();
+    let call = UnsignedCall {
+        tx: tx.clone(),
+        unsigned,
+        signed,
+    };
+    if !announce(Announcement::Unsigned(call)) {
+        eyre::bail!("nothing is waiting for this call's next unsigned transaction");
+    }
+    receiver
+        .await
+        .map_err(|_| eyre::eyre!("the unsigned transaction was discarded without being broadcast"))
+}
+
+fn already_broadcast() -> pyo3::PyErr {
+    PyRuntimeError::new_err("this transaction has already been broadcast")
+}
+
+/// Builds several transactions that one call sends in order, with consecutive
+/// nonces. Each is broadcast separately and gives back its receipt.
+pub async fn build_sequence(
+    tx: &TxSender,
+    calls: Vec<TransactionRequest>,
+    options: Option<&TxOptions>,
+) -> eyre::Result<Vec<PyUnsignedTransaction>> {
+    let mut nonce = options.and_then(|options| options.nonce);
+    let mut built = Vec::with_capacity(calls.len());
+    for call in calls {
+        let call = match options {
+            Some(options) => options.apply(call),
+            None => call,
+        };
+        let call = match nonce {
+            Some(nonce) => call.nonce(nonce),
+            None => call,
+        };
+        let unsigned = tx.populate(call).await?;
+        nonce = unsigned.request.nonce.map(|nonce| nonce + 1);
+        built.push(PyUnsignedTransaction::standalone(tx.clone(), unsigned));
+    }
+    Ok(built)
+}
+
+/// A signed transaction, as bytes or a 0x-prefixed hex string.
+pub struct RawTx(pub Bytes);
+
+impl<'py> FromPyObject<'py> for RawTx {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(bytes) = ob.downcast::<PyBytes>() {
+            return Ok(Self(Bytes::copy_from_slice(bytes.as_bytes())));
+        }
+        let text = ob.downcast::<PyString>()?.to_cow()?;
+        hex::decode(text.as_ref())
+            .map(|raw| Self(raw.into()))
+            .map_err(|e| map_parse_to_pyerr(format!("invalid raw transaction: {}", e)))
+    }
+}
+
+/// Returned by the methods of `client.unsigned` in place of sending. Sign
+/// `signing_hash` (or the `rlp()` payload) with the external signer, then pass
+/// the signed transaction to `broadcast_signed`. For calls that send several
+/// transactions, that returns the next one to sign, until the last returns
+/// the call's result.
+#[pyclass]
+pub struct PyUnsignedTransaction {
+    tx: TxSender,
+    unsigned: UnsignedTx,
+    // None for transactions built together, like the approvals of a token
+    // bundle, rather than by a suspended client method
+    call: Option<SuspendedCall>,
+}
+
+struct SuspendedCall {
+    // Taken once the transaction has reached the node
+    signed: Arc<Mutex<Option<oneshot::Sender<TxHash>>>>,
+    result: CallResult,
+    // Where the call announces its next transaction, if it sends another
+    slot: BroadcastSlot,
+}
+
+impl PyUnsignedTransaction {
+    pub fn suspended(call: UnsignedCall, result: CallResult, slot: BroadcastSlot) -> Self {
+        Self {
+            tx: call.tx,
+            unsigned: call.unsigned,
+            call: Some(SuspendedCall {
+                signed: Arc::new(Mutex::new(Some(call.signed))),
+                result,
+                slot,
+            }),
+        }
+    }
+
+    pub fn standalone(tx: TxSender, unsigned: UnsignedTx) -> Self {
+        Self {
+            tx,
+            unsigned,
+            call: None,
+        }
+    }
+
+    fn request(&self) -> &TransactionRequest {
+        &self.unsigned.request
+    }
+}
+
+#[pymethods]
+impl PyUnsignedTransaction {
+    #[getter]
+    pub fn from_address(&self) -> Option<String> {
+        self.request().from.map(|from| from.to_string())
+    }
+
+    #[getter]
+    pub fn to(&self) -> Option<String> {
+        self.request()
+            .to
+            .and_then(|kind| kind.to().copied())
+            .map(|to| to.to_string())
+    }
+
+    #[getter]
+    pub fn data<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        let input = self.request().input.input().cloned().unwrap_or_default();
+        PyBytes::new(py, &input)
+    }
+
+    #[getter]
+    pub fn value(&self) -> PyU256 {
+        self.request().value.unwrap_or(U256::ZERO).into()
+    }
+
+    #[getter]
+    pub fn gas(&self) -> Option<u64> {
+        self.request().gas
+    }
+
+    #[getter]
+    pub fn chain_id(&self) -> Option<u64> {
+        self.request().chain_id
+    }
+
+    #[getter]
+    pub fn nonce(&self) -> Option<u64> {
+        self.request().nonce
+    }
+
+    #[getter]
+    pub fn max_fee_per_gas(&self) -> Option<u128> {
+        self.request().max_fee_per_gas
+    }
+
+    #[getter]
+    pub fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        self.request().max_priority_fee_per_gas
+    }
+
+    /// Hash the external signer signs, as hex
+    #[getter]
+    pub fn signing_hash(&self) -> String {
+        self.unsigned.signature_hash().to_string()
+    }
+
+    /// The EIP-2718 payload that `signing_hash` is the keccak256 of
+    pub fn rlp<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.unsigned.typed.encoded_for_signing())
+    }
+
+    /// The transaction as a dict in the form `eth_account` and web3.py sign
+    pub fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("type", u8::from(TxEip1559::tx_type()))?;
+        dict.set_item("chainId", self.chain_id())?;
+        dict.set_item("nonce", self.nonce())?;
+        dict.set_item("from", self.from_address())?;
+        dict.set_item("to", self.to())?;
+        dict.set_item("value", self.value())?;
+        dict.set_item("gas", self.gas())?;
+        dict.set_item("maxFeePerGas", self.max_fee_per_gas())?;
+        dict.set_item("maxPriorityFeePerGas", self.max_priority_fee_per_gas())?;
+        let input = self.request().input.input().cloned().unwrap_or_default();
+        dict.set_item("data", hex::encode_prefixed(input))?;
+        Ok(dict)
+    }
+
+    /// The transaction as JSON-RPC request JSON, with hex quantities
+    pub fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(self.request())
+            .map_err(|e| PyValueError::new_err(format!("failed to serialize transaction: {}", e)))
+    }
+
+    /// Signs with a `Signer`, e.g. one wrapping a hardware wallet through the
+    /// signing protocol, and returns the raw transaction for `broadcast_signed`.
+    pub fn sign<'py>(
+        &self,
+        py: Python<'py>,
+        signer: AlkahestSigner,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        if self.request().from != Some(signer.address()) {
+            return Err(PyValueError::new_err(format!(
+                "transaction is from {}, not the signer's address {}",
+                self.from_address().unwrap_or_default(),
+                signer.address()
+            )));
+        }
+        let unsigned = self.unsigned.clone();
+        crate::runtime::future_into_py(py, async move {
+            unsigned.sign(&signer).await.map_err(map_eyre_to_pyerr)
+        })
+    }
+
+    /// Broadcasts the signed transaction and returns what the method that built
+    /// it would have returned once it's mined. If the method sends another
+    /// transaction after this one, that comes back unsigned instead.
+    pub fn broadcast_signed<'py>(
+        &self,
+        py: Python<'py>,
+        raw_tx: RawTx,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.unsigned
+            .check_signed(&raw_tx.0)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let tx = self.tx.clone();
+
+        let Some(call) = &self.call else {
+            return crate::runtime::future_into_py(py, async move {
+                let receipt = tx
+                    .broadcast_raw(&raw_tx.0)
+                    .await
+                    .map_err(map_eyre_to_pyerr)?;
+                Ok(PyTransactionReceipt::from(receipt))
+            });
+        };
+        if call.signed.lock().unwrap().is_none() {
+            return Err(already_broadcast());
+        }
+        let signed = call.signed.clone();
+        let result = call.result.clone();
+        let slot = call.slot.clone();
+        crate::runtime::future_into_py(py, async move {
+            let hash = tx.send_raw(&raw_tx.0).await.map_err(map_eyre_to_pyerr)?;
+            // Only a broadcast that reached the node uses up the handle, so a
+            // failed one can be retried
+            let Some(signed) = signed.lock().unwrap().take() else {
+                return Err(already_broadcast());
+            };
+            let next = slot.rearm();
+            signed.send(hash).map_err(|_| {
+                PyRuntimeError::new_err("the call this transaction belongs to ended")
+            })?;
+
+            tokio::select! {
+                Ok(Announcement::Unsigned(next)) = next => Python::with_gil(|py| {
+                    PyUnsignedTransaction::suspended(next, result, slot).into_py_any(py)
+                }),
+                result = result.clone() => Python::with_gil(|py| match &*result {
+                    Ok(value) => Ok(value.clone_ref(py)),
+                    Err(err) => Err(err.clone_ref(py)),
+                }),
+            }
+        })
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "UnsignedTransaction(to={:?}, nonce={:?}, gas={:?}, chain_id={:?})",
+            self.to(),
+            self.nonce(),
+            self.gas(),
+            self.chain_id()
+        )
+    }
+}
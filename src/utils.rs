@@ -1,7 +1,16 @@
-use crate::{types::PyDefaultExtensionConfig, PyAlkahestClient};
+use crate::{
+    error_handling::{map_eyre_to_pyerr, parse_address},
+    runtime::block_on,
+    signer::AlkahestSigner,
+    transport::{RetryConfig, RpcEndpoints},
+    tx::TxSender,
+    types::{PyDefaultExtensionConfig, PyU256},
+    PyAlkahestClient,
+};
 use alkahest_rs::{
     types::WalletProvider,
     utils::{setup_test_environment, MockAddresses, TestContext},
+    AlkahestClient,
 };
 use pyo3::{pyclass, pymethods, PyResult};
 
@@ -18,8 +27,8 @@ impl PyWalletProvider {
         py: pyo3::Python<'py>,
         seconds: u64,
     ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        use crate::runtime::future_into_py;
         use alloy::providers::ext::AnvilApi;
-        use pyo3_async_runtimes::tokio::future_into_py;
 
         let provider = self.inner.clone();
 
@@ -27,7 +36,66 @@ impl PyWalletProvider {
             provider
                 .anvil_increase_time(seconds)
                 .await
-                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+            Ok(())
+        })
+    }
+
+    pub fn anvil_mine<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        blocks: u64,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        use crate::runtime::future_into_py;
+        use alloy::providers::ext::AnvilApi;
+
+        let provider = self.inner.clone();
+
+        future_into_py(py, async move {
+            provider
+                .anvil_mine(Some(blocks), None)
+                .await
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+            Ok(())
+        })
+    }
+
+    pub fn anvil_set_balance<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        address: String,
+        balance: PyU256,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        use crate::runtime::future_into_py;
+        use alloy::providers::ext::AnvilApi;
+
+        let provider = self.inner.clone();
+
+        future_into_py(py, async move {
+            let address = parse_address(&address)?;
+            provider
+                .anvil_set_balance(address, balance.0)
+                .await
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
+            Ok(())
+        })
+    }
+
+    pub fn anvil_set_auto_mine<'py>(
+        &self,
+        py: pyo3::Python<'py>,
+        enabled: bool,
+    ) -> PyResult<pyo3::Bound<'py, pyo3::PyAny>> {
+        use crate::runtime::future_into_py;
+        use alloy::providers::ext::AnvilApi;
+
+        let provider = self.inner.clone();
+
+        future_into_py(py, async move {
+            provider
+                .anvil_set_auto_mine(enabled)
+                .await
+                .map_err(|e| map_eyre_to_pyerr(e.into()))?;
             Ok(())
         })
     }
@@ -66,7 +134,6 @@ impl From<&MockAddresses> for PyMockAddresses {
 #[pyclass]
 pub struct EnvTestManager {
     inner: TestContext, // Optional: keep TestContext for internal Rust usage
-    runtime: tokio::runtime::Runtime,
 
     #[pyo3(get)]
     pub rpc_url: String,
@@ -92,21 +159,38 @@ pub struct EnvTestManager {
 impl EnvTestManager {
     #[new]
     pub fn new() -> PyResult<Self> {
-        let rt = tokio::runtime::Runtime::new()?;
-        let ctx = rt
-            .block_on(setup_test_environment())
-            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+        let (ctx, alice_client, bob_client) = block_on(async {
+            let ctx = setup_test_environment().await?;
+            let rpc_url = RpcEndpoints::single(&ctx.anvil.ws_endpoint_url().to_string());
+            let retry = RetryConfig::default();
+            // The test clients get their own connections, like any other client,
+            // rather than sharing the ones the test context opened
+            let mut clients = Vec::with_capacity(2);
+            for signer in [&ctx.alice, &ctx.bob] {
+                let tx = TxSender::connect(AlkahestSigner::Local(signer.clone()), &rpc_url, &retry)
+                    .await?;
+                let client: AlkahestClient = AlkahestClient::from_providers(
+                    tx.alkahest_providers(),
+                    Some(ctx.addresses.clone()),
+                )
+                .await?;
+                clients.push(PyAlkahestClient::from_client(client, tx));
+            }
+            let bob_client = clients.pop().expect("two clients");
+            let alice_client = clients.pop().expect("two clients");
+            eyre::Ok((ctx, alice_client, bob_client))
+        })
+        .map_err(map_eyre_to_pyerr)?;
 
         Ok(Self {
-            runtime: rt,
             rpc_url: ctx.anvil.ws_endpoint_url().to_string(),
             god: ctx.god.address().to_string(),
             alice: ctx.alice.address().to_string(),
             bob: ctx.bob.address().to_string(),
             addresses: PyDefaultExtensionConfig::from(&ctx.addresses),
             mock_addresses: PyMockAddresses::from(&ctx.mock_addresses),
-            alice_client: PyAlkahestClient::from_client(ctx.alice_client.clone()),
-            bob_client: PyAlkahestClient::from_client(ctx.bob_client.clone()),
+            alice_client,
+            bob_client,
             god_wallet_provider: PyWalletProvider {
                 inner: ctx.god_provider.clone(),
             },